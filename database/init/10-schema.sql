@@ -1,9 +1,115 @@
 CREATE TABLE IF NOT EXISTS events (
   id UUID PRIMARY KEY,
+  provider TEXT,
+  base_plan_id TEXT,
+  plan_id TEXT,
   title TEXT NOT NULL,
   start_time TIMESTAMP WITH TIME ZONE NOT NULL,
   end_time TIMESTAMP WITH TIME ZONE NOT NULL,
-  min_price INTEGER NOT NULL,
-  max_price INTEGER NOT NULL
+  time_zone TEXT NOT NULL DEFAULT 'UTC',
+  min_price BIGINT NOT NULL,
+  max_price BIGINT NOT NULL,
+  currency CHAR(3) NOT NULL,
+  sell_mode TEXT NOT NULL CHECK (sell_mode IN ('online', 'offline')),
+  organizer_company_id TEXT,
+  sell_from TIMESTAMP WITH TIME ZONE NOT NULL,
+  sell_to TIMESTAMP WITH TIME ZONE NOT NULL,
+  sold_out BOOLEAN NOT NULL DEFAULT FALSE,
+  missing_runs INTEGER NOT NULL DEFAULT 0 CHECK (missing_runs >= 0),
+  cancelled_at TIMESTAMP WITH TIME ZONE,
+  -- Last time the event was inserted or any of its attributes changed
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  -- An event ending before it starts occupies its start time only
+  time_range TSTZRANGE GENERATED ALWAYS AS (tstzrange(start_time, GREATEST(start_time, end_time), '[]')) STORED,
+  CONSTRAINT events_external_id_key UNIQUE (provider, base_plan_id, plan_id)
 );
 
+CREATE INDEX IF NOT EXISTS events_start_time_id_idx ON events (start_time, id);
+CREATE INDEX IF NOT EXISTS events_time_range_idx ON events USING GIST (time_range);
+
+CREATE TABLE IF NOT EXISTS event_zones (
+  event_id UUID NOT NULL REFERENCES events (id) ON DELETE CASCADE,
+  position INTEGER NOT NULL,
+  zone_id TEXT NOT NULL,
+  name TEXT NOT NULL,
+  price BIGINT NOT NULL,
+  currency CHAR(3) NOT NULL,
+  capacity INTEGER NOT NULL CHECK (capacity >= 0),
+  numbered BOOLEAN NOT NULL,
+  PRIMARY KEY (event_id, position)
+);
+
+CREATE TABLE IF NOT EXISTS event_prices (
+  event_id UUID NOT NULL REFERENCES events (id) ON DELETE CASCADE,
+  observed_at TIMESTAMP WITH TIME ZONE NOT NULL,
+  min_price BIGINT NOT NULL,
+  max_price BIGINT NOT NULL,
+  currency CHAR(3) NOT NULL,
+  PRIMARY KEY (event_id, observed_at)
+);
+
+CREATE TABLE IF NOT EXISTS ingestion_runs (
+  id UUID PRIMARY KEY,
+  state TEXT NOT NULL CHECK (state IN ('running', 'succeeded', 'failed')),
+  provider TEXT,
+  started_at TIMESTAMP WITH TIME ZONE NOT NULL,
+  finished_at TIMESTAMP WITH TIME ZONE,
+  fetched INTEGER NOT NULL DEFAULT 0 CHECK (fetched >= 0),
+  inserted INTEGER NOT NULL DEFAULT 0 CHECK (inserted >= 0),
+  updated INTEGER NOT NULL DEFAULT 0 CHECK (updated >= 0),
+  skipped INTEGER NOT NULL DEFAULT 0 CHECK (skipped >= 0),
+  failed INTEGER NOT NULL DEFAULT 0 CHECK (failed >= 0),
+  cancelled INTEGER NOT NULL DEFAULT 0 CHECK (cancelled >= 0),
+  not_modified INTEGER NOT NULL DEFAULT 0 CHECK (not_modified >= 0),
+  rejected INTEGER NOT NULL DEFAULT 0 CHECK (rejected >= 0)
+);
+
+CREATE INDEX IF NOT EXISTS ingestion_runs_started_at_idx ON ingestion_runs (started_at DESC);
+
+CREATE TABLE IF NOT EXISTS event_changes (
+  id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+  event_id UUID NOT NULL REFERENCES events (id) ON DELETE CASCADE,
+  ingestion_run_id UUID NOT NULL REFERENCES ingestion_runs (id),
+  field TEXT NOT NULL,
+  old_value TEXT,
+  new_value TEXT,
+  changed_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS event_changes_event_id_idx ON event_changes (event_id, changed_at);
+
+CREATE TABLE IF NOT EXISTS ingestion_rejections (
+  id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+  ingestion_run_id UUID NOT NULL REFERENCES ingestion_runs (id) ON DELETE CASCADE,
+  provider TEXT NOT NULL,
+  base_plan_id TEXT,
+  plan_id TEXT,
+  zone_id TEXT,
+  reason TEXT NOT NULL,
+  rejected_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS ingestion_rejections_ingestion_run_id_idx ON ingestion_rejections (ingestion_run_id, id);
+
+CREATE TABLE IF NOT EXISTS event_dead_letters (
+  id UUID PRIMARY KEY,
+  ingestion_run_id UUID NOT NULL REFERENCES ingestion_runs (id) ON DELETE CASCADE,
+  provider TEXT,
+  base_plan_id TEXT,
+  plan_id TEXT,
+  payload JSONB NOT NULL,
+  error TEXT NOT NULL,
+  attempts INTEGER NOT NULL CHECK (attempts > 0),
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+  last_failed_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS event_dead_letters_created_at_idx ON event_dead_letters (created_at, id);
+CREATE UNIQUE INDEX IF NOT EXISTS event_dead_letters_external_id_key ON event_dead_letters (provider, base_plan_id, plan_id);
+
+CREATE TABLE IF NOT EXISTS provider_feed_validators (
+  provider TEXT PRIMARY KEY,
+  etag TEXT,
+  last_modified TEXT,
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
@@ -1,5 +1,5 @@
-INSERT INTO events (id, title, start_time, end_time, min_price, max_price)
+INSERT INTO events (id, title, start_time, end_time, time_zone, min_price, max_price, currency, sell_mode, organizer_company_id, sell_from, sell_to, sold_out)
 VALUES 
-  ('3fa85f64-5717-4562-b3fc-2c963f66afa6'::UUID, 'Quevedo', '2025-11-12 22:00:00.000Z', '2025-11-12 23:00:00.000Z', 1599, 3999),
-  ('e762a900-93fc-4b71-bda9-ab81997ad262'::UUID,  'Nirvana', '2025-10-31 16:30:00.000Z', '2025-10-31 23:59:59.000Z', 7500, 9999),
-  ('9765b4d4-ad7a-4672-a7a8-527bbec661b0'::UUID, 'Tool', '2025-12-24 21:00:00.000Z', '2025-12-24 23:45:00.000Z', 19999, 19999);
\ No newline at end of file
+  ('3fa85f64-5717-4562-b3fc-2c963f66afa6'::UUID, 'Quevedo', '2025-11-12 22:00:00.000Z', '2025-11-12 23:00:00.000Z', 'Europe/Madrid', 1599, 3999, 'EUR', 'online', NULL, '2025-06-01 00:00:00.000Z', '2025-11-12 20:00:00.000Z', FALSE),
+  ('e762a900-93fc-4b71-bda9-ab81997ad262'::UUID,  'Nirvana', '2025-10-31 16:30:00.000Z', '2025-10-31 23:59:59.000Z', 'Europe/Madrid', 7500, 9999, 'EUR', 'online', '1', '2025-05-01 00:00:00.000Z', '2025-10-31 14:00:00.000Z', TRUE),
+  ('9765b4d4-ad7a-4672-a7a8-527bbec661b0'::UUID, 'Tool', '2025-12-24 21:00:00.000Z', '2025-12-24 23:45:00.000Z', 'Europe/Madrid', 19999, 19999, 'EUR', 'offline', '2', '2025-09-01 00:00:00.000Z', '2025-12-24 19:00:00.000Z', FALSE);
\ No newline at end of file
@@ -1,18 +1,138 @@
-use std::f64;
-
 use anyhow::Result;
 use chrono::DateTime;
 use chrono::Utc;
+use chrono_tz::Tz;
+use futures::TryFutureExt;
+use futures::stream::{self, Stream};
+
+use crate::domain::event::{ExternalId, SellMode};
+use crate::domain::money::Money;
+use crate::domain::zone::Zone;
 
 pub trait EventProviderClient {
+    /// Name of the provider, which every event it yields is tagged with as its source
+    fn name(&self) -> &str;
+    /// State of the circuit breaker guarding requests to the provider, or `None` when they are not
+    /// guarded by one
+    fn circuit_state(&self) -> Option<CircuitState> {
+        None
+    }
     fn fetch_events(&self) -> impl std::future::Future<Output = Result<Vec<ProviderEvent>>> + Send;
+    /// Yields provider events in chunks as they are downloaded, so callers can process feeds of any
+    /// size with bounded memory. Defaults to a single chunk holding the whole fetch.
+    fn stream_events(&self) -> impl Stream<Item = Result<FeedChunk>> + Send
+    where
+        Self: Sync,
+    {
+        stream::once(self.fetch_events().map_ok(FeedChunk::from))
+    }
+    /// Streams the events unless the provider reports the feed unchanged since it was fetched with
+    /// the given validators. Defaults to always streaming, without validators.
+    fn stream_events_if_modified(
+        &self,
+        _validators: &FeedValidators,
+    ) -> impl std::future::Future<
+        Output = Result<ConditionalFetch<impl Stream<Item = Result<FeedChunk>> + Send>>,
+    > + Send
+    where
+        Self: Sync,
+    {
+        async {
+            Ok(ConditionalFetch::Modified {
+                events: self.stream_events(),
+                validators: FeedValidators::default(),
+            })
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests flow normally
+    Closed,
+    /// Requests fail fast without reaching the provider
+    Open,
+    /// The open period is over and the next request is a trial
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half_open",
+        }
+    }
+}
+
+/// Part of a provider feed: the events mapped from it and the items that had to be rejected
+#[derive(Debug, Default, PartialEq)]
+pub struct FeedChunk {
+    pub events: Vec<ProviderEvent>,
+    pub rejections: Vec<ProviderRejection>,
+}
+
+impl From<Vec<ProviderEvent>> for FeedChunk {
+    fn from(events: Vec<ProviderEvent>) -> Self {
+        Self {
+            events,
+            rejections: Vec::new(),
+        }
+    }
+}
+
+/// Feed item that could not be mapped to an event, identified as far as the feed allows
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProviderRejection {
+    pub base_plan_id: Option<String>,
+    pub plan_id: Option<String>,
+    pub zone_id: Option<String>,
+    pub reason: String,
+}
+
+/// Cache validators the provider sent along a feed, to ask it whether the feed has changed since
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FeedValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+/// Outcome of fetching a feed only if it has changed
+pub enum ConditionalFetch<S> {
+    NotModified,
+    Modified {
+        events: S,
+        /// Validators of the fetched feed, to send on the next fetch
+        validators: FeedValidators,
+    },
+}
+
+impl<S> ConditionalFetch<S> {
+    pub fn map_events<U>(self, f: impl FnOnce(S) -> U) -> ConditionalFetch<U> {
+        match self {
+            Self::NotModified => ConditionalFetch::NotModified,
+            Self::Modified { events, validators } => ConditionalFetch::Modified {
+                events: f(events),
+                validators,
+            },
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
 pub struct ProviderEvent {
+    pub external_id: ExternalId,
     pub title: String,
     pub start_time: DateTime<Utc>,
     pub end_time: DateTime<Utc>,
-    pub min_price: f64,
-    pub max_price: f64,
+    pub time_zone: Tz,
+    pub min_price: Money,
+    pub max_price: Money,
+    pub sell_mode: SellMode,
+    pub organizer_company_id: Option<String>,
+    pub sell_from: DateTime<Utc>,
+    pub sell_to: DateTime<Utc>,
+    pub sold_out: bool,
+    pub zones: Vec<Zone>,
 }
@@ -1,33 +1,391 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use uuid::Uuid;
 
-use crate::domain::event::Event;
+use super::provider::FeedValidators;
+use crate::domain::event::{Event, ExternalId, SellMode};
+use crate::domain::event_change::EventChange;
+use crate::domain::ingestion_run::{IngestionRejection, IngestionRun};
+use crate::domain::money::Money;
+use crate::domain::price_point::PricePoint;
+use crate::domain::zone::Zone;
 
 #[allow(dead_code)]
 pub trait EventRepository {
     async fn find_all(&self) -> Result<Vec<Event>>;
-    async fn find_by_id(&self, id: &Uuid) -> Result<Option<Event>>;
-    fn find_by_title(
+    fn find_by_id(
         &self,
-        title: &str,
+        id: &Uuid,
     ) -> impl std::future::Future<Output = Result<Option<Event>>> + Send;
-    fn find_between(
+    /// When the event with the external id was last inserted or changed, or `None` if not stored
+    fn find_updated_at(
+        &self,
+        external_id: &ExternalId,
+    ) -> impl std::future::Future<Output = Result<Option<DateTime<Utc>>>> + Send;
+    /// Page of the events meeting the criteria, in the sort order
+    fn find_matching(
         &self,
-        start_time: DateTime<Utc>,
-        end_time: DateTime<Utc>,
+        criteria: &EventSearchCriteria,
+        sort: &EventSort,
+        page: &EventPage,
         limit: u64,
-        offset: u64,
     ) -> impl std::future::Future<Output = Result<Vec<Event>>> + Send;
-    fn save(&self, e: SaveEventRequest) -> impl std::future::Future<Output = Result<Event>> + Send;
-    fn upsert(&self, entity: Event) -> impl std::future::Future<Output = Result<Event>> + Send;
+    /// Counts the events meeting the criteria exactly up to `exact_limit`, and estimates larger
+    /// counts
+    fn count_matching(
+        &self,
+        criteria: &EventSearchCriteria,
+        exact_limit: u64,
+    ) -> impl std::future::Future<Output = Result<EventCount>> + Send;
+    /// Inserts or updates events by external id in a single transaction, reporting an outcome per
+    /// request in input order. External ids must be unique within the batch.
+    /// Identical events are left untouched, and the changed attributes of updated ones are recorded
+    /// in their history as changes of the given ingestion run.
+    fn bulk_upsert(
+        &self,
+        requests: Vec<SaveEventRequest>,
+        ingestion_run_id: Uuid,
+    ) -> impl std::future::Future<Output = Result<Vec<BulkUpsertResult>>> + Send;
+    /// Counts one more missed run for the provider's live events absent from `present_external_ids`
+    /// and cancels those missing for `cancel_after_missing_runs` consecutive runs, returning how many
+    /// got cancelled
+    fn mark_missing_events(
+        &self,
+        provider: &str,
+        present_external_ids: &[ExternalId],
+        cancel_after_missing_runs: u32,
+        ingestion_run_id: Uuid,
+    ) -> impl std::future::Future<Output = Result<u64>> + Send;
+    fn find_zones_by_event_id(
+        &self,
+        event_id: &Uuid,
+    ) -> impl std::future::Future<Output = Result<Vec<Zone>>> + Send;
+    fn find_zones_by_event_ids(
+        &self,
+        event_ids: &[Uuid],
+    ) -> impl std::future::Future<Output = Result<HashMap<Uuid, Vec<Zone>>>> + Send;
+    /// Prices observed within the optional bounds, oldest first
+    fn find_prices_by_event_id(
+        &self,
+        event_id: &Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> impl std::future::Future<Output = Result<Vec<PricePoint>>> + Send;
+    /// Oldest changes first
+    fn find_changes_by_event_id(
+        &self,
+        event_id: &Uuid,
+    ) -> impl std::future::Future<Output = Result<Vec<EventChange>>> + Send;
+}
+
+pub trait IngestionRunRepository {
+    /// Exclusive ingestion lock shared by every instance of the service, released when dropped
+    type IngestionLock: Send + 'static;
+
+    fn try_acquire_ingestion_lock(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Option<Self::IngestionLock>>> + Send;
+    fn save_run(
+        &self,
+        run: IngestionRun,
+    ) -> impl std::future::Future<Output = Result<IngestionRun>> + Send;
+    fn find_run_by_id(
+        &self,
+        id: &Uuid,
+    ) -> impl std::future::Future<Output = Result<Option<IngestionRun>>> + Send;
+    fn find_running_run(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Option<IngestionRun>>> + Send;
+    /// Fails the runs still marked as running, returning how many there were. Only to be called
+    /// while holding the ingestion lock, when any such run was left behind by a stopped instance.
+    fn fail_running_runs(&self) -> impl std::future::Future<Output = Result<u64>> + Send;
+    /// Most recently started runs first
+    fn find_runs(
+        &self,
+        limit: u64,
+        offset: u64,
+    ) -> impl std::future::Future<Output = Result<Vec<IngestionRun>>> + Send;
+    /// Validators of the last feed of the provider that was fully ingested
+    fn find_feed_validators(
+        &self,
+        provider: &str,
+    ) -> impl std::future::Future<Output = Result<Option<FeedValidators>>> + Send;
+    fn save_feed_validators(
+        &self,
+        provider: &str,
+        validators: &FeedValidators,
+    ) -> impl std::future::Future<Output = Result<()>> + Send;
+    fn save_rejections(
+        &self,
+        rejections: Vec<IngestionRejection>,
+    ) -> impl std::future::Future<Output = Result<()>> + Send;
+    /// Rejections of the run in the order they were recorded
+    fn find_rejections_by_run_id(
+        &self,
+        run_id: &Uuid,
+        limit: u64,
+        offset: u64,
+    ) -> impl std::future::Future<Output = Result<Vec<IngestionRejection>>> + Send;
+    /// Replaces any pending dead letter of the same event, keeping its id and creation time and
+    /// adding up the attempts
+    fn save_dead_letters(
+        &self,
+        dead_letters: Vec<DeadLetter>,
+    ) -> impl std::future::Future<Output = Result<()>> + Send;
+    fn find_dead_letter_by_id(
+        &self,
+        id: &Uuid,
+    ) -> impl std::future::Future<Output = Result<Option<DeadLetter>>> + Send;
+    /// Oldest dead letters first
+    fn find_dead_letters(
+        &self,
+        limit: u64,
+        offset: u64,
+    ) -> impl std::future::Future<Output = Result<Vec<DeadLetter>>> + Send;
+    /// Counts one more failed attempt to write the dead letter, keeping the error of the last one
+    fn record_dead_letter_failure(
+        &self,
+        id: &Uuid,
+        error: &str,
+    ) -> impl std::future::Future<Output = Result<Option<DeadLetter>>> + Send;
+    fn delete_dead_letter(&self, id: &Uuid)
+    -> impl std::future::Future<Output = Result<()>> + Send;
+    /// Drops the dead letters of events that have been written since they failed
+    fn delete_dead_letters_by_external_ids(
+        &self,
+        external_ids: &[ExternalId],
+    ) -> impl std::future::Future<Output = Result<u64>> + Send;
+}
+
+/// How the time of an event relates to the searched datetimes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimeMatch {
+    /// The event starts and ends within them
+    #[default]
+    Within,
+    /// The event takes place at some point within them
+    Overlaps,
+    /// The event starts within them
+    StartsIn,
+}
+
+/// Conditions that every searched event must meet
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventSearchCriteria {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub time_match: TimeMatch,
+    pub include_cancelled: bool,
+    /// Only events whose minimum price is lower than it was at that time
+    pub price_dropped_since: Option<DateTime<Utc>>,
+    /// Text that the title contains, ignoring case
+    pub title_contains: Option<String>,
+    /// Only events in the bound's currency with some price at or above it
+    pub min_price: Option<Money>,
+    /// Only events in the bound's currency with some price at or below it
+    pub max_price: Option<Money>,
+    pub sold_out: Option<bool>,
+    pub sell_mode: Option<SellMode>,
+}
+
+impl EventSearchCriteria {
+    /// Live events occurring within the datetimes, with no further conditions
+    pub fn between(start_time: DateTime<Utc>, end_time: DateTime<Utc>) -> Self {
+        Self {
+            start_time,
+            end_time,
+            time_match: TimeMatch::Within,
+            include_cancelled: false,
+            price_dropped_since: None,
+            title_contains: None,
+            min_price: None,
+            max_price: None,
+            sold_out: None,
+            sell_mode: None,
+        }
+    }
+
+    pub fn matching_time(self, time_match: TimeMatch) -> Self {
+        Self { time_match, ..self }
+    }
+
+    pub fn including_cancelled(self, include_cancelled: bool) -> Self {
+        Self {
+            include_cancelled,
+            ..self
+        }
+    }
+
+    pub fn with_price_dropped_since(self, price_dropped_since: Option<DateTime<Utc>>) -> Self {
+        Self {
+            price_dropped_since,
+            ..self
+        }
+    }
+
+    pub fn with_title_containing(self, title_contains: Option<String>) -> Self {
+        Self {
+            title_contains,
+            ..self
+        }
+    }
+
+    pub fn with_price_between(self, min_price: Option<Money>, max_price: Option<Money>) -> Self {
+        Self {
+            min_price,
+            max_price,
+            ..self
+        }
+    }
+
+    pub fn with_sold_out(self, sold_out: Option<bool>) -> Self {
+        Self { sold_out, ..self }
+    }
+
+    pub fn with_sell_mode(self, sell_mode: Option<SellMode>) -> Self {
+        Self { sell_mode, ..self }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventSortField {
+    StartTime,
+    /// Compared by amount in minor units, whatever the currency
+    MinPrice,
+    /// Compared by amount in minor units, whatever the currency
+    MaxPrice,
+    Title,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventSortKey {
+    pub field: EventSortField,
+    pub descending: bool,
+}
+
+/// Order of searched events, by each key in turn and finally by id
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EventSort(pub Vec<EventSortKey>);
+
+impl Default for EventSort {
+    /// Earliest events first
+    fn default() -> Self {
+        Self(vec![EventSortKey {
+            field: EventSortField::StartTime,
+            descending: false,
+        }])
+    }
+}
+
+impl EventSort {
+    pub fn compare(&self, a: &EventKey, b: &EventKey) -> Ordering {
+        self.0
+            .iter()
+            .map(|key| {
+                let ordering = match key.field {
+                    EventSortField::StartTime => a.start_time.cmp(&b.start_time),
+                    EventSortField::MinPrice => a.min_price.cmp(&b.min_price),
+                    EventSortField::MaxPrice => a.max_price.cmp(&b.max_price),
+                    EventSortField::Title => a.title.cmp(&b.title),
+                };
+                if key.descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| a.id.cmp(&b.id))
+    }
+}
+
+/// Values of an event that search results can be sorted by, locating it in any sort order
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EventKey {
+    pub start_time: DateTime<Utc>,
+    /// Amount in minor units
+    pub min_price: i64,
+    /// Amount in minor units
+    pub max_price: i64,
+    pub title: String,
+    pub id: Uuid,
+}
+
+impl From<&Event> for EventKey {
+    fn from(value: &Event) -> Self {
+        Self {
+            start_time: value.start_time,
+            min_price: value.min_price.amount_in_minor_units(),
+            max_price: value.max_price.amount_in_minor_units(),
+            title: value.title.clone(),
+            id: value.id,
+        }
+    }
+}
+
+/// Number of events meeting search criteria
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventCount {
+    Exact(u64),
+    /// Approximation of a count too large to be worth counting exactly
+    Estimated(u64),
+}
+
+/// Where a page of searched events starts
+#[derive(Clone, Debug, PartialEq)]
+pub enum EventPage {
+    /// After skipping that many events
+    Offset(u64),
+    /// Right after the event with the key
+    After(EventKey),
+    /// Right before the event with the key, still in the sort order
+    Before(EventKey),
+}
+
+/// Event write that failed during ingestion, kept so that it can be replayed
+#[derive(Clone)]
+pub struct DeadLetter {
+    pub id: Uuid,
+    pub ingestion_run_id: Uuid,
+    pub request: SaveEventRequest,
+    /// Error of the last failed attempt
+    pub error: String,
+    /// Failed attempts to write the event, including the one during ingestion
+    pub attempts: u32,
+    pub created_at: DateTime<Utc>,
+    pub last_failed_at: DateTime<Utc>,
 }
 
 #[derive(Clone)]
 pub struct SaveEventRequest {
+    pub external_id: Option<ExternalId>,
     pub title: String,
     pub start_time: DateTime<Utc>,
     pub end_time: DateTime<Utc>,
-    pub min_price: f64,
-    pub max_price: f64,
+    pub time_zone: Tz,
+    pub min_price: Money,
+    pub max_price: Money,
+    pub sell_mode: SellMode,
+    pub organizer_company_id: Option<String>,
+    pub sell_from: DateTime<Utc>,
+    pub sell_to: DateTime<Utc>,
+    pub sold_out: bool,
+    pub zones: Vec<Zone>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpsertOutcome {
+    Inserted,
+    Updated,
+    Unchanged,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BulkUpsertResult {
+    pub event_id: Uuid,
+    pub outcome: UpsertOutcome,
 }
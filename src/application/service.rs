@@ -1,49 +1,235 @@
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
 use chrono::{DateTime, Utc};
-use log::{error, info};
+use futures::{TryStreamExt, pin_mut};
+use log::{error, info, warn};
+use uuid::Uuid;
 
-use super::ports::provider::{EventProviderClient, ProviderEvent};
-use super::ports::repository::{EventRepository, SaveEventRequest};
-use crate::domain::event::Event;
+use super::ports::provider::{
+    CircuitState, ConditionalFetch, EventProviderClient, FeedChunk, ProviderEvent,
+    ProviderRejection,
+};
+use super::ports::repository::{
+    BulkUpsertResult, DeadLetter, EventCount, EventKey, EventPage, EventRepository,
+    EventSearchCriteria, EventSort, IngestionRunRepository, SaveEventRequest, TimeMatch,
+    UpsertOutcome,
+};
+use crate::domain::event::{Event, ExternalId, SellMode};
+use crate::domain::event_change::EventChange;
+use crate::domain::ingestion_run::{IngestionRejection, IngestionRun, IngestionRunState};
+use crate::domain::money::Money;
+use crate::domain::price_point::PricePoint;
+use crate::domain::zone::Zone;
 
 pub struct SearchEventService<T: EventRepository> {
     event_repository: Arc<T>,
+    /// Totals above it are estimated rather than counted
+    exact_total_limit: u64,
 }
 
 impl<T: EventRepository> SearchEventService<T> {
     pub fn new(event_repository: Arc<T>) -> Self {
-        Self { event_repository }
+        Self {
+            event_repository,
+            exact_total_limit: 10_000,
+        }
+    }
+
+    pub fn with_exact_total_limit(self, exact_total_limit: u64) -> Self {
+        Self {
+            exact_total_limit,
+            ..self
+        }
     }
 
     pub async fn search_events(
         &self,
-        start_time: DateTime<Utc>,
-        end_time: DateTime<Utc>,
-        limit: u64,
-        offset: u64,
+        request: SearchEventServiceRequest,
     ) -> Result<SearchEventServiceResponse, SearchEventServiceError> {
-        let events = self
+        let SearchEventServiceRequest {
+            start_time,
+            end_time,
+            time_match,
+            limit,
+            page,
+            sort,
+            include_zones,
+            include_total,
+            include_cancelled,
+            price_dropped_since,
+            title_contains,
+            min_price,
+            max_price,
+            sold_out,
+            sell_mode,
+        } = request;
+        let criteria = EventSearchCriteria::between(start_time, end_time)
+            .matching_time(time_match)
+            .including_cancelled(include_cancelled)
+            .with_price_dropped_since(price_dropped_since)
+            .with_title_containing(title_contains)
+            .with_price_between(min_price, max_price)
+            .with_sold_out(sold_out)
+            .with_sell_mode(sell_mode);
+        // One more event than requested tells whether there is a further page
+        let mut events = self
             .event_repository
-            .find_between(start_time, end_time, limit, offset)
+            .find_matching(&criteria, &sort, &page, limit.saturating_add(1))
             .await
-            .inspect_err(|e| {
-                error!("Error searching events between {start_time:?} and {end_time:?}: {e:?}")
-            })?;
+            .inspect_err(|e| error!("Error searching events matching {criteria:?}: {e:?}"))?;
+        let has_more = events.len() as u64 > limit;
+        if has_more {
+            match page {
+                EventPage::Before(_) => {
+                    events.remove(0);
+                }
+                EventPage::Offset(_) | EventPage::After(_) => events.truncate(events.len() - 1),
+            }
+        }
+        let first = events.first().map(EventKey::from);
+        let last = events.last().map(EventKey::from);
+        let (next_page, prev_page) = match &page {
+            EventPage::Offset(offset) => (last.filter(|_| has_more), first.filter(|_| *offset > 0)),
+            EventPage::After(_) => (last.filter(|_| has_more), first),
+            EventPage::Before(_) => (last, first.filter(|_| has_more)),
+        };
+
+        let total = if include_total {
+            let total = self
+                .event_repository
+                .count_matching(&criteria, self.exact_total_limit)
+                .await
+                .inspect_err(|e| error!("Error counting events matching {criteria:?}: {e:?}"))?;
+            Some(total)
+        } else {
+            None
+        };
+
+        let zones = if include_zones {
+            let event_ids: Vec<Uuid> = events.iter().map(|e| e.id).collect();
+            let zones = self
+                .event_repository
+                .find_zones_by_event_ids(&event_ids)
+                .await
+                .inspect_err(|e| error!("Error finding zones of searched events: {e:?}"))?;
+            Some(zones)
+        } else {
+            None
+        };
 
         Ok(SearchEventServiceResponse {
             events,
+            zones,
             limit,
-            offset,
+            offset: match page {
+                EventPage::Offset(offset) => offset,
+                EventPage::After(_) | EventPage::Before(_) => 0,
+            },
+            next_page: next_page.map(EventPage::After),
+            prev_page: prev_page.map(EventPage::Before),
+            total,
         })
     }
+
+    pub async fn find_event_zones(
+        &self,
+        event_id: Uuid,
+    ) -> Result<Option<Vec<Zone>>, SearchEventServiceError> {
+        let event = self
+            .event_repository
+            .find_by_id(&event_id)
+            .await
+            .inspect_err(|e| error!("Error finding event {event_id}: {e:?}"))?;
+        if event.is_none() {
+            return Ok(None);
+        }
+
+        let zones = self
+            .event_repository
+            .find_zones_by_event_id(&event_id)
+            .await
+            .inspect_err(|e| error!("Error finding zones of event {event_id}: {e:?}"))?;
+
+        Ok(Some(zones))
+    }
+
+    /// Price history of an event, or `None` when the event does not exist
+    pub async fn find_event_prices(
+        &self,
+        event_id: Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Option<Vec<PricePoint>>, SearchEventServiceError> {
+        let event = self
+            .event_repository
+            .find_by_id(&event_id)
+            .await
+            .inspect_err(|e| error!("Error finding event {event_id}: {e:?}"))?;
+        if event.is_none() {
+            return Ok(None);
+        }
+
+        let prices = self
+            .event_repository
+            .find_prices_by_event_id(&event_id, from, to)
+            .await
+            .inspect_err(|e| error!("Error finding prices of event {event_id}: {e:?}"))?;
+
+        Ok(Some(prices))
+    }
+
+    pub async fn find_event_history(
+        &self,
+        event_id: Uuid,
+    ) -> Result<Option<Vec<EventChange>>, SearchEventServiceError> {
+        let event = self
+            .event_repository
+            .find_by_id(&event_id)
+            .await
+            .inspect_err(|e| error!("Error finding event {event_id}: {e:?}"))?;
+        if event.is_none() {
+            return Ok(None);
+        }
+
+        let changes = self
+            .event_repository
+            .find_changes_by_event_id(&event_id)
+            .await
+            .inspect_err(|e| error!("Error finding history of event {event_id}: {e:?}"))?;
+
+        Ok(Some(changes))
+    }
+}
+
+pub struct SearchEventServiceRequest {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub time_match: TimeMatch,
+    pub limit: u64,
+    pub page: EventPage,
+    pub sort: EventSort,
+    pub include_zones: bool,
+    pub include_total: bool,
+    pub include_cancelled: bool,
+    /// Only events whose minimum price has fallen since then
+    pub price_dropped_since: Option<DateTime<Utc>>,
+    pub title_contains: Option<String>,
+    pub min_price: Option<Money>,
+    pub max_price: Option<Money>,
+    pub sold_out: Option<bool>,
+    pub sell_mode: Option<SellMode>,
 }
 
 pub struct SearchEventServiceResponse {
     pub events: Vec<Event>,
+    pub zones: Option<HashMap<Uuid, Vec<Zone>>>,
     pub limit: u64,
     pub offset: u64,
+    pub next_page: Option<EventPage>,
+    pub prev_page: Option<EventPage>,
+    pub total: Option<EventCount>,
 }
 
 pub struct SearchEventServiceError;
@@ -54,96 +240,1769 @@ impl From<anyhow::Error> for SearchEventServiceError {
     }
 }
 
-pub struct IngestEventService<T: EventProviderClient, S: EventRepository> {
-    event_provider_client: Arc<T>,
+pub struct IngestEventService<T: EventProviderClient, S: EventRepository, R: IngestionRunRepository>
+{
+    event_provider_clients: Vec<Arc<T>>,
     event_repository: Arc<S>,
+    ingestion_run_repository: Arc<R>,
+    active_run_id: Arc<Mutex<Option<Uuid>>>,
+    settings: IngestionSettings,
 }
 
-impl<T: EventProviderClient + Sync + Send + 'static, S: EventRepository + Sync + Send + 'static>
-    IngestEventService<T, S>
+impl<
+    T: EventProviderClient + Sync + Send + 'static,
+    S: EventRepository + Sync + Send + 'static,
+    R: IngestionRunRepository + Sync + Send + 'static,
+> IngestEventService<T, S, R>
 {
-    pub fn new(event_provider_client: Arc<T>, event_repository: Arc<S>) -> Self {
+    pub fn new(
+        event_provider_clients: Vec<Arc<T>>,
+        event_repository: Arc<S>,
+        ingestion_run_repository: Arc<R>,
+        settings: IngestionSettings,
+    ) -> Self {
         Self {
-            event_provider_client,
+            event_provider_clients,
             event_repository,
+            ingestion_run_repository,
+            active_run_id: Arc::new(Mutex::new(None)),
+            settings,
         }
     }
 
-    pub async fn ingest_events(&self) -> Result<()> {
-        self.start_event_ingestion();
-        Ok(())
+    /// Starts a new ingestion run over the given provider, or every provider when `None`, unless
+    /// one is already in progress in this or another instance
+    pub async fn ingest_events(
+        &self,
+        provider: Option<&str>,
+    ) -> Result<IngestionRun, IngestEventServiceError> {
+        let event_provider_clients: Vec<Arc<T>> = match provider {
+            Some(provider) => {
+                let event_provider_client = self
+                    .event_provider_clients
+                    .iter()
+                    .find(|client| client.name() == provider)
+                    .ok_or_else(|| {
+                        IngestEventServiceError::UnknownProvider(provider.to_string())
+                    })?;
+                vec![event_provider_client.clone()]
+            }
+            None => self.event_provider_clients.clone(),
+        };
+        let run = IngestionRun::start(provider.map(String::from));
+        let active_run = {
+            let mut active_run_id = self
+                .active_run_id
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if let Some(id) = *active_run_id {
+                info!("Event ingestion requested while run {id} is in progress");
+                return Err(IngestEventServiceError::AlreadyRunning(Some(id)));
+            }
+            *active_run_id = Some(run.id);
+            ActiveRunGuard(self.active_run_id.clone())
+        };
+
+        let ingestion_lock = self.acquire_ingestion_lock().await.inspect_err(|e| {
+            if let IngestEventServiceError::AlreadyRunning(_) = e {
+                info!("Event ingestion requested while another instance is ingesting");
+            }
+        })?;
+
+        let run = self
+            .ingestion_run_repository
+            .save_run(run)
+            .await
+            .inspect_err(|e| error!("Error registering new ingestion run: {e:?}"))?;
+        self.start_event_ingestion(
+            run.clone(),
+            event_provider_clients,
+            ingestion_lock,
+            active_run,
+        );
+        Ok(run)
+    }
+
+    pub async fn find_ingestion_run(&self, id: Uuid) -> Result<Option<IngestionRun>> {
+        self.ingestion_run_repository
+            .find_run_by_id(&id)
+            .await
+            .inspect_err(|e| error!("Error finding ingestion run {id}: {e:?}"))
     }
 
-    fn start_event_ingestion(&self) {
-        let event_provider_client = self.event_provider_client.clone();
-        let event_repository = self.event_repository.clone();
+    /// Every configured provider, in the order their feeds are ingested
+    pub fn provider_statuses(&self) -> Vec<ProviderStatus> {
+        self.event_provider_clients
+            .iter()
+            .map(|client| ProviderStatus {
+                name: client.name().to_string(),
+                circuit_state: client.circuit_state(),
+            })
+            .collect()
+    }
 
-        tokio::spawn(async move {
-            // 1. Fetch event data from third-party event provider
-            info!("Fetching event data from provider...");
-            let provider_events = event_provider_client.fetch_events()
+    pub async fn find_ingestion_runs(&self, limit: u64, offset: u64) -> Result<Vec<IngestionRun>> {
+        self.ingestion_run_repository
+            .find_runs(limit, offset)
+            .await
+            .inspect_err(|e| error!("Error finding ingestion runs: {e:?}"))
+    }
+
+    /// Failed event writes awaiting replay, oldest first
+    pub async fn find_dead_letters(&self, limit: u64, offset: u64) -> Result<Vec<DeadLetter>> {
+        self.ingestion_run_repository
+            .find_dead_letters(limit, offset)
+            .await
+            .inspect_err(|e| error!("Error finding dead letters: {e:?}"))
+    }
+
+    /// Writes a dead letter to the event store again, or returns `None` when it does not exist.
+    /// Replays never overlap with an ingestion run, which may be writing the same events.
+    pub async fn replay_dead_letter(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<DeadLetterReplay>, IngestEventServiceError> {
+        let _ingestion_lock = self.acquire_ingestion_lock().await?;
+        let Some(dead_letter) = self
+            .ingestion_run_repository
+            .find_dead_letter_by_id(&id)
+            .await
+            .inspect_err(|e| error!("Error finding dead letter {id}: {e:?}"))?
+        else {
+            return Ok(None);
+        };
+
+        let provider = dead_letter
+            .request
+            .external_id
+            .as_ref()
+            .map(|external_id| external_id.provider.clone());
+        let mut replays = self.replay_in_run(vec![dead_letter], provider).await?;
+        Ok(replays.pop())
+    }
+
+    /// Writes up to `limit` dead letters to the event store again, oldest first
+    pub async fn replay_dead_letters(
+        &self,
+        limit: u64,
+    ) -> Result<Vec<DeadLetterReplay>, IngestEventServiceError> {
+        let _ingestion_lock = self.acquire_ingestion_lock().await?;
+        let dead_letters = self
+            .ingestion_run_repository
+            .find_dead_letters(limit, 0)
+            .await
+            .inspect_err(|e| error!("Error finding dead letters: {e:?}"))?;
+        if dead_letters.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Ok(self.replay_in_run(dead_letters, None).await?)
+    }
+
+    /// Replays dead letters in turn within a new ingestion run, which their writes are attributed
+    /// to and which tallies their outcomes
+    async fn replay_in_run(
+        &self,
+        dead_letters: Vec<DeadLetter>,
+        provider: Option<String>,
+    ) -> Result<Vec<DeadLetterReplay>> {
+        let mut run = self
+            .ingestion_run_repository
+            .save_run(IngestionRun::start(provider))
+            .await
+            .inspect_err(|e| error!("Error registering new ingestion run for replays: {e:?}"))?;
+        info!(
+            "Replaying {} dead letters in ingestion run {}",
+            dead_letters.len(),
+            run.id
+        );
+
+        let mut replays = Vec::with_capacity(dead_letters.len());
+        let mut outcome = Ok(());
+        for dead_letter in dead_letters {
+            match self.replay(dead_letter, &mut run).await {
+                Ok(replay) => replays.push(replay),
+                Err(error) => {
+                    outcome = Err(error);
+                    break;
+                }
+            }
+        }
+
+        run.finish(if outcome.is_ok() {
+            IngestionRunState::Succeeded
+        } else {
+            IngestionRunState::Failed
+        });
+        let run_id = run.id;
+        self.ingestion_run_repository
+            .save_run(run)
+            .await
+            .inspect_err(|e| error!("Error saving ingestion run {run_id} of replays: {e:?}"))
+            .ok();
+        outcome.map(|()| replays)
+    }
+
+    /// Upserts the event of a dead letter, dropping the dead letter once written or counting one
+    /// more failed attempt otherwise. A dead letter whose event has been written since it was
+    /// created is dropped without replaying it, as it would overwrite newer data.
+    async fn replay(
+        &self,
+        dead_letter: DeadLetter,
+        run: &mut IngestionRun,
+    ) -> Result<DeadLetterReplay> {
+        let id = dead_letter.id;
+        match self.write_dead_letter(&dead_letter, run.id).await {
+            Ok(written) => {
+                self.ingestion_run_repository
+                    .delete_dead_letter(&id)
+                    .await
+                    .inspect_err(|e| error!("Error deleting replayed dead letter {id}: {e:?}"))
+                    .ok();
+                let result = match written {
+                    Some(results) => {
+                        info!("Dead letter {id} replayed");
+                        results.into_iter().next()
+                    }
+                    None => {
+                        info!("Dead letter {id} superseded by a newer write of its event");
+                        None
+                    }
+                };
+                match result.as_ref().map(|result| result.outcome) {
+                    Some(UpsertOutcome::Inserted) => run.inserted += 1,
+                    Some(UpsertOutcome::Updated) => run.updated += 1,
+                    Some(UpsertOutcome::Unchanged) | None => run.skipped += 1,
+                }
+                Ok(DeadLetterReplay {
+                    superseded: result.is_none(),
+                    dead_letter,
+                    ingestion_run_id: run.id,
+                    result,
+                })
+            }
+            Err(error) => {
+                warn!("Replay of dead letter {id} failed: {error:?}");
+                run.failed += 1;
+                let dead_letter = self
+                    .ingestion_run_repository
+                    .record_dead_letter_failure(&id, &format!("{error:#}"))
+                    .await
+                    .inspect_err(|e| error!("Error recording failure of dead letter {id}: {e:?}"))?
+                    .unwrap_or(dead_letter);
+                Ok(DeadLetterReplay {
+                    dead_letter,
+                    ingestion_run_id: run.id,
+                    superseded: false,
+                    result: None,
+                })
+            }
+        }
+    }
+
+    /// Upserts the event of a dead letter as a write of the run, or returns `None` without
+    /// writing it when the stored event has been written since the dead letter was created
+    async fn write_dead_letter(
+        &self,
+        dead_letter: &DeadLetter,
+        ingestion_run_id: Uuid,
+    ) -> Result<Option<Vec<BulkUpsertResult>>> {
+        if let Some(external_id) = &dead_letter.request.external_id
+            && let Some(updated_at) = self.event_repository.find_updated_at(external_id).await?
+            && updated_at > dead_letter.created_at
+        {
+            return Ok(None);
+        }
+
+        self.event_repository
+            .bulk_upsert(vec![dead_letter.request.clone()], ingestion_run_id)
+            .await
+            .map(Some)
+    }
+
+    /// Takes the ingestion lock shared by every instance of the service, failing when a run holds it.
+    /// Runs still marked as running once it is taken were left behind by a stopped instance, so
+    /// they are failed.
+    async fn acquire_ingestion_lock(&self) -> Result<R::IngestionLock, IngestEventServiceError> {
+        if let Some(ingestion_lock) = self
+            .ingestion_run_repository
+            .try_acquire_ingestion_lock()
+            .await
+            .inspect_err(|e| error!("Error acquiring ingestion lock: {e:?}"))?
+        {
+            let abandoned = self
+                .ingestion_run_repository
+                .fail_running_runs()
                 .await
-                .inspect_err(|error| {
-                    error!(
-                        "Error fetching event data from provider: {error:?}.\n\nEvent data ingestion failed.",
-                    );
-            })?;
+                .inspect_err(|e| error!("Error failing abandoned ingestion runs: {e:?}"))?;
+            if abandoned > 0 {
+                warn!("Failed {abandoned} ingestion runs abandoned by stopped instances");
+            }
+            return Ok(ingestion_lock);
+        }
+
+        let running_run = self
+            .ingestion_run_repository
+            .find_running_run()
+            .await
+            .inspect_err(|e| error!("Error finding running ingestion run: {e:?}"))?;
+        Err(IngestEventServiceError::AlreadyRunning(
+            running_run.map(|r| r.id),
+        ))
+    }
+
+    /// Provider items the run rejected, or `None` when the run does not exist
+    pub async fn find_ingestion_rejections(
+        &self,
+        id: Uuid,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Option<Vec<IngestionRejection>>> {
+        if self.find_ingestion_run(id).await?.is_none() {
+            return Ok(None);
+        }
+
+        self.ingestion_run_repository
+            .find_rejections_by_run_id(&id, limit, offset)
+            .await
+            .inspect_err(|e| error!("Error finding rejections of ingestion run {id}: {e:?}"))
+            .map(Some)
+    }
+
+    fn start_event_ingestion(
+        &self,
+        mut run: IngestionRun,
+        event_provider_clients: Vec<Arc<T>>,
+        ingestion_lock: R::IngestionLock,
+        active_run: ActiveRunGuard,
+    ) {
+        let event_repository = self.event_repository.clone();
+        let ingestion_run_repository = self.ingestion_run_repository.clone();
+        let settings = self.settings;
 
-            // 2. Insert or update events in repository depending on ingestion criteria
+        tokio::spawn(async move {
+            let state = match ingest(
+                &event_provider_clients,
+                event_repository.as_ref(),
+                ingestion_run_repository.as_ref(),
+                settings,
+                &mut run,
+            )
+            .await
+            {
+                Ok(()) => IngestionRunState::Succeeded,
+                Err(_) => IngestionRunState::Failed,
+            };
+            run.finish(state);
             info!(
-                "Updating event store with provider data: {} entities to be processed",
-                provider_events.len()
+                "Ingestion run {} {}: {} fetched, {} inserted, {} updated, {} skipped, {} failed, {} cancelled, {} not modified, {} rejected",
+                run.id,
+                run.state,
+                run.fetched,
+                run.inserted,
+                run.updated,
+                run.skipped,
+                run.failed,
+                run.cancelled,
+                run.not_modified,
+                run.rejected
             );
-            for pe in provider_events {
-                // Ingestion skips individual entities when unexpected error happens in the repository
-                let res = event_repository.find_by_title(&pe.title).await;
-                if res.is_err() {
-                    res.inspect_err(|error| {
-                        error!("Error finding event by title in the event store: {error:?}")
-                    })
-                    .ok();
-                    continue;
+
+            ingestion_run_repository
+                .save_run(run)
+                .await
+                .inspect_err(|error| error!("Error recording finished ingestion run: {error:?}"))
+                .ok();
+            drop(ingestion_lock);
+            drop(active_run);
+        });
+    }
+}
+
+/// How requests to a provider are faring
+pub struct ProviderStatus {
+    pub name: String,
+    /// State of the circuit breaker guarding requests to the provider, if any
+    pub circuit_state: Option<CircuitState>,
+}
+
+/// Outcome of replaying a dead letter
+pub struct DeadLetterReplay {
+    /// The dead letter as of the end of the replay, with its attempts and last error
+    pub dead_letter: DeadLetter,
+    /// Ingestion run the replay is attributed to
+    pub ingestion_run_id: Uuid,
+    /// Whether the dead letter was dropped unwritten, its event having been written since
+    pub superseded: bool,
+    /// Result of the write, or `None` when it failed again or was superseded
+    pub result: Option<BulkUpsertResult>,
+}
+
+pub enum IngestEventServiceError {
+    AlreadyRunning(Option<Uuid>),
+    UnknownProvider(String),
+    Unexpected,
+}
+
+impl From<anyhow::Error> for IngestEventServiceError {
+    fn from(_value: anyhow::Error) -> Self {
+        Self::Unexpected
+    }
+}
+
+/// Clears the in-process active run marker when the run ends, even if its task panics
+struct ActiveRunGuard(Arc<Mutex<Option<Uuid>>>);
+
+impl Drop for ActiveRunGuard {
+    fn drop(&mut self) {
+        *self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+    }
+}
+
+/// Ingests the feed of every given provider, tallying the outcome on the run. A provider failing
+/// does not stop the others from being ingested, but fails the run.
+async fn ingest<T: EventProviderClient + Sync, S: EventRepository, R: IngestionRunRepository>(
+    event_provider_clients: &[Arc<T>],
+    event_repository: &S,
+    ingestion_run_repository: &R,
+    settings: IngestionSettings,
+    run: &mut IngestionRun,
+) -> Result<()> {
+    let mut failed_providers = Vec::new();
+    for event_provider_client in event_provider_clients {
+        let provider = event_provider_client.name();
+        if let Err(error) = ingest_provider(
+            event_provider_client.as_ref(),
+            event_repository,
+            ingestion_run_repository,
+            settings,
+            run,
+        )
+        .await
+        {
+            error!("Event data ingestion from provider {provider} failed: {error:?}");
+            failed_providers.push(provider);
+        }
+    }
+    if !failed_providers.is_empty() {
+        bail!(
+            "Failed to ingest events from providers {}",
+            failed_providers.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Fetches the events of a provider and writes them to the event store, unless its feed has not
+/// changed since it was last ingested
+async fn ingest_provider<
+    T: EventProviderClient + Sync,
+    S: EventRepository,
+    R: IngestionRunRepository,
+>(
+    event_provider_client: &T,
+    event_repository: &S,
+    ingestion_run_repository: &R,
+    settings: IngestionSettings,
+    run: &mut IngestionRun,
+) -> Result<()> {
+    let provider = event_provider_client.name();
+
+    // 1. Stream event data from third-party event provider, writing it in batches as it arrives
+    info!("Fetching event data from provider {provider}...");
+    let previous_validators = ingestion_run_repository
+        .find_feed_validators(provider)
+        .await
+        .context("Failed to find feed validators of provider")?
+        .unwrap_or_default();
+    let (provider_events, validators) = match event_provider_client
+        .stream_events_if_modified(&previous_validators)
+        .await
+        .context("Failed to fetch event data from provider")?
+    {
+        ConditionalFetch::NotModified => {
+            info!("Feed of provider {provider} has not changed: skipping event store update.");
+            run.not_modified += 1;
+            return Ok(());
+        }
+        ConditionalFetch::Modified { events, validators } => (events, validators),
+    };
+    pin_mut!(provider_events);
+    let failed_before = run.failed;
+    let batch_size = settings.batch_size.max(1);
+    let mut batch: Vec<SaveEventRequest> = Vec::with_capacity(batch_size);
+    let mut present_external_ids: HashSet<ExternalId> = HashSet::new();
+    // Rejected plans are still in the feed, so they must not be cancelled as missing from it
+    let mut rejected_external_ids: HashSet<ExternalId> = HashSet::new();
+    let mut unidentified_rejections = false;
+    while let Some(FeedChunk { events, rejections }) = provider_events
+        .try_next()
+        .await
+        .context("Failed to fetch event data from provider")?
+    {
+        run.fetched += u32::try_from(events.len()).unwrap_or(u32::MAX);
+        for rejection in &rejections {
+            match (&rejection.base_plan_id, &rejection.plan_id) {
+                (Some(base_plan_id), Some(plan_id)) => {
+                    rejected_external_ids.insert(ExternalId {
+                        provider: provider.to_string(),
+                        base_plan_id: base_plan_id.clone(),
+                        plan_id: plan_id.clone(),
+                    });
                 }
+                _ => unidentified_rejections = true,
+            }
+        }
+        if !rejections.is_empty() {
+            record_rejections(ingestion_run_repository, provider, rejections, run).await;
+        }
+        for pe in events {
+            // The first occurrence of a plan in the feed wins over later duplicates
+            if !present_external_ids.insert(pe.external_id.clone()) {
+                warn!("Skipping duplicated provider event {:?}", pe.external_id);
+                run.skipped += 1;
+                continue;
+            }
+            batch.push(pe.into());
+            if batch.len() >= batch_size {
+                write_batch(
+                    event_repository,
+                    ingestion_run_repository,
+                    std::mem::take(&mut batch),
+                    run,
+                )
+                .await;
+            }
+        }
+    }
+    if !batch.is_empty() {
+        write_batch(event_repository, ingestion_run_repository, batch, run).await;
+    }
+    present_external_ids.extend(rejected_external_ids);
+    if present_external_ids.is_empty() {
+        // An empty feed is more likely a provider hiccup than every event being cancelled
+        warn!("Provider {provider} returned no events: skipping event store reconciliation.");
+        return Ok(());
+    }
+    if unidentified_rejections {
+        // The plans such rejections stand for cannot be told apart from the ones gone from the feed
+        warn!(
+            "Provider {provider} returned items rejected without a plan id: skipping event store reconciliation."
+        );
+        return Ok(());
+    }
 
-                if let Some(mut e) = res.unwrap() {
-                    // Upsert
-                    e.start_time = pe.start_time;
-                    e.end_time = pe.end_time;
-                    e.min_price = pe.min_price;
-                    e.max_price = pe.max_price;
-                    event_repository
-                        .upsert(e)
-                        .await
-                        .inspect_err(|error| {
-                            error!("Error upserting event in event store: {error:?}")
-                        })
-                        .ok();
-                } else {
-                    // Save
-                    event_repository
-                        .save(pe.into())
-                        .await
-                        .inspect_err(|error| {
-                            error!("Error saving new event in event store: {error:?}")
-                        })
-                        .ok();
+    // 2. Cancel stored events that have been missing from the complete feed for too long
+    let external_ids: Vec<ExternalId> = present_external_ids.into_iter().collect();
+    let cancelled = event_repository
+        .mark_missing_events(
+            provider,
+            &external_ids,
+            settings.cancel_after_missing_runs,
+            run.id,
+        )
+        .await
+        .context("Failed to reconcile events missing from provider")?;
+    run.cancelled += u32::try_from(cancelled).unwrap_or(u32::MAX);
+    info!("Event store update from provider {provider} finished.");
+
+    // 3. Remember the feed only once all of it is stored, so a partly written feed gets fetched again
+    if run.failed == failed_before && validators != previous_validators {
+        ingestion_run_repository
+            .save_feed_validators(provider, &validators)
+            .await
+            .inspect_err(|e| error!("Error saving feed validators of provider {provider}: {e:?}"))
+            .ok();
+    }
+
+    Ok(())
+}
+
+/// Stores the provider items rejected by the run for later inspection. Failing to store them does
+/// not fail the run, as the valid events of the feed can still be ingested.
+async fn record_rejections<R: IngestionRunRepository>(
+    ingestion_run_repository: &R,
+    provider: &str,
+    rejections: Vec<ProviderRejection>,
+    run: &mut IngestionRun,
+) {
+    run.rejected += u32::try_from(rejections.len()).unwrap_or(u32::MAX);
+    let rejected_at = Utc::now();
+    let rejections = rejections
+        .into_iter()
+        .map(|rejection| IngestionRejection {
+            ingestion_run_id: run.id,
+            provider: provider.to_string(),
+            base_plan_id: rejection.base_plan_id,
+            plan_id: rejection.plan_id,
+            zone_id: rejection.zone_id,
+            reason: rejection.reason,
+            rejected_at,
+        })
+        .collect();
+    ingestion_run_repository
+        .save_rejections(rejections)
+        .await
+        .inspect_err(|e| error!("Error saving items rejected from provider {provider}: {e:?}"))
+        .ok();
+}
+
+/// Upserts a batch of provider events, tallying the outcome on the run. A failed batch is
+/// counted and kept as dead letters so the rest of the feed still gets written.
+async fn write_batch<S: EventRepository, R: IngestionRunRepository>(
+    event_repository: &S,
+    ingestion_run_repository: &R,
+    requests: Vec<SaveEventRequest>,
+    run: &mut IngestionRun,
+) {
+    info!(
+        "Updating event store with provider data: {} entities to be processed",
+        requests.len()
+    );
+    let request_count = u32::try_from(requests.len()).unwrap_or(u32::MAX);
+    match event_repository.bulk_upsert(requests.clone(), run.id).await {
+        Ok(results) => {
+            for result in results {
+                match result.outcome {
+                    UpsertOutcome::Inserted => run.inserted += 1,
+                    UpsertOutcome::Updated => run.updated += 1,
+                    UpsertOutcome::Unchanged => run.skipped += 1,
                 }
             }
-            info!("Event store update finished.");
+            // Earlier failed writes of these events are superseded by the ones just written
+            let external_ids: Vec<ExternalId> = requests
+                .into_iter()
+                .filter_map(|request| request.external_id)
+                .collect();
+            ingestion_run_repository
+                .delete_dead_letters_by_external_ids(&external_ids)
+                .await
+                .inspect_err(|e| error!("Error deleting superseded dead letters: {e:?}"))
+                .ok();
+        }
+        Err(error) => {
+            error!("Error upserting events in event store: {error:?}");
+            run.failed += request_count;
+            let failed_at = Utc::now();
+            let dead_letters = requests
+                .into_iter()
+                .map(|request| DeadLetter {
+                    id: Uuid::new_v4(),
+                    ingestion_run_id: run.id,
+                    request,
+                    error: format!("{error:#}"),
+                    attempts: 1,
+                    created_at: failed_at,
+                    last_failed_at: failed_at,
+                })
+                .collect();
+            ingestion_run_repository
+                .save_dead_letters(dead_letters)
+                .await
+                .inspect_err(|e| error!("Error saving dead letters of failed event writes: {e:?}"))
+                .ok();
+        }
+    }
+}
 
-            Ok::<(), anyhow::Error>(())
-        });
+/// Tuning of how ingestion runs write and reconcile provider data
+#[derive(Clone, Copy, Debug)]
+pub struct IngestionSettings {
+    /// Consecutive runs an event may be missing from the provider's feed before it is cancelled
+    pub cancel_after_missing_runs: u32,
+    /// Provider events written per event store transaction
+    pub batch_size: usize,
+}
+
+impl Default for IngestionSettings {
+    fn default() -> Self {
+        Self {
+            cancel_after_missing_runs: 3,
+            batch_size: 500,
+        }
     }
 }
 
 impl From<ProviderEvent> for SaveEventRequest {
     fn from(value: ProviderEvent) -> Self {
         SaveEventRequest {
+            external_id: Some(value.external_id),
             title: value.title,
             start_time: value.start_time,
             end_time: value.end_time,
+            time_zone: value.time_zone,
             min_price: value.min_price,
             max_price: value.max_price,
+            sell_mode: value.sell_mode,
+            organizer_company_id: value.organizer_company_id,
+            sell_from: value.sell_from,
+            sell_to: value.sell_to,
+            sold_out: value.sold_out,
+            zones: value.zones,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::str::FromStr;
+
+    use chrono_tz::Tz;
+    use futures::stream::{self, Stream};
+
+    use super::*;
+    use crate::application::ports::provider::FeedValidators;
+    use crate::domain::money::Currency;
+
+    const PROVIDER: &str = "test-provider";
+
+    /// Provider whose every fetch yields the next of its scripted feeds, `None` being a feed that
+    /// has not changed since the last fetch
+    struct ScriptedEventProviderClient(Mutex<VecDeque<Option<Vec<FeedChunk>>>>);
+
+    impl ScriptedEventProviderClient {
+        fn new(feeds: Vec<Option<Vec<FeedChunk>>>) -> Self {
+            Self(Mutex::new(feeds.into()))
+        }
+    }
+
+    impl EventProviderClient for ScriptedEventProviderClient {
+        fn name(&self) -> &str {
+            PROVIDER
+        }
+
+        async fn fetch_events(&self) -> Result<Vec<ProviderEvent>> {
+            bail!("Scripted provider only streams its feeds")
+        }
+
+        async fn stream_events_if_modified(
+            &self,
+            _validators: &FeedValidators,
+        ) -> Result<ConditionalFetch<impl Stream<Item = Result<FeedChunk>> + Send>> {
+            let feed = self
+                .0
+                .lock()
+                .unwrap()
+                .pop_front()
+                .context("Scripted provider has no feed left")?;
+            Ok(match feed {
+                None => ConditionalFetch::NotModified,
+                Some(chunks) => ConditionalFetch::Modified {
+                    events: stream::iter(chunks.into_iter().map(Ok)),
+                    validators: FeedValidators {
+                        etag: Some("\"feed\"".to_string()),
+                        last_modified: None,
+                    },
+                },
+            })
+        }
+    }
+
+    struct StoredEvent {
+        request: SaveEventRequest,
+        missing_runs: u32,
+        cancelled_at: Option<DateTime<Utc>>,
+        updated_at: DateTime<Utc>,
+    }
+
+    /// In-memory event store keeping the external ids of every batch written to it and the runs
+    /// that reconciled it, which fails the writes of the batches at the given positions
+    #[derive(Default)]
+    struct RecordingEventRepository {
+        events: Mutex<HashMap<ExternalId, StoredEvent>>,
+        batches: Mutex<Vec<Vec<ExternalId>>>,
+        reconciling_runs: Mutex<Vec<Uuid>>,
+        failing_batches: HashSet<usize>,
+    }
+
+    impl RecordingEventRepository {
+        fn failing_batches(failing_batches: impl IntoIterator<Item = usize>) -> Self {
+            Self {
+                failing_batches: failing_batches.into_iter().collect(),
+                ..Self::default()
+            }
+        }
+
+        fn batches(&self) -> Vec<Vec<ExternalId>> {
+            self.batches.lock().unwrap().clone()
+        }
+
+        fn stored_title(&self, plan_id: &str) -> Option<String> {
+            self.events
+                .lock()
+                .unwrap()
+                .get(&external_id(plan_id))
+                .map(|e| e.request.title.clone())
+        }
+
+        fn is_cancelled(&self, plan_id: &str) -> bool {
+            self.events
+                .lock()
+                .unwrap()
+                .get(&external_id(plan_id))
+                .is_some_and(|e| e.cancelled_at.is_some())
+        }
+    }
+
+    #[allow(unused_variables)]
+    impl EventRepository for RecordingEventRepository {
+        async fn find_all(&self) -> Result<Vec<Event>> {
+            todo!("Not yet implemented")
+        }
+        async fn find_matching(
+            &self,
+            criteria: &EventSearchCriteria,
+            sort: &EventSort,
+            page: &EventPage,
+            limit: u64,
+        ) -> Result<Vec<Event>> {
+            todo!("Not yet implemented")
+        }
+        async fn count_matching(
+            &self,
+            criteria: &EventSearchCriteria,
+            exact_limit: u64,
+        ) -> Result<EventCount> {
+            todo!("Not yet implemented")
+        }
+        async fn find_by_id(&self, id: &Uuid) -> Result<Option<Event>> {
+            todo!("Not yet implemented")
+        }
+        async fn find_updated_at(&self, external_id: &ExternalId) -> Result<Option<DateTime<Utc>>> {
+            Ok(self
+                .events
+                .lock()
+                .unwrap()
+                .get(external_id)
+                .map(|e| e.updated_at))
+        }
+        async fn bulk_upsert(
+            &self,
+            requests: Vec<SaveEventRequest>,
+            ingestion_run_id: Uuid,
+        ) -> Result<Vec<BulkUpsertResult>> {
+            let mut batches = self.batches.lock().unwrap();
+            batches.push(
+                requests
+                    .iter()
+                    .filter_map(|request| request.external_id.clone())
+                    .collect(),
+            );
+            if self.failing_batches.contains(&(batches.len() - 1)) {
+                bail!("Failed to bulk upsert events in event database");
+            }
+
+            let mut events = self.events.lock().unwrap();
+            Ok(requests
+                .into_iter()
+                .map(|request| {
+                    let external_id = request.external_id.clone().unwrap();
+                    let outcome = match events.get(&external_id) {
+                        None => UpsertOutcome::Inserted,
+                        Some(stored)
+                            if stored.cancelled_at.is_none()
+                                && stored.request.title == request.title =>
+                        {
+                            UpsertOutcome::Unchanged
+                        }
+                        Some(_) => UpsertOutcome::Updated,
+                    };
+                    let updated_at = match (outcome, events.get(&external_id)) {
+                        (UpsertOutcome::Unchanged, Some(stored)) => stored.updated_at,
+                        _ => Utc::now(),
+                    };
+                    events.insert(
+                        external_id,
+                        StoredEvent {
+                            request,
+                            missing_runs: 0,
+                            cancelled_at: None,
+                            updated_at,
+                        },
+                    );
+                    BulkUpsertResult {
+                        event_id: Uuid::new_v4(),
+                        outcome,
+                    }
+                })
+                .collect())
+        }
+        async fn mark_missing_events(
+            &self,
+            provider: &str,
+            present_external_ids: &[ExternalId],
+            cancel_after_missing_runs: u32,
+            ingestion_run_id: Uuid,
+        ) -> Result<u64> {
+            self.reconciling_runs.lock().unwrap().push(ingestion_run_id);
+            let mut cancelled = 0;
+            for (external_id, event) in self.events.lock().unwrap().iter_mut() {
+                if external_id.provider != provider
+                    || event.cancelled_at.is_some()
+                    || present_external_ids.contains(external_id)
+                {
+                    continue;
+                }
+                event.missing_runs += 1;
+                if event.missing_runs >= cancel_after_missing_runs.max(1) {
+                    event.cancelled_at = Some(Utc::now());
+                    event.updated_at = Utc::now();
+                    cancelled += 1;
+                }
+            }
+            Ok(cancelled)
+        }
+        async fn find_zones_by_event_id(&self, event_id: &Uuid) -> Result<Vec<Zone>> {
+            todo!("Not yet implemented")
+        }
+        async fn find_zones_by_event_ids(
+            &self,
+            event_ids: &[Uuid],
+        ) -> Result<HashMap<Uuid, Vec<Zone>>> {
+            todo!("Not yet implemented")
+        }
+        async fn find_prices_by_event_id(
+            &self,
+            event_id: &Uuid,
+            from: Option<DateTime<Utc>>,
+            to: Option<DateTime<Utc>>,
+        ) -> Result<Vec<PricePoint>> {
+            todo!("Not yet implemented")
+        }
+        async fn find_changes_by_event_id(&self, event_id: &Uuid) -> Result<Vec<EventChange>> {
+            todo!("Not yet implemented")
+        }
+    }
+
+    /// Ingestion run store keeping the runs, validators, rejections and dead letters written to
+    /// it, which fails to delete dead letters when told to
+    #[derive(Default)]
+    struct RecordingIngestionRunRepository {
+        runs: Mutex<Vec<IngestionRun>>,
+        saved_validators: Mutex<Vec<(String, FeedValidators)>>,
+        rejections: Mutex<Vec<IngestionRejection>>,
+        dead_letters: Mutex<Vec<DeadLetter>>,
+        failing_dead_letter_deletes: bool,
+    }
+
+    #[allow(unused_variables)]
+    impl IngestionRunRepository for RecordingIngestionRunRepository {
+        type IngestionLock = ();
+
+        async fn try_acquire_ingestion_lock(&self) -> Result<Option<()>> {
+            Ok(Some(()))
+        }
+        async fn save_run(&self, run: IngestionRun) -> Result<IngestionRun> {
+            let mut runs = self.runs.lock().unwrap();
+            runs.retain(|saved| saved.id != run.id);
+            runs.push(run.clone());
+            Ok(run)
+        }
+        async fn find_run_by_id(&self, id: &Uuid) -> Result<Option<IngestionRun>> {
+            todo!("Not yet implemented")
+        }
+        async fn find_running_run(&self) -> Result<Option<IngestionRun>> {
+            todo!("Not yet implemented")
+        }
+        async fn fail_running_runs(&self) -> Result<u64> {
+            Ok(0)
+        }
+        async fn find_runs(&self, limit: u64, offset: u64) -> Result<Vec<IngestionRun>> {
+            todo!("Not yet implemented")
+        }
+        async fn find_feed_validators(&self, provider: &str) -> Result<Option<FeedValidators>> {
+            Ok(self
+                .saved_validators
+                .lock()
+                .unwrap()
+                .iter()
+                .rfind(|(saved_provider, _)| saved_provider == provider)
+                .map(|(_, validators)| validators.clone()))
+        }
+        async fn save_feed_validators(
+            &self,
+            provider: &str,
+            validators: &FeedValidators,
+        ) -> Result<()> {
+            self.saved_validators
+                .lock()
+                .unwrap()
+                .push((provider.to_string(), validators.clone()));
+            Ok(())
+        }
+        async fn save_rejections(&self, rejections: Vec<IngestionRejection>) -> Result<()> {
+            self.rejections.lock().unwrap().extend(rejections);
+            Ok(())
         }
+        async fn find_rejections_by_run_id(
+            &self,
+            run_id: &Uuid,
+            limit: u64,
+            offset: u64,
+        ) -> Result<Vec<IngestionRejection>> {
+            todo!("Not yet implemented")
+        }
+        async fn save_dead_letters(&self, dead_letters: Vec<DeadLetter>) -> Result<()> {
+            let mut stored = self.dead_letters.lock().unwrap();
+            for dead_letter in dead_letters {
+                match stored.iter_mut().find(|pending| {
+                    pending.request.external_id.is_some()
+                        && pending.request.external_id == dead_letter.request.external_id
+                }) {
+                    Some(pending) => {
+                        pending.ingestion_run_id = dead_letter.ingestion_run_id;
+                        pending.request = dead_letter.request;
+                        pending.error = dead_letter.error;
+                        pending.attempts += dead_letter.attempts;
+                        pending.last_failed_at = dead_letter.last_failed_at;
+                    }
+                    None => stored.push(dead_letter),
+                }
+            }
+            Ok(())
+        }
+        async fn find_dead_letter_by_id(&self, id: &Uuid) -> Result<Option<DeadLetter>> {
+            todo!("Not yet implemented")
+        }
+        async fn find_dead_letters(&self, limit: u64, offset: u64) -> Result<Vec<DeadLetter>> {
+            let mut dead_letters = self.dead_letters.lock().unwrap().clone();
+            dead_letters.sort_by_key(|dead_letter| (dead_letter.created_at, dead_letter.id));
+            Ok(dead_letters
+                .into_iter()
+                .skip(offset.try_into()?)
+                .take(limit.try_into()?)
+                .collect())
+        }
+        async fn record_dead_letter_failure(
+            &self,
+            id: &Uuid,
+            error: &str,
+        ) -> Result<Option<DeadLetter>> {
+            todo!("Not yet implemented")
+        }
+        async fn delete_dead_letter(&self, id: &Uuid) -> Result<()> {
+            if self.failing_dead_letter_deletes {
+                bail!("Failed to delete dead letter in event database");
+            }
+            self.dead_letters
+                .lock()
+                .unwrap()
+                .retain(|dead_letter| dead_letter.id != *id);
+            Ok(())
+        }
+        async fn delete_dead_letters_by_external_ids(
+            &self,
+            external_ids: &[ExternalId],
+        ) -> Result<u64> {
+            let mut dead_letters = self.dead_letters.lock().unwrap();
+            let count = dead_letters.len();
+            dead_letters.retain(|dead_letter| {
+                dead_letter
+                    .request
+                    .external_id
+                    .as_ref()
+                    .is_none_or(|external_id| !external_ids.contains(external_id))
+            });
+            Ok((count - dead_letters.len()).try_into()?)
+        }
+    }
+
+    fn external_id(plan_id: &str) -> ExternalId {
+        ExternalId {
+            provider: PROVIDER.to_string(),
+            base_plan_id: "291".to_string(),
+            plan_id: plan_id.to_string(),
+        }
+    }
+
+    fn provider_event(plan_id: &str, title: &str) -> ProviderEvent {
+        let eur = |amount_in_cents| Money::new(amount_in_cents, Currency::from_str("EUR").unwrap());
+        ProviderEvent {
+            external_id: external_id(plan_id),
+            title: title.to_string(),
+            start_time: DateTime::from_str("2021-06-30T19:00:00Z").unwrap(),
+            end_time: DateTime::from_str("2021-06-30T20:00:00Z").unwrap(),
+            time_zone: Tz::Europe__Madrid,
+            min_price: eur(1500),
+            max_price: eur(3000),
+            sell_mode: SellMode::Online,
+            organizer_company_id: None,
+            sell_from: DateTime::from_str("2020-06-30T22:00:00Z").unwrap(),
+            sell_to: DateTime::from_str("2021-06-30T18:00:00Z").unwrap(),
+            sold_out: false,
+            zones: Vec::new(),
+        }
+    }
+
+    fn dead_letter(plan_id: &str, title: &str, created_at: DateTime<Utc>) -> DeadLetter {
+        DeadLetter {
+            id: Uuid::new_v4(),
+            ingestion_run_id: Uuid::new_v4(),
+            request: provider_event(plan_id, title).into(),
+            error: "Failed to bulk upsert events in event database".to_string(),
+            attempts: 1,
+            created_at,
+            last_failed_at: created_at,
+        }
+    }
+
+    fn feed(chunks: Vec<Vec<ProviderEvent>>) -> Option<Vec<FeedChunk>> {
+        Some(chunks.into_iter().map(FeedChunk::from).collect())
+    }
+
+    fn rejection(base_plan_id: &str, plan_id: Option<&str>) -> ProviderRejection {
+        ProviderRejection {
+            base_plan_id: Some(base_plan_id.to_string()),
+            plan_id: plan_id.map(String::from),
+            zone_id: None,
+            reason: "Error parsing zone: missing price".to_string(),
+        }
+    }
+
+    fn settings(batch_size: usize) -> IngestionSettings {
+        IngestionSettings {
+            cancel_after_missing_runs: 2,
+            batch_size,
+        }
+    }
+
+    /// Runs the ingestion of the next feed of every provider to completion
+    async fn ingest_next_feeds(
+        event_provider_clients: &[Arc<ScriptedEventProviderClient>],
+        event_repository: &RecordingEventRepository,
+        ingestion_run_repository: &RecordingIngestionRunRepository,
+        settings: IngestionSettings,
+    ) -> IngestionRun {
+        let mut run = IngestionRun::start(None);
+        ingest(
+            event_provider_clients,
+            event_repository,
+            ingestion_run_repository,
+            settings,
+            &mut run,
+        )
+        .await
+        .unwrap();
+        run
+    }
+
+    #[tokio::test]
+    async fn ingestion_writes_feed_in_batches_of_configured_size() {
+        let event_provider_client = Arc::new(ScriptedEventProviderClient::new(vec![feed(vec![
+            vec![provider_event("1", "A"), provider_event("2", "B")],
+            vec![
+                provider_event("3", "C"),
+                provider_event("4", "D"),
+                provider_event("5", "E"),
+            ],
+        ])]));
+        let event_repository = RecordingEventRepository::default();
+        let ingestion_run_repository = RecordingIngestionRunRepository::default();
+
+        let run = ingest_next_feeds(
+            &[event_provider_client],
+            &event_repository,
+            &ingestion_run_repository,
+            settings(2),
+        )
+        .await;
+
+        assert_eq!(
+            event_repository.batches(),
+            vec![
+                vec![external_id("1"), external_id("2")],
+                vec![external_id("3"), external_id("4")],
+                vec![external_id("5")],
+            ]
+        );
+        assert_eq!(run.fetched, 5);
+        assert_eq!(run.inserted, 5);
+        assert_eq!((run.updated, run.skipped, run.failed), (0, 0, 0));
+    }
+
+    #[tokio::test]
+    async fn ingestion_tallies_inserted_updated_and_unchanged_events() {
+        let event_provider_client = Arc::new(ScriptedEventProviderClient::new(vec![
+            feed(vec![vec![
+                provider_event("1", "A"),
+                provider_event("2", "B"),
+            ]]),
+            feed(vec![vec![
+                provider_event("1", "A"),
+                provider_event("2", "B (new date)"),
+                provider_event("3", "C"),
+            ]]),
+        ]));
+        let event_repository = RecordingEventRepository::default();
+        let ingestion_run_repository = RecordingIngestionRunRepository::default();
+        let event_provider_clients = [event_provider_client];
+        ingest_next_feeds(
+            &event_provider_clients,
+            &event_repository,
+            &ingestion_run_repository,
+            settings(500),
+        )
+        .await;
+
+        let run = ingest_next_feeds(
+            &event_provider_clients,
+            &event_repository,
+            &ingestion_run_repository,
+            settings(500),
+        )
+        .await;
+
+        assert_eq!(run.fetched, 3);
+        assert_eq!(run.inserted, 1);
+        assert_eq!(run.updated, 1);
+        assert_eq!(run.skipped, 1);
+        assert_eq!(run.failed, 0);
+        assert_eq!(
+            event_repository.stored_title("2"),
+            Some("B (new date)".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn ingestion_skips_duplicated_events_keeping_the_first_of_the_feed() {
+        let event_provider_client = Arc::new(ScriptedEventProviderClient::new(vec![feed(vec![
+            vec![provider_event("1", "A"), provider_event("2", "B")],
+            vec![provider_event("1", "A (duplicate)")],
+        ])]));
+        let event_repository = RecordingEventRepository::default();
+        let ingestion_run_repository = RecordingIngestionRunRepository::default();
+
+        let run = ingest_next_feeds(
+            &[event_provider_client],
+            &event_repository,
+            &ingestion_run_repository,
+            settings(500),
+        )
+        .await;
+
+        assert_eq!(
+            event_repository.batches(),
+            vec![vec![external_id("1"), external_id("2")]]
+        );
+        assert_eq!(run.fetched, 3);
+        assert_eq!(run.inserted, 2);
+        assert_eq!(run.skipped, 1);
+        assert_eq!(event_repository.stored_title("1"), Some("A".to_string()));
+    }
+
+    #[tokio::test]
+    async fn ingestion_dead_letters_failed_batches_and_writes_the_rest_of_the_feed() {
+        let event_provider_client =
+            Arc::new(ScriptedEventProviderClient::new(vec![feed(vec![vec![
+                provider_event("1", "A"),
+                provider_event("2", "B"),
+                provider_event("3", "C"),
+                provider_event("4", "D"),
+                provider_event("5", "E"),
+            ]])]));
+        let event_repository = RecordingEventRepository::failing_batches([1]);
+        let ingestion_run_repository = RecordingIngestionRunRepository::default();
+
+        let run = ingest_next_feeds(
+            &[event_provider_client],
+            &event_repository,
+            &ingestion_run_repository,
+            settings(2),
+        )
+        .await;
+
+        assert_eq!(event_repository.batches().len(), 3);
+        assert_eq!(run.failed, 2);
+        assert_eq!(run.inserted, 3);
+        assert_eq!(event_repository.stored_title("5"), Some("E".to_string()));
+        assert_eq!(event_repository.stored_title("3"), None);
+        let dead_letters = ingestion_run_repository.dead_letters.lock().unwrap();
+        assert_eq!(
+            dead_letters
+                .iter()
+                .map(|dead_letter| dead_letter.request.external_id.clone())
+                .collect::<Vec<_>>(),
+            vec![Some(external_id("3")), Some(external_id("4"))]
+        );
+        for dead_letter in dead_letters.iter() {
+            assert_eq!(dead_letter.ingestion_run_id, run.id);
+            assert_eq!(dead_letter.attempts, 1);
+            assert_eq!(
+                dead_letter.error,
+                "Failed to bulk upsert events in event database"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn ingestion_deletes_dead_letters_superseded_by_successful_writes() {
+        let event_provider_clients = [Arc::new(ScriptedEventProviderClient::new(vec![
+            feed(vec![vec![
+                provider_event("1", "A"),
+                provider_event("2", "B"),
+            ]]),
+            feed(vec![vec![
+                provider_event("1", "A"),
+                provider_event("3", "C"),
+            ]]),
+        ]))];
+        let event_repository = RecordingEventRepository::failing_batches([0]);
+        let ingestion_run_repository = RecordingIngestionRunRepository::default();
+        let run = ingest_next_feeds(
+            &event_provider_clients,
+            &event_repository,
+            &ingestion_run_repository,
+            settings(500),
+        )
+        .await;
+        assert_eq!(run.failed, 2);
+        assert_eq!(
+            ingestion_run_repository.dead_letters.lock().unwrap().len(),
+            2
+        );
+
+        let run = ingest_next_feeds(
+            &event_provider_clients,
+            &event_repository,
+            &ingestion_run_repository,
+            settings(500),
+        )
+        .await;
+
+        assert_eq!(run.failed, 0);
+        assert_eq!(run.inserted, 2);
+        let dead_letters = ingestion_run_repository.dead_letters.lock().unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].request.external_id, Some(external_id("2")));
+    }
+
+    #[tokio::test]
+    async fn ingestion_counts_attempts_of_batches_failing_again_on_the_same_dead_letter() {
+        let event_provider_clients = [Arc::new(ScriptedEventProviderClient::new(vec![
+            feed(vec![vec![
+                provider_event("1", "A"),
+                provider_event("2", "B"),
+            ]]),
+            feed(vec![vec![
+                provider_event("1", "A (new date)"),
+                provider_event("2", "B"),
+            ]]),
+        ]))];
+        let event_repository = RecordingEventRepository::failing_batches([0, 1]);
+        let ingestion_run_repository = RecordingIngestionRunRepository::default();
+        let first_run = ingest_next_feeds(
+            &event_provider_clients,
+            &event_repository,
+            &ingestion_run_repository,
+            settings(500),
+        )
+        .await;
+        let first_dead_letter_ids: Vec<Uuid> = ingestion_run_repository
+            .dead_letters
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|dead_letter| dead_letter.id)
+            .collect();
+
+        let run = ingest_next_feeds(
+            &event_provider_clients,
+            &event_repository,
+            &ingestion_run_repository,
+            settings(500),
+        )
+        .await;
+
+        assert_ne!(run.id, first_run.id);
+        assert_eq!(run.failed, 2);
+        let dead_letters = ingestion_run_repository.dead_letters.lock().unwrap();
+        assert_eq!(
+            dead_letters
+                .iter()
+                .map(|dead_letter| dead_letter.id)
+                .collect::<Vec<_>>(),
+            first_dead_letter_ids
+        );
+        for dead_letter in dead_letters.iter() {
+            assert_eq!(dead_letter.ingestion_run_id, run.id);
+            assert_eq!(dead_letter.attempts, 2);
+        }
+        assert_eq!(dead_letters[0].request.title, "A (new date)");
+    }
+
+    #[tokio::test]
+    async fn ingestion_cancels_events_missing_from_feed_for_configured_runs() {
+        let event_provider_clients = [Arc::new(ScriptedEventProviderClient::new(vec![
+            feed(vec![vec![
+                provider_event("1", "A"),
+                provider_event("2", "B"),
+            ]]),
+            feed(vec![vec![provider_event("1", "A")]]),
+            feed(vec![vec![provider_event("1", "A")]]),
+        ]))];
+        let event_repository = RecordingEventRepository::default();
+        let ingestion_run_repository = RecordingIngestionRunRepository::default();
+        ingest_next_feeds(
+            &event_provider_clients,
+            &event_repository,
+            &ingestion_run_repository,
+            settings(500),
+        )
+        .await;
+
+        let run = ingest_next_feeds(
+            &event_provider_clients,
+            &event_repository,
+            &ingestion_run_repository,
+            settings(500),
+        )
+        .await;
+        assert_eq!(run.cancelled, 0);
+        assert!(!event_repository.is_cancelled("2"));
+
+        let run = ingest_next_feeds(
+            &event_provider_clients,
+            &event_repository,
+            &ingestion_run_repository,
+            settings(500),
+        )
+        .await;
+        assert_eq!(run.cancelled, 1);
+        assert!(event_repository.is_cancelled("2"));
+        assert!(!event_repository.is_cancelled("1"));
+    }
+
+    #[tokio::test]
+    async fn ingestion_restores_cancelled_events_back_in_feed() {
+        let event_provider_clients = [Arc::new(ScriptedEventProviderClient::new(vec![
+            feed(vec![vec![
+                provider_event("1", "A"),
+                provider_event("2", "B"),
+            ]]),
+            feed(vec![vec![provider_event("1", "A")]]),
+            feed(vec![vec![provider_event("1", "A")]]),
+            feed(vec![vec![
+                provider_event("1", "A"),
+                provider_event("2", "B"),
+            ]]),
+        ]))];
+        let event_repository = RecordingEventRepository::default();
+        let ingestion_run_repository = RecordingIngestionRunRepository::default();
+        for _ in 0..3 {
+            ingest_next_feeds(
+                &event_provider_clients,
+                &event_repository,
+                &ingestion_run_repository,
+                settings(500),
+            )
+            .await;
+        }
+        assert!(event_repository.is_cancelled("2"));
+
+        let run = ingest_next_feeds(
+            &event_provider_clients,
+            &event_repository,
+            &ingestion_run_repository,
+            settings(500),
+        )
+        .await;
+
+        assert_eq!(run.updated, 1);
+        assert_eq!(run.skipped, 1);
+        assert_eq!(run.cancelled, 0);
+        assert!(!event_repository.is_cancelled("2"));
+    }
+
+    #[tokio::test]
+    async fn ingestion_skips_reconciliation_of_empty_feeds() {
+        let event_provider_clients = [Arc::new(ScriptedEventProviderClient::new(vec![
+            feed(vec![vec![provider_event("1", "A")]]),
+            feed(Vec::new()),
+            feed(vec![Vec::new()]),
+        ]))];
+        let event_repository = RecordingEventRepository::default();
+        let ingestion_run_repository = RecordingIngestionRunRepository::default();
+        ingest_next_feeds(
+            &event_provider_clients,
+            &event_repository,
+            &ingestion_run_repository,
+            settings(500),
+        )
+        .await;
+
+        for _ in 0..2 {
+            let run = ingest_next_feeds(
+                &event_provider_clients,
+                &event_repository,
+                &ingestion_run_repository,
+                settings(500),
+            )
+            .await;
+            assert_eq!(run.cancelled, 0);
+        }
+        assert!(!event_repository.is_cancelled("1"));
+    }
+
+    #[tokio::test]
+    async fn ingestion_does_not_cancel_rejected_events_still_in_feed() {
+        let rejected_feed = || {
+            Some(vec![FeedChunk {
+                events: vec![provider_event("1", "A")],
+                rejections: vec![rejection("291", Some("2"))],
+            }])
+        };
+        let event_provider_clients = [Arc::new(ScriptedEventProviderClient::new(vec![
+            feed(vec![vec![
+                provider_event("1", "A"),
+                provider_event("2", "B"),
+            ]]),
+            rejected_feed(),
+            rejected_feed(),
+        ]))];
+        let event_repository = RecordingEventRepository::default();
+        let ingestion_run_repository = RecordingIngestionRunRepository::default();
+
+        for _ in 0..3 {
+            let run = ingest_next_feeds(
+                &event_provider_clients,
+                &event_repository,
+                &ingestion_run_repository,
+                settings(500),
+            )
+            .await;
+            assert_eq!(run.cancelled, 0);
+        }
+        assert!(!event_repository.is_cancelled("2"));
+        assert_eq!(ingestion_run_repository.rejections.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn ingestion_skips_reconciliation_when_rejections_lack_plan_ids() {
+        let rejected_feed = || {
+            Some(vec![FeedChunk {
+                events: vec![provider_event("1", "A")],
+                rejections: vec![rejection("291", None)],
+            }])
+        };
+        let event_provider_clients = [Arc::new(ScriptedEventProviderClient::new(vec![
+            feed(vec![vec![
+                provider_event("1", "A"),
+                provider_event("2", "B"),
+            ]]),
+            rejected_feed(),
+            rejected_feed(),
+        ]))];
+        let event_repository = RecordingEventRepository::default();
+        let ingestion_run_repository = RecordingIngestionRunRepository::default();
+
+        for _ in 0..3 {
+            let run = ingest_next_feeds(
+                &event_provider_clients,
+                &event_repository,
+                &ingestion_run_repository,
+                settings(500),
+            )
+            .await;
+            assert_eq!(run.cancelled, 0);
+        }
+        assert!(!event_repository.is_cancelled("2"));
+    }
+
+    #[tokio::test]
+    async fn ingestion_of_unchanged_feed_skips_event_store_update() {
+        let event_provider_clients = [Arc::new(ScriptedEventProviderClient::new(vec![
+            feed(vec![vec![
+                provider_event("1", "A"),
+                provider_event("2", "B"),
+            ]]),
+            None,
+        ]))];
+        let event_repository = RecordingEventRepository::default();
+        let ingestion_run_repository = RecordingIngestionRunRepository::default();
+        ingest_next_feeds(
+            &event_provider_clients,
+            &event_repository,
+            &ingestion_run_repository,
+            settings(500),
+        )
+        .await;
+
+        let run = ingest_next_feeds(
+            &event_provider_clients,
+            &event_repository,
+            &ingestion_run_repository,
+            settings(500),
+        )
+        .await;
+
+        assert_eq!(run.not_modified, 1);
+        assert_eq!((run.fetched, run.inserted, run.updated), (0, 0, 0));
+        assert_eq!((run.skipped, run.failed, run.cancelled), (0, 0, 0));
+        assert_eq!(event_repository.batches().len(), 1);
+        assert!(
+            !event_repository
+                .reconciling_runs
+                .lock()
+                .unwrap()
+                .contains(&run.id)
+        );
+        assert_eq!(
+            ingestion_run_repository
+                .saved_validators
+                .lock()
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn ingestion_saves_feed_validators_only_once_the_whole_feed_is_written() {
+        let event_provider_clients = [Arc::new(ScriptedEventProviderClient::new(vec![
+            feed(vec![vec![
+                provider_event("1", "A"),
+                provider_event("2", "B"),
+            ]]),
+            feed(vec![vec![
+                provider_event("1", "A"),
+                provider_event("2", "B"),
+            ]]),
+        ]))];
+        let event_repository = RecordingEventRepository::failing_batches([0]);
+        let ingestion_run_repository = RecordingIngestionRunRepository::default();
+
+        let run = ingest_next_feeds(
+            &event_provider_clients,
+            &event_repository,
+            &ingestion_run_repository,
+            settings(500),
+        )
+        .await;
+        assert_eq!(run.failed, 2);
+        assert!(
+            ingestion_run_repository
+                .saved_validators
+                .lock()
+                .unwrap()
+                .is_empty()
+        );
+
+        let run = ingest_next_feeds(
+            &event_provider_clients,
+            &event_repository,
+            &ingestion_run_repository,
+            settings(500),
+        )
+        .await;
+        assert_eq!(run.failed, 0);
+        assert_eq!(
+            *ingestion_run_repository.saved_validators.lock().unwrap(),
+            vec![(
+                PROVIDER.to_string(),
+                FeedValidators {
+                    etag: Some("\"feed\"".to_string()),
+                    last_modified: None,
+                }
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn dead_letter_replays_are_attributed_to_a_new_run_and_skip_superseded_events() {
+        let event_repository = Arc::new(RecordingEventRepository::default());
+        let failed_at = Utc::now() - chrono::Duration::hours(1);
+        let superseded = dead_letter("1", "A (stale)", failed_at);
+        let pending = dead_letter("2", "B", failed_at + chrono::Duration::minutes(1));
+        let ingestion_run_repository = Arc::new(RecordingIngestionRunRepository {
+            dead_letters: Mutex::new(vec![superseded.clone(), pending.clone()]),
+            ..RecordingIngestionRunRepository::default()
+        });
+        event_repository
+            .bulk_upsert(vec![provider_event("1", "A").into()], Uuid::new_v4())
+            .await
+            .unwrap();
+        let ingest_event_service = IngestEventService::new(
+            vec![Arc::new(ScriptedEventProviderClient::new(Vec::new()))],
+            event_repository.clone(),
+            ingestion_run_repository.clone(),
+            settings(500),
+        );
+
+        let Ok(replays) = ingest_event_service.replay_dead_letters(100).await else {
+            panic!("Dead letters could not be replayed");
+        };
+
+        assert_eq!(replays.len(), 2);
+        assert!(replays[0].superseded);
+        assert!(replays[0].result.is_none());
+        assert!(!replays[1].superseded);
+        assert_eq!(
+            replays[1].result.as_ref().map(|result| result.outcome),
+            Some(UpsertOutcome::Inserted)
+        );
+        assert_eq!(event_repository.stored_title("1").as_deref(), Some("A"));
+        assert_eq!(event_repository.stored_title("2").as_deref(), Some("B"));
+        let runs = ingestion_run_repository.runs.lock().unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].state, IngestionRunState::Succeeded);
+        assert_eq!((runs[0].inserted, runs[0].skipped), (1, 1));
+        assert!(
+            replays
+                .iter()
+                .all(|replay| replay.ingestion_run_id == runs[0].id)
+        );
+        assert_ne!(runs[0].id, pending.ingestion_run_id);
+        assert!(
+            ingestion_run_repository
+                .dead_letters
+                .lock()
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn dead_letter_replays_go_on_when_a_replayed_dead_letter_cannot_be_dropped() {
+        let event_repository = Arc::new(RecordingEventRepository::default());
+        let failed_at = Utc::now() - chrono::Duration::hours(1);
+        let ingestion_run_repository = Arc::new(RecordingIngestionRunRepository {
+            dead_letters: Mutex::new(vec![
+                dead_letter("1", "A", failed_at),
+                dead_letter("2", "B", failed_at + chrono::Duration::minutes(1)),
+            ]),
+            failing_dead_letter_deletes: true,
+            ..RecordingIngestionRunRepository::default()
+        });
+        let ingest_event_service = IngestEventService::new(
+            vec![Arc::new(ScriptedEventProviderClient::new(Vec::new()))],
+            event_repository.clone(),
+            ingestion_run_repository.clone(),
+            settings(500),
+        );
+
+        let Ok(replays) = ingest_event_service.replay_dead_letters(100).await else {
+            panic!("Dead letters could not be replayed");
+        };
+
+        assert_eq!(
+            replays
+                .iter()
+                .map(|replay| replay.result.as_ref().map(|result| result.outcome))
+                .collect::<Vec<_>>(),
+            vec![Some(UpsertOutcome::Inserted), Some(UpsertOutcome::Inserted)]
+        );
+        assert_eq!(
+            event_repository.batches(),
+            vec![vec![external_id("1")], vec![external_id("2")]]
+        );
+        assert_eq!(
+            ingestion_run_repository.dead_letters.lock().unwrap().len(),
+            2
+        );
     }
 }
@@ -1,12 +1,68 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use uuid::Uuid;
 
+use super::money::Money;
+
 #[derive(Clone)]
 pub struct Event {
     pub id: Uuid,
+    pub external_id: Option<ExternalId>,
     pub title: String,
     pub start_time: DateTime<Utc>,
     pub end_time: DateTime<Utc>,
-    pub min_price: f64,
-    pub max_price: f64,
+    pub time_zone: Tz,
+    pub min_price: Money,
+    pub max_price: Money,
+    pub sell_mode: SellMode,
+    pub organizer_company_id: Option<String>,
+    pub sell_from: DateTime<Utc>,
+    pub sell_to: DateTime<Utc>,
+    pub sold_out: bool,
+    /// Set once the event has been missing from the provider's feed for long enough
+    pub cancelled_at: Option<DateTime<Utc>>,
+}
+
+/// Stable identity of an event occurrence in a third-party provider's catalog
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ExternalId {
+    pub provider: String,
+    pub base_plan_id: String,
+    pub plan_id: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SellMode {
+    Online,
+    Offline,
+}
+
+impl SellMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SellMode::Online => "online",
+            SellMode::Offline => "offline",
+        }
+    }
+}
+
+impl Display for SellMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SellMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "online" => Ok(SellMode::Online),
+            "offline" => Ok(SellMode::Offline),
+            _ => Err(anyhow::anyhow!("Unknown sell mode: {s}")),
+        }
+    }
 }
@@ -0,0 +1,209 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use uuid::Uuid;
+
+use super::event::Event;
+use super::zone::Zone;
+
+/// Change of an event attribute, as detected by an ingestion run
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventChange {
+    pub event_id: Uuid,
+    pub ingestion_run_id: Uuid,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Attribute that differs between two versions of an event, with both values rendered as text
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+/// Attributes that differ from the old version of an event to the new one, in a stable order
+pub fn diff_events(
+    old: &Event,
+    old_zones: &[Zone],
+    new: &Event,
+    new_zones: &[Zone],
+) -> Vec<FieldChange> {
+    let datetime = |value: DateTime<Utc>| Some(value.to_rfc3339_opts(SecondsFormat::Secs, true));
+    let fields = [
+        ("title", Some(old.title.clone()), Some(new.title.clone())),
+        (
+            "start_time",
+            datetime(old.start_time),
+            datetime(new.start_time),
+        ),
+        ("end_time", datetime(old.end_time), datetime(new.end_time)),
+        (
+            "time_zone",
+            Some(old.time_zone.to_string()),
+            Some(new.time_zone.to_string()),
+        ),
+        (
+            "min_price",
+            Some(old.min_price.to_string()),
+            Some(new.min_price.to_string()),
+        ),
+        (
+            "max_price",
+            Some(old.max_price.to_string()),
+            Some(new.max_price.to_string()),
+        ),
+        (
+            "sell_mode",
+            Some(old.sell_mode.to_string()),
+            Some(new.sell_mode.to_string()),
+        ),
+        (
+            "organizer_company_id",
+            old.organizer_company_id.clone(),
+            new.organizer_company_id.clone(),
+        ),
+        (
+            "sell_from",
+            datetime(old.sell_from),
+            datetime(new.sell_from),
+        ),
+        ("sell_to", datetime(old.sell_to), datetime(new.sell_to)),
+        (
+            "sold_out",
+            Some(old.sold_out.to_string()),
+            Some(new.sold_out.to_string()),
+        ),
+        (
+            "zones",
+            Some(zones_text(old_zones)),
+            Some(zones_text(new_zones)),
+        ),
+        (
+            "cancelled_at",
+            old.cancelled_at.and_then(datetime),
+            new.cancelled_at.and_then(datetime),
+        ),
+    ];
+
+    fields
+        .into_iter()
+        .filter(|(_, old_value, new_value)| old_value != new_value)
+        .map(|(field, old_value, new_value)| FieldChange {
+            field,
+            old_value,
+            new_value,
+        })
+        .collect()
+}
+
+fn zones_text(zones: &[Zone]) -> String {
+    zones
+        .iter()
+        .map(|zone| {
+            format!(
+                "{} {}: {}, capacity {}{}",
+                zone.zone_id,
+                zone.name,
+                zone.price,
+                zone.capacity,
+                if zone.numbered { ", numbered" } else { "" }
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use chrono_tz::Tz;
+
+    use super::*;
+    use crate::domain::event::SellMode;
+    use crate::domain::money::{Currency, Money};
+
+    fn eur(amount_in_cents: i64) -> Money {
+        Money::new(amount_in_cents, Currency::from_str("EUR").unwrap())
+    }
+
+    fn event() -> Event {
+        Event {
+            id: Uuid::new_v4(),
+            external_id: None,
+            title: "Quevedo".to_string(),
+            start_time: DateTime::from_str("2025-11-12T22:00:00Z").unwrap(),
+            end_time: DateTime::from_str("2025-11-12T23:00:00Z").unwrap(),
+            time_zone: Tz::Europe__Madrid,
+            min_price: eur(1599),
+            max_price: eur(3999),
+            sell_mode: SellMode::Online,
+            organizer_company_id: None,
+            sell_from: DateTime::from_str("2025-06-01T00:00:00Z").unwrap(),
+            sell_to: DateTime::from_str("2025-11-12T20:00:00Z").unwrap(),
+            sold_out: false,
+            cancelled_at: None,
+        }
+    }
+
+    #[test]
+    fn identical_events_have_no_changes() {
+        let zones = vec![Zone {
+            zone_id: "1".to_string(),
+            name: "Pista".to_string(),
+            price: eur(1599),
+            capacity: 800,
+            numbered: false,
+        }];
+
+        assert_eq!(diff_events(&event(), &zones, &event(), &zones), vec![]);
+    }
+
+    #[test]
+    fn changed_attributes_are_reported_with_old_and_new_values() {
+        let old = Event {
+            cancelled_at: Some(DateTime::from_str("2025-11-01T02:00:00Z").unwrap()),
+            ..event()
+        };
+        let new = Event {
+            start_time: DateTime::from_str("2025-11-13T22:00:00Z").unwrap(),
+            min_price: eur(1299),
+            ..event()
+        };
+        let new_zones = vec![Zone {
+            zone_id: "1".to_string(),
+            name: "Pista".to_string(),
+            price: eur(1299),
+            capacity: 800,
+            numbered: true,
+        }];
+
+        assert_eq!(
+            diff_events(&old, &[], &new, &new_zones),
+            vec![
+                FieldChange {
+                    field: "start_time",
+                    old_value: Some("2025-11-12T22:00:00Z".to_string()),
+                    new_value: Some("2025-11-13T22:00:00Z".to_string()),
+                },
+                FieldChange {
+                    field: "min_price",
+                    old_value: Some("15.99 EUR".to_string()),
+                    new_value: Some("12.99 EUR".to_string()),
+                },
+                FieldChange {
+                    field: "zones",
+                    old_value: Some(String::new()),
+                    new_value: Some("1 Pista: 12.99 EUR, capacity 800, numbered".to_string()),
+                },
+                FieldChange {
+                    field: "cancelled_at",
+                    old_value: Some("2025-11-01T02:00:00Z".to_string()),
+                    new_value: None,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Tracked execution of an event data ingestion job
+#[derive(Clone, Debug, PartialEq)]
+pub struct IngestionRun {
+    pub id: Uuid,
+    pub state: IngestionRunState,
+    /// Provider the run was limited to, or `None` when it covers every configured provider
+    pub provider: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub fetched: u32,
+    pub inserted: u32,
+    pub updated: u32,
+    pub skipped: u32,
+    pub failed: u32,
+    pub cancelled: u32,
+    /// Providers whose feed had not changed since it was last ingested
+    pub not_modified: u32,
+    /// Provider items that could not be mapped to events
+    pub rejected: u32,
+}
+
+impl IngestionRun {
+    pub fn start(provider: Option<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            state: IngestionRunState::Running,
+            provider,
+            started_at: Utc::now(),
+            finished_at: None,
+            fetched: 0,
+            inserted: 0,
+            updated: 0,
+            skipped: 0,
+            failed: 0,
+            cancelled: 0,
+            not_modified: 0,
+            rejected: 0,
+        }
+    }
+
+    pub fn finish(&mut self, state: IngestionRunState) {
+        self.state = state;
+        self.finished_at = Some(Utc::now());
+    }
+}
+
+/// Provider item an ingestion run could not map to an event, with the reason it was rejected
+#[derive(Clone, Debug, PartialEq)]
+pub struct IngestionRejection {
+    pub ingestion_run_id: Uuid,
+    pub provider: String,
+    pub base_plan_id: Option<String>,
+    pub plan_id: Option<String>,
+    pub zone_id: Option<String>,
+    pub reason: String,
+    pub rejected_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IngestionRunState {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl IngestionRunState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IngestionRunState::Running => "running",
+            IngestionRunState::Succeeded => "succeeded",
+            IngestionRunState::Failed => "failed",
+        }
+    }
+}
+
+impl Display for IngestionRunState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IngestionRunState {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "running" => Ok(IngestionRunState::Running),
+            "succeeded" => Ok(IngestionRunState::Succeeded),
+            "failed" => Ok(IngestionRunState::Failed),
+            _ => Err(anyhow::anyhow!("Unknown ingestion run state: {s}")),
+        }
+    }
+}
@@ -1 +1,6 @@
 pub mod event;
+pub mod event_change;
+pub mod ingestion_run;
+pub mod money;
+pub mod price_point;
+pub mod zone;
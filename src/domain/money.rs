@@ -0,0 +1,247 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use anyhow::{Context, Result, anyhow, bail};
+
+/// ISO-4217 currency, identified by its alphabetic code
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Currency {
+    code: [u8; 3],
+}
+
+/// Currencies whose minor unit is not the usual hundredth
+const ZERO_MINOR_DIGIT_CURRENCIES: [&str; 16] = [
+    "BIF", "CLP", "DJF", "GNF", "ISK", "JPY", "KMF", "KRW", "PYG", "RWF", "UGX", "UYI", "VND",
+    "VUV", "XAF", "XOF",
+];
+const THREE_MINOR_DIGIT_CURRENCIES: [&str; 7] = ["BHD", "IQD", "JOD", "KWD", "LYD", "OMR", "TND"];
+
+impl Currency {
+    pub fn code(&self) -> &str {
+        std::str::from_utf8(&self.code).expect("currency code is always ASCII")
+    }
+
+    pub fn minor_digits(&self) -> u32 {
+        let code = self.code();
+        if ZERO_MINOR_DIGIT_CURRENCIES.contains(&code) {
+            0
+        } else if THREE_MINOR_DIGIT_CURRENCIES.contains(&code) {
+            3
+        } else {
+            2
+        }
+    }
+}
+
+impl FromStr for Currency {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code: [u8; 3] = s
+            .as_bytes()
+            .try_into()
+            .map_err(|_| anyhow!("Invalid currency code: {s}"))?;
+        if !code.iter().all(u8::is_ascii_uppercase) {
+            bail!("Invalid currency code: {s}");
+        }
+
+        Ok(Self { code })
+    }
+}
+
+impl Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Exact monetary amount, held as an integer number of the currency's minor units
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Money {
+    amount: i64,
+    currency: Currency,
+}
+
+impl Money {
+    pub fn new(amount_in_minor_units: i64, currency: Currency) -> Self {
+        Self {
+            amount: amount_in_minor_units,
+            currency,
+        }
+    }
+
+    /// Parses a decimal amount such as `20.00`, rejecting more fractional digits than the currency has
+    pub fn from_decimal_str(value: &str, currency: Currency) -> Result<Self> {
+        let (negative, unsigned) = match value.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, value),
+        };
+        let (integer_part, fractional_part) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        let minor_digits = currency.minor_digits() as usize;
+
+        if integer_part.is_empty()
+            || !integer_part.bytes().all(|b| b.is_ascii_digit())
+            || !fractional_part.bytes().all(|b| b.is_ascii_digit())
+            || (unsigned.contains('.') && fractional_part.is_empty())
+        {
+            bail!("Invalid decimal amount: {value}");
+        }
+        // Trailing zeros past the currency's minor digits do not change the amount
+        let (fractional_part, excess_digits) =
+            fractional_part.split_at(fractional_part.len().min(minor_digits));
+        if excess_digits.bytes().any(|b| b != b'0') {
+            bail!("Amount {value} has more fractional digits than {currency} allows");
+        }
+
+        let overflow = || anyhow!("Amount {value} is out of range");
+        let integer: i64 = integer_part.parse().with_context(overflow)?;
+        let fraction: i64 = if fractional_part.is_empty() {
+            0
+        } else {
+            format!("{fractional_part:0<minor_digits$}")
+                .parse()
+                .with_context(overflow)?
+        };
+        let amount = integer
+            .checked_mul(10i64.pow(minor_digits as u32))
+            .and_then(|a| a.checked_add(fraction))
+            .ok_or_else(overflow)?;
+
+        Ok(Self {
+            amount: if negative { -amount } else { amount },
+            currency,
+        })
+    }
+
+    pub fn amount_in_minor_units(self) -> i64 {
+        self.amount
+    }
+
+    pub fn currency(self) -> Currency {
+        self.currency
+    }
+
+    pub fn to_decimal_string(self) -> String {
+        let minor_digits = self.currency.minor_digits();
+        let sign = if self.amount < 0 { "-" } else { "" };
+        let unsigned = self.amount.unsigned_abs();
+        if minor_digits == 0 {
+            return format!("{sign}{unsigned}");
+        }
+
+        let factor = 10u64.pow(minor_digits);
+        format!(
+            "{sign}{}.{:0width$}",
+            unsigned / factor,
+            unsigned % factor,
+            width = minor_digits as usize
+        )
+    }
+}
+
+impl PartialOrd for Money {
+    /// Amounts in different currencies are not comparable
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.currency == other.currency).then(|| self.amount.cmp(&other.amount))
+    }
+}
+
+impl Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.to_decimal_string(), self.currency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn currency(code: &str) -> Currency {
+        Currency::from_str(code).unwrap()
+    }
+
+    #[test]
+    fn decimal_amounts_are_parsed_exactly() {
+        let eur = currency("EUR");
+
+        assert_eq!(
+            Money::from_decimal_str("19.99", eur).unwrap(),
+            Money::new(1999, eur)
+        );
+        assert_eq!(
+            Money::from_decimal_str("20.00", eur).unwrap(),
+            Money::new(2000, eur)
+        );
+        assert_eq!(
+            Money::from_decimal_str("20.5", eur).unwrap(),
+            Money::new(2050, eur)
+        );
+        assert_eq!(
+            Money::from_decimal_str("7", eur).unwrap(),
+            Money::new(700, eur)
+        );
+    }
+
+    #[test]
+    fn decimal_amounts_respect_currency_minor_digits() {
+        let jpy = currency("JPY");
+        let kwd = currency("KWD");
+
+        assert_eq!(
+            Money::from_decimal_str("500", jpy).unwrap(),
+            Money::new(500, jpy)
+        );
+        assert!(Money::from_decimal_str("500.5", jpy).is_err());
+        assert_eq!(
+            Money::from_decimal_str("1500.00", jpy).unwrap(),
+            Money::new(1500, jpy)
+        );
+        assert_eq!(
+            Money::from_decimal_str("20.500", currency("EUR")).unwrap(),
+            Money::new(2050, currency("EUR"))
+        );
+        assert_eq!(
+            Money::from_decimal_str("1.25", kwd).unwrap(),
+            Money::new(1250, kwd)
+        );
+        assert!(Money::from_decimal_str("19.999", currency("EUR")).is_err());
+    }
+
+    #[test]
+    fn invalid_or_overflowing_amounts_are_rejected() {
+        let eur = currency("EUR");
+
+        for value in [
+            "",
+            ".5",
+            "5.",
+            "abc",
+            "1.2.3",
+            "1e3",
+            "92233720368547758.08",
+        ] {
+            assert!(
+                Money::from_decimal_str(value, eur).is_err(),
+                "{value} should be rejected"
+            );
+        }
+        assert!(Currency::from_str("eur").is_err());
+        assert!(Currency::from_str("EURO").is_err());
+    }
+
+    #[test]
+    fn amounts_are_formatted_with_currency_minor_digits() {
+        assert_eq!(
+            Money::new(1599, currency("EUR")).to_decimal_string(),
+            "15.99"
+        );
+        assert_eq!(Money::new(5, currency("EUR")).to_decimal_string(), "0.05");
+        assert_eq!(Money::new(-5, currency("EUR")).to_decimal_string(), "-0.05");
+        assert_eq!(Money::new(500, currency("JPY")).to_decimal_string(), "500");
+        assert_eq!(
+            Money::new(1250, currency("KWD")).to_decimal_string(),
+            "1.250"
+        );
+    }
+}
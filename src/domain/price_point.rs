@@ -0,0 +1,11 @@
+use chrono::{DateTime, Utc};
+
+use super::money::Money;
+
+/// Price range of an event as observed in its provider's feed, until a later observation
+#[derive(Clone, Debug, PartialEq)]
+pub struct PricePoint {
+    pub min_price: Money,
+    pub max_price: Money,
+    pub observed_at: DateTime<Utc>,
+}
@@ -0,0 +1,10 @@
+use super::money::Money;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Zone {
+    pub zone_id: String,
+    pub name: String,
+    pub price: Money,
+    pub capacity: u32,
+    pub numbered: bool,
+}
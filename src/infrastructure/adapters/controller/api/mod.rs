@@ -9,12 +9,13 @@ use serde::ser::SerializeStruct;
 use super::ApplicationState;
 
 use crate::application::ports::provider::EventProviderClient;
-use crate::application::ports::repository::EventRepository;
+use crate::application::ports::repository::{EventRepository, IngestionRunRepository};
 
-pub fn configure<T, S>(state: Arc<ApplicationState<T, S>>) -> Router
+pub fn configure<T, S, R>(state: Arc<ApplicationState<T, S, R>>) -> Router
 where
     T: EventRepository + Send + Sync + 'static,
     S: EventProviderClient + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
 {
     Router::new().nest("/api/v1", v1::configure(state))
 }
@@ -1,21 +1,43 @@
 use std::sync::Arc;
 
 use axum::Router;
-use axum::routing::{get, patch};
+use axum::routing::{get, patch, post};
 
 use crate::application::ports::provider::EventProviderClient;
-use crate::application::ports::repository::EventRepository;
+use crate::application::ports::repository::{EventRepository, IngestionRunRepository};
 use crate::infrastructure::adapters::controller::handlers;
 use crate::infrastructure::adapters::controller::state::ApplicationState;
 
-pub fn configure<T, S>(state: Arc<ApplicationState<T, S>>) -> Router
+pub fn configure<T, S, R>(state: Arc<ApplicationState<T, S, R>>) -> Router
 where
     T: EventRepository + Send + Sync + 'static,
     S: EventProviderClient + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
 {
     Router::new()
         .route("/", get(handlers::handle_root))
         .route("/search", get(handlers::handle_search))
-        .route("/ingest", patch(handlers::handle_ingest))
+        .route("/events/{id}/zones", get(handlers::handle_event_zones))
+        .route("/events/{id}/prices", get(handlers::handle_event_prices))
+        .route("/events/{id}/history", get(handlers::handle_event_history))
+        .route(
+            "/ingest",
+            patch(handlers::handle_ingest).get(handlers::handle_ingestion_runs),
+        )
+        .route("/ingest/{job_id}", get(handlers::handle_ingestion_run))
+        .route("/providers", get(handlers::handle_providers))
+        .route(
+            "/ingest/{job_id}/rejections",
+            get(handlers::handle_ingestion_rejections),
+        )
+        .route("/admin/dead-letters", get(handlers::handle_dead_letters))
+        .route(
+            "/admin/dead-letters/replay",
+            post(handlers::handle_dead_letters_replay),
+        )
+        .route(
+            "/admin/dead-letters/{id}/replay",
+            post(handlers::handle_dead_letter_replay),
+        )
         .with_state(state)
 }
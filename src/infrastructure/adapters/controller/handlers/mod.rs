@@ -1,19 +1,38 @@
+use std::str::FromStr;
 use std::sync::Arc;
 
 use axum::Json;
+use axum::extract::OriginalUri;
 use axum::extract::State;
+use axum::extract::{Path, rejection::PathRejection};
 use axum::extract::{Query, rejection::QueryRejection};
-use axum::http::StatusCode;
-use chrono::{DateTime, Utc};
+use axum::http::{HeaderMap, HeaderName, StatusCode, Uri, header};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, SecondsFormat, Utc};
+use chrono_tz::Tz;
 use log::debug;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use super::api::{ApiResponse, ErrorResponse};
 use super::state::ApplicationState;
 
 use crate::application::ports::provider::EventProviderClient;
-use crate::application::ports::repository::EventRepository;
-use crate::application::service::{SearchEventServiceError, SearchEventServiceResponse};
+use crate::application::ports::repository::{
+    DeadLetter, EventCount, EventKey, EventPage, EventRepository, EventSort, EventSortField,
+    EventSortKey, IngestionRunRepository, TimeMatch, UpsertOutcome,
+};
+use crate::application::service::{
+    DeadLetterReplay, IngestEventServiceError, ProviderStatus, SearchEventServiceError,
+    SearchEventServiceRequest, SearchEventServiceResponse,
+};
+use crate::domain::event::SellMode;
+use crate::domain::event_change::EventChange;
+use crate::domain::ingestion_run::{IngestionRejection, IngestionRun};
+use crate::domain::money::{Currency, Money};
+use crate::domain::price_point::PricePoint;
+use crate::domain::zone::Zone;
 
 pub async fn handle_root() -> Json<ApiResponse<HelloWorldResponse, ()>> {
     Json(ApiResponse::Ok(
@@ -27,9 +46,11 @@ pub async fn handle_root() -> Json<ApiResponse<HelloWorldResponse, ()>> {
 pub async fn handle_search<
     T: EventRepository + Send + Sync + 'static,
     S: EventProviderClient + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
 >(
+    OriginalUri(uri): OriginalUri,
     params: Result<Query<SearchParams>, QueryRejection>,
-    State(state): State<Arc<ApplicationState<T, S>>>,
+    State(state): State<Arc<ApplicationState<T, S, R>>>,
 ) -> Result<
     Json<ApiResponse<SearchResponse, SearchMetadata>>,
     (StatusCode, Json<ApiResponse<(), ()>>),
@@ -52,28 +73,341 @@ pub async fn handle_search<
 
     debug!("Search query params are: {query:?}");
 
+    if query.start_time > query.end_time {
+        debug!(
+            "Search range starts after it ends: {} > {}",
+            query.start_time, query.end_time
+        );
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "25".to_string(),
+                message: "Invalid search range".to_string(),
+            })),
+        ));
+    }
+
+    let invalid_filters = |err: anyhow::Error| {
+        debug!("Search filters are invalid: {err}");
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "21".to_string(),
+                message: "Invalid search filters".to_string(),
+            })),
+        )
+    };
+    let (min_price, max_price) = query.price_bounds().map_err(invalid_filters)?;
+    let sell_mode = query
+        .sell_mode
+        .as_deref()
+        .map(SellMode::from_str)
+        .transpose()
+        .map_err(invalid_filters)?;
+    let time_match = query
+        .time_match
+        .as_deref()
+        .map(parse_time_match)
+        .transpose()
+        .map_err(invalid_filters)?
+        .unwrap_or_default();
+    let sort = query
+        .sort
+        .as_deref()
+        .map(parse_sort)
+        .transpose()
+        .map_err(|err| {
+            debug!("Search sort is invalid: {err}");
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::Ko(ErrorResponse {
+                    code: "23".to_string(),
+                    message: "Invalid sort".to_string(),
+                })),
+            )
+        })?
+        .unwrap_or_default();
+    let page = match query.cursor.as_deref() {
+        Some(cursor) => parse_cursor(cursor, &sort).map_err(|err| {
+            debug!("Search cursor is invalid: {err}");
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::Ko(ErrorResponse {
+                    code: "22".to_string(),
+                    message: "Invalid cursor".to_string(),
+                })),
+            )
+        })?,
+        None => EventPage::Offset(query.offset),
+    };
+
     let events = search_event_service
-        .search_events(query.start_time, query.end_time, query.limit, query.offset)
+        .search_events(SearchEventServiceRequest {
+            start_time: query.start_time,
+            end_time: query.end_time,
+            time_match,
+            limit: query.limit,
+            page,
+            sort: sort.clone(),
+            include_zones: query.include_zones,
+            include_total: query.include_total,
+            include_cancelled: query.include_cancelled,
+            price_dropped_since: query.price_dropped_since,
+            title_contains: query.q.clone().filter(|q| !q.trim().is_empty()),
+            min_price,
+            max_price,
+            sold_out: query.sold_out,
+            sell_mode,
+        })
         .await?;
 
-    Ok(events.into())
+    Ok(search_response(
+        events,
+        &sort,
+        query.tz.unwrap_or(Tz::UTC),
+        &uri,
+    ))
+}
+
+pub async fn handle_event_zones<
+    T: EventRepository + Send + Sync + 'static,
+    S: EventProviderClient + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
+>(
+    event_id: Result<Path<Uuid>, PathRejection>,
+    State(state): State<Arc<ApplicationState<T, S, R>>>,
+) -> Result<Json<ApiResponse<EventZonesResponse, ()>>, (StatusCode, Json<ApiResponse<(), ()>>)> {
+    let ApplicationState {
+        ref search_event_service,
+        ..
+    } = *state;
+
+    let Path(event_id) = event_id.map_err(|err| {
+        debug!("Event id path param is invalid: {}", err);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "12".to_string(),
+                message: "Invalid event id".to_string(),
+            })),
+        )
+    })?;
+
+    match search_event_service.find_event_zones(event_id).await {
+        Ok(Some(zones)) => Ok(Json(ApiResponse::Ok(
+            EventZonesResponse {
+                zones: zones.iter().map(ZoneResponse::from).collect(),
+            },
+            (),
+        ))),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "13".to_string(),
+                message: "Event not found".to_string(),
+            })),
+        )),
+        Err(_e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "99".to_string(),
+                message: "Unexpected error when finding event zones.".to_string(),
+            })),
+        )),
+    }
+}
+
+pub async fn handle_event_prices<
+    T: EventRepository + Send + Sync + 'static,
+    S: EventProviderClient + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
+>(
+    event_id: Result<Path<Uuid>, PathRejection>,
+    params: Result<Query<EventPricesParams>, QueryRejection>,
+    State(state): State<Arc<ApplicationState<T, S, R>>>,
+) -> Result<Json<ApiResponse<EventPricesResponse, ()>>, (StatusCode, Json<ApiResponse<(), ()>>)> {
+    let ApplicationState {
+        ref search_event_service,
+        ..
+    } = *state;
+
+    let Path(event_id) = event_id.map_err(|err| {
+        debug!("Event id path param is invalid: {}", err);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "12".to_string(),
+                message: "Invalid event id".to_string(),
+            })),
+        )
+    })?;
+    let invalid_range = || {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "18".to_string(),
+                message: "Invalid price history range".to_string(),
+            })),
+        )
+    };
+    let Query(query) = params.map_err(|err| {
+        debug!("Event prices query params are invalid: {}", err);
+        invalid_range()
+    })?;
+    if let (Some(from), Some(to)) = (query.from, query.to)
+        && from > to
+    {
+        debug!("Event prices range starts after it ends: {from} > {to}");
+        return Err(invalid_range());
+    }
+
+    match search_event_service
+        .find_event_prices(event_id, query.from, query.to)
+        .await
+    {
+        Ok(Some(prices)) => Ok(Json(ApiResponse::Ok(
+            EventPricesResponse {
+                prices: prices.iter().map(PricePointResponse::from).collect(),
+            },
+            (),
+        ))),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "13".to_string(),
+                message: "Event not found".to_string(),
+            })),
+        )),
+        Err(_e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "99".to_string(),
+                message: "Unexpected error when finding event prices.".to_string(),
+            })),
+        )),
+    }
+}
+
+pub async fn handle_event_history<
+    T: EventRepository + Send + Sync + 'static,
+    S: EventProviderClient + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
+>(
+    event_id: Result<Path<Uuid>, PathRejection>,
+    State(state): State<Arc<ApplicationState<T, S, R>>>,
+) -> Result<Json<ApiResponse<EventHistoryResponse, ()>>, (StatusCode, Json<ApiResponse<(), ()>>)> {
+    let ApplicationState {
+        ref search_event_service,
+        ..
+    } = *state;
+
+    let Path(event_id) = event_id.map_err(|err| {
+        debug!("Event id path param is invalid: {}", err);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "12".to_string(),
+                message: "Invalid event id".to_string(),
+            })),
+        )
+    })?;
+
+    match search_event_service.find_event_history(event_id).await {
+        Ok(Some(changes)) => Ok(Json(ApiResponse::Ok(
+            EventHistoryResponse {
+                changes: changes.iter().map(EventChangeResponse::from).collect(),
+            },
+            (),
+        ))),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "13".to_string(),
+                message: "Event not found".to_string(),
+            })),
+        )),
+        Err(_e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "99".to_string(),
+                message: "Unexpected error when finding event history.".to_string(),
+            })),
+        )),
+    }
 }
 
 pub async fn handle_ingest<
     T: EventRepository + Send + Sync + 'static,
     S: EventProviderClient + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
 >(
-    State(state): State<Arc<ApplicationState<T, S>>>,
-) -> Result<StatusCode, (StatusCode, Json<ApiResponse<(), ()>>)> {
+    params: Result<Query<IngestParams>, QueryRejection>,
+    State(state): State<Arc<ApplicationState<T, S, R>>>,
+) -> Result<
+    (
+        StatusCode,
+        [(HeaderName, String); 1],
+        Json<ApiResponse<IngestionRunResponse, ()>>,
+    ),
+    (StatusCode, HeaderMap, Json<ApiResponse<(), ()>>),
+> {
     let ApplicationState {
         ref ingest_event_service,
         ..
     } = *state;
 
-    match ingest_event_service.ingest_events().await {
-        Ok(()) => Ok(StatusCode::ACCEPTED),
-        Err(_e) => Err((
+    let Query(params) = params.map_err(|err| {
+        debug!("Ingestion query params are invalid: {}", err);
+        (
+            StatusCode::BAD_REQUEST,
+            HeaderMap::new(),
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "24".to_string(),
+                message: "Invalid ingestion params".to_string(),
+            })),
+        )
+    })?;
+
+    match ingest_event_service
+        .ingest_events(params.provider.as_deref())
+        .await
+    {
+        Ok(run) => Ok((
+            StatusCode::ACCEPTED,
+            [(header::LOCATION, ingestion_job_location(&run.id))],
+            Json(ApiResponse::Ok(IngestionRunResponse::from(&run), ())),
+        )),
+        Err(IngestEventServiceError::AlreadyRunning(job_id)) => {
+            let mut headers = HeaderMap::new();
+            let message = match job_id {
+                Some(job_id) => {
+                    if let Ok(location) = ingestion_job_location(&job_id).parse() {
+                        headers.insert(header::LOCATION, location);
+                    }
+                    format!("Event ingestion already in progress: job {job_id}")
+                }
+                None => "Event ingestion already in progress".to_string(),
+            };
+            Err((
+                StatusCode::CONFLICT,
+                headers,
+                Json(ApiResponse::Ko(ErrorResponse {
+                    code: "78".to_string(),
+                    message,
+                })),
+            ))
+        }
+        Err(IngestEventServiceError::UnknownProvider(provider)) => Err((
+            StatusCode::NOT_FOUND,
+            HeaderMap::new(),
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "17".to_string(),
+                message: format!("Event provider not found: {provider}"),
+            })),
+        )),
+        Err(IngestEventServiceError::Unexpected) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
+            HeaderMap::new(),
             Json(ApiResponse::Ko(ErrorResponse {
                 code: "77".to_string(),
                 message: "Unexpected error when starting event ingestion.".to_string(),
@@ -82,18 +416,444 @@ pub async fn handle_ingest<
     }
 }
 
+fn ingestion_job_location(job_id: &Uuid) -> String {
+    format!("/api/v1/ingest/{job_id}")
+}
+
+pub async fn handle_ingestion_run<
+    T: EventRepository + Send + Sync + 'static,
+    S: EventProviderClient + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
+>(
+    job_id: Result<Path<Uuid>, PathRejection>,
+    State(state): State<Arc<ApplicationState<T, S, R>>>,
+) -> Result<Json<ApiResponse<IngestionRunResponse, ()>>, (StatusCode, Json<ApiResponse<(), ()>>)> {
+    let ApplicationState {
+        ref ingest_event_service,
+        ..
+    } = *state;
+
+    let Path(job_id) = job_id.map_err(|err| {
+        debug!("Job id path param is invalid: {}", err);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "14".to_string(),
+                message: "Invalid job id".to_string(),
+            })),
+        )
+    })?;
+
+    match ingest_event_service.find_ingestion_run(job_id).await {
+        Ok(Some(run)) => Ok(Json(ApiResponse::Ok(IngestionRunResponse::from(&run), ()))),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "15".to_string(),
+                message: "Ingestion job not found".to_string(),
+            })),
+        )),
+        Err(_e) => Err(ingestion_run_lookup_error()),
+    }
+}
+
+pub async fn handle_providers<
+    T: EventRepository + Send + Sync + 'static,
+    S: EventProviderClient + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
+>(
+    State(state): State<Arc<ApplicationState<T, S, R>>>,
+) -> Json<ApiResponse<ProvidersResponse, ()>> {
+    let ApplicationState {
+        ref ingest_event_service,
+        ..
+    } = *state;
+
+    Json(ApiResponse::Ok(
+        ProvidersResponse {
+            providers: ingest_event_service
+                .provider_statuses()
+                .iter()
+                .map(ProviderResponse::from)
+                .collect(),
+        },
+        (),
+    ))
+}
+
+pub async fn handle_ingestion_runs<
+    T: EventRepository + Send + Sync + 'static,
+    S: EventProviderClient + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
+>(
+    params: Result<Query<IngestionRunsParams>, QueryRejection>,
+    State(state): State<Arc<ApplicationState<T, S, R>>>,
+) -> Result<
+    Json<ApiResponse<IngestionRunsResponse, IngestionRunsMetadata>>,
+    (StatusCode, Json<ApiResponse<(), ()>>),
+> {
+    let ApplicationState {
+        ref ingest_event_service,
+        ..
+    } = *state;
+
+    let Query(query) = params.map_err(|err| {
+        debug!("Ingestion runs query params are invalid: {}", err);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "16".to_string(),
+                message: "Invalid pagination params".to_string(),
+            })),
+        )
+    })?;
+
+    match ingest_event_service
+        .find_ingestion_runs(query.limit, query.offset)
+        .await
+    {
+        Ok(runs) => Ok(Json(ApiResponse::Ok(
+            IngestionRunsResponse {
+                jobs: runs.iter().map(IngestionRunResponse::from).collect(),
+            },
+            IngestionRunsMetadata {
+                limit: query.limit,
+                offset: query.offset,
+            },
+        ))),
+        Err(_e) => Err(ingestion_run_lookup_error()),
+    }
+}
+
+pub async fn handle_ingestion_rejections<
+    T: EventRepository + Send + Sync + 'static,
+    S: EventProviderClient + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
+>(
+    job_id: Result<Path<Uuid>, PathRejection>,
+    params: Result<Query<IngestionRejectionsParams>, QueryRejection>,
+    State(state): State<Arc<ApplicationState<T, S, R>>>,
+) -> Result<
+    Json<ApiResponse<IngestionRejectionsResponse, IngestionRunsMetadata>>,
+    (StatusCode, Json<ApiResponse<(), ()>>),
+> {
+    let ApplicationState {
+        ref ingest_event_service,
+        ..
+    } = *state;
+
+    let Path(job_id) = job_id.map_err(|err| {
+        debug!("Job id path param is invalid: {}", err);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "14".to_string(),
+                message: "Invalid job id".to_string(),
+            })),
+        )
+    })?;
+    let Query(query) = params.map_err(|err| {
+        debug!("Ingestion rejections query params are invalid: {}", err);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "16".to_string(),
+                message: "Invalid pagination params".to_string(),
+            })),
+        )
+    })?;
+
+    match ingest_event_service
+        .find_ingestion_rejections(job_id, query.limit, query.offset)
+        .await
+    {
+        Ok(Some(rejections)) => Ok(Json(ApiResponse::Ok(
+            IngestionRejectionsResponse {
+                rejections: rejections
+                    .iter()
+                    .map(IngestionRejectionResponse::from)
+                    .collect(),
+            },
+            IngestionRunsMetadata {
+                limit: query.limit,
+                offset: query.offset,
+            },
+        ))),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "15".to_string(),
+                message: "Ingestion job not found".to_string(),
+            })),
+        )),
+        Err(_e) => Err(ingestion_run_lookup_error()),
+    }
+}
+
+pub async fn handle_dead_letters<
+    T: EventRepository + Send + Sync + 'static,
+    S: EventProviderClient + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
+>(
+    params: Result<Query<DeadLettersParams>, QueryRejection>,
+    State(state): State<Arc<ApplicationState<T, S, R>>>,
+) -> Result<
+    Json<ApiResponse<DeadLettersResponse, IngestionRunsMetadata>>,
+    (StatusCode, Json<ApiResponse<(), ()>>),
+> {
+    let ApplicationState {
+        ref ingest_event_service,
+        ..
+    } = *state;
+
+    let Query(query) = params.map_err(|err| {
+        debug!("Dead letters query params are invalid: {}", err);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "16".to_string(),
+                message: "Invalid pagination params".to_string(),
+            })),
+        )
+    })?;
+
+    match ingest_event_service
+        .find_dead_letters(query.limit, query.offset)
+        .await
+    {
+        Ok(dead_letters) => Ok(Json(ApiResponse::Ok(
+            DeadLettersResponse {
+                dead_letters: dead_letters.iter().map(DeadLetterResponse::from).collect(),
+            },
+            IngestionRunsMetadata {
+                limit: query.limit,
+                offset: query.offset,
+            },
+        ))),
+        Err(_e) => Err(dead_letter_replay_error(
+            IngestEventServiceError::Unexpected,
+        )),
+    }
+}
+
+pub async fn handle_dead_letter_replay<
+    T: EventRepository + Send + Sync + 'static,
+    S: EventProviderClient + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
+>(
+    id: Result<Path<Uuid>, PathRejection>,
+    State(state): State<Arc<ApplicationState<T, S, R>>>,
+) -> Result<Json<ApiResponse<DeadLetterReplayResponse, ()>>, (StatusCode, Json<ApiResponse<(), ()>>)>
+{
+    let ApplicationState {
+        ref ingest_event_service,
+        ..
+    } = *state;
+
+    let Path(id) = id.map_err(|err| {
+        debug!("Dead letter id path param is invalid: {}", err);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "19".to_string(),
+                message: "Invalid dead letter id".to_string(),
+            })),
+        )
+    })?;
+
+    match ingest_event_service.replay_dead_letter(id).await {
+        Ok(Some(replay)) => Ok(Json(ApiResponse::Ok(
+            DeadLetterReplayResponse::from(&replay),
+            (),
+        ))),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "20".to_string(),
+                message: "Dead letter not found".to_string(),
+            })),
+        )),
+        Err(e) => Err(dead_letter_replay_error(e)),
+    }
+}
+
+pub async fn handle_dead_letters_replay<
+    T: EventRepository + Send + Sync + 'static,
+    S: EventProviderClient + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
+>(
+    params: Result<Query<DeadLettersReplayParams>, QueryRejection>,
+    State(state): State<Arc<ApplicationState<T, S, R>>>,
+) -> Result<Json<ApiResponse<DeadLettersReplayResponse, ()>>, (StatusCode, Json<ApiResponse<(), ()>>)>
+{
+    let ApplicationState {
+        ref ingest_event_service,
+        ..
+    } = *state;
+
+    let Query(query) = params.map_err(|err| {
+        debug!("Dead letters replay query params are invalid: {}", err);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "16".to_string(),
+                message: "Invalid pagination params".to_string(),
+            })),
+        )
+    })?;
+
+    match ingest_event_service.replay_dead_letters(query.limit).await {
+        Ok(replays) => Ok(Json(ApiResponse::Ok(
+            DeadLettersReplayResponse {
+                replays: replays.iter().map(DeadLetterReplayResponse::from).collect(),
+            },
+            (),
+        ))),
+        Err(e) => Err(dead_letter_replay_error(e)),
+    }
+}
+
+fn dead_letter_replay_error(
+    error: IngestEventServiceError,
+) -> (StatusCode, Json<ApiResponse<(), ()>>) {
+    match error {
+        IngestEventServiceError::AlreadyRunning(job_id) => (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "78".to_string(),
+                message: match job_id {
+                    Some(job_id) => format!("Event ingestion already in progress: job {job_id}"),
+                    None => "Event ingestion already in progress".to_string(),
+                },
+            })),
+        ),
+        _ => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::Ko(ErrorResponse {
+                code: "99".to_string(),
+                message: "Unexpected error when handling dead letters.".to_string(),
+            })),
+        ),
+    }
+}
+
+fn ingestion_run_lookup_error() -> (StatusCode, Json<ApiResponse<(), ()>>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::Ko(ErrorResponse {
+            code: "99".to_string(),
+            message: "Unexpected error when finding ingestion jobs.".to_string(),
+        })),
+    )
+}
+
 #[derive(Serialize)]
 pub struct HelloWorldResponse {
     greetings: String,
 }
 
+#[derive(Deserialize, Debug)]
+pub struct IngestParams {
+    provider: Option<String>,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct SearchParams {
     start_time: DateTime<Utc>,
     end_time: DateTime<Utc>,
+    /// `within` (default), `overlaps` or `starts_in` the datetimes
+    #[serde(rename = "match")]
+    time_match: Option<String>,
     limit: u64,
     #[serde(default = "default_offset")]
     offset: u64,
+    #[serde(default)]
+    include_zones: bool,
+    /// Adds the total of matching events and navigation links to the metadata
+    #[serde(default)]
+    include_total: bool,
+    #[serde(default)]
+    include_cancelled: bool,
+    price_dropped_since: Option<DateTime<Utc>>,
+    tz: Option<Tz>,
+    /// Text that event titles contain
+    q: Option<String>,
+    min_price: Option<String>,
+    max_price: Option<String>,
+    /// Currency of the price bounds
+    currency: Option<String>,
+    sold_out: Option<bool>,
+    sell_mode: Option<String>,
+    /// Page returned as `next_cursor` or `prev_cursor` by a previous search, instead of `offset`
+    cursor: Option<String>,
+    /// Comma-separated fields of `SORT_FIELDS`, descending when prefixed with `-`
+    sort: Option<String>,
+}
+
+fn parse_time_match(time_match: &str) -> anyhow::Result<TimeMatch> {
+    match time_match {
+        "within" => Ok(TimeMatch::Within),
+        "overlaps" => Ok(TimeMatch::Overlaps),
+        "starts_in" => Ok(TimeMatch::StartsIn),
+        _ => anyhow::bail!("Unknown time match: {time_match}"),
+    }
+}
+
+/// Fields that search results can be sorted by
+const SORT_FIELDS: [(&str, EventSortField); 4] = [
+    ("start_time", EventSortField::StartTime),
+    ("min_price", EventSortField::MinPrice),
+    ("max_price", EventSortField::MaxPrice),
+    ("title", EventSortField::Title),
+];
+
+fn parse_sort(sort: &str) -> anyhow::Result<EventSort> {
+    let mut keys: Vec<EventSortKey> = Vec::new();
+    for item in sort.split(',') {
+        let (name, descending) = match item.strip_prefix('-') {
+            Some(name) => (name, true),
+            None => (item, false),
+        };
+        let field = SORT_FIELDS
+            .iter()
+            .find(|(field_name, _)| *field_name == name)
+            .map(|(_, field)| *field)
+            .ok_or_else(|| anyhow::anyhow!("Unknown sort field: {item:?}"))?;
+        if keys.iter().any(|key| key.field == field) {
+            anyhow::bail!("Repeated sort field: {name}");
+        }
+        keys.push(EventSortKey { field, descending });
+    }
+
+    Ok(EventSort(keys))
+}
+
+impl SearchParams {
+    /// Price bounds as amounts of the given currency, which is required along with them
+    fn price_bounds(&self) -> anyhow::Result<(Option<Money>, Option<Money>)> {
+        if self.min_price.is_none() && self.max_price.is_none() {
+            return Ok((None, None));
+        }
+        let currency = self
+            .currency
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Price bounds need a currency"))
+            .and_then(Currency::from_str)?;
+        let bound = |amount: &Option<String>| {
+            amount
+                .as_deref()
+                .map(|amount| Money::from_decimal_str(amount, currency))
+                .transpose()
+        };
+        let (min_price, max_price) = (bound(&self.min_price)?, bound(&self.max_price)?);
+        if let (Some(min), Some(max)) = (min_price, max_price)
+            && min > max
+        {
+            anyhow::bail!("Minimum price {min} is above maximum price {max}");
+        }
+
+        Ok((min_price, max_price))
+    }
 }
 
 fn default_offset() -> u64 {
@@ -113,45 +873,539 @@ pub struct SearchEventResponse {
     start_time: String,
     end_date: String,
     end_time: String,
-    min_price: f64,
-    max_price: f64,
+    time_zone: String,
+    min_price: MoneyResponse,
+    max_price: MoneyResponse,
+    sell_mode: String,
+    organizer_company_id: Option<String>,
+    sell_from: String,
+    sell_to: String,
+    sold_out: bool,
+    /// Provider the event was ingested from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cancelled_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    zones: Option<Vec<ZoneResponse>>,
+}
+
+#[derive(Serialize)]
+pub struct EventZonesResponse {
+    zones: Vec<ZoneResponse>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct EventPricesParams {
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+pub struct EventPricesResponse {
+    prices: Vec<PricePointResponse>,
+}
+
+#[derive(Serialize)]
+pub struct PricePointResponse {
+    min_price: MoneyResponse,
+    max_price: MoneyResponse,
+    observed_at: String,
+}
+
+impl From<&PricePoint> for PricePointResponse {
+    fn from(value: &PricePoint) -> Self {
+        Self {
+            min_price: value.min_price.into(),
+            max_price: value.max_price.into(),
+            observed_at: value.observed_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct EventHistoryResponse {
+    changes: Vec<EventChangeResponse>,
+}
+
+#[derive(Serialize)]
+pub struct EventChangeResponse {
+    field: String,
+    old_value: Option<String>,
+    new_value: Option<String>,
+    job_id: String,
+    changed_at: String,
+}
+
+impl From<&EventChange> for EventChangeResponse {
+    fn from(value: &EventChange) -> Self {
+        Self {
+            field: value.field.clone(),
+            old_value: value.old_value.clone(),
+            new_value: value.new_value.clone(),
+            job_id: value.ingestion_run_id.into(),
+            changed_at: value.changed_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ZoneResponse {
+    zone_id: String,
+    name: String,
+    price: MoneyResponse,
+    capacity: u32,
+    numbered: bool,
+}
+
+#[derive(Serialize)]
+pub struct MoneyResponse {
+    amount: String,
+    currency: String,
+}
+
+impl From<Money> for MoneyResponse {
+    fn from(value: Money) -> Self {
+        Self {
+            amount: value.to_decimal_string(),
+            currency: value.currency().to_string(),
+        }
+    }
+}
+
+impl From<&Zone> for ZoneResponse {
+    fn from(value: &Zone) -> Self {
+        Self {
+            zone_id: value.zone_id.clone(),
+            name: value.name.clone(),
+            price: value.price.into(),
+            capacity: value.capacity,
+            numbered: value.numbered,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct IngestionRunsParams {
+    #[serde(default = "default_ingestion_runs_limit")]
+    limit: u64,
+    #[serde(default = "default_offset")]
+    offset: u64,
+}
+
+fn default_ingestion_runs_limit() -> u64 {
+    20
+}
+
+#[derive(Serialize)]
+pub struct IngestionRunsResponse {
+    jobs: Vec<IngestionRunResponse>,
+}
+
+#[derive(Serialize)]
+pub struct IngestionRunResponse {
+    job_id: String,
+    state: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<String>,
+    started_at: String,
+    finished_at: Option<String>,
+    fetched: u32,
+    inserted: u32,
+    updated: u32,
+    skipped: u32,
+    failed: u32,
+    cancelled: u32,
+    not_modified: u32,
+    rejected: u32,
+}
+
+impl From<&IngestionRun> for IngestionRunResponse {
+    fn from(value: &IngestionRun) -> Self {
+        Self {
+            job_id: value.id.into(),
+            state: value.state.to_string(),
+            provider: value.provider.clone(),
+            started_at: value.started_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            finished_at: value
+                .finished_at
+                .map(|finished_at| finished_at.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            fetched: value.fetched,
+            inserted: value.inserted,
+            updated: value.updated,
+            skipped: value.skipped,
+            failed: value.failed,
+            cancelled: value.cancelled,
+            not_modified: value.not_modified,
+            rejected: value.rejected,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct IngestionRejectionsParams {
+    #[serde(default = "default_ingestion_rejections_limit")]
+    limit: u64,
+    #[serde(default = "default_offset")]
+    offset: u64,
+}
+
+fn default_ingestion_rejections_limit() -> u64 {
+    100
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DeadLettersParams {
+    #[serde(default = "default_dead_letters_limit")]
+    limit: u64,
+    #[serde(default = "default_offset")]
+    offset: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DeadLettersReplayParams {
+    #[serde(default = "default_dead_letters_limit")]
+    limit: u64,
+}
+
+fn default_dead_letters_limit() -> u64 {
+    100
+}
+
+#[derive(Serialize)]
+pub struct ProvidersResponse {
+    providers: Vec<ProviderResponse>,
+}
+
+#[derive(Serialize)]
+pub struct ProviderResponse {
+    name: String,
+    circuit_state: Option<String>,
+}
+
+impl From<&ProviderStatus> for ProviderResponse {
+    fn from(value: &ProviderStatus) -> Self {
+        Self {
+            name: value.name.clone(),
+            circuit_state: value.circuit_state.map(|state| state.as_str().to_string()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DeadLettersResponse {
+    dead_letters: Vec<DeadLetterResponse>,
+}
+
+#[derive(Serialize)]
+pub struct DeadLetterResponse {
+    id: String,
+    job_id: String,
+    provider: Option<String>,
+    base_plan_id: Option<String>,
+    plan_id: Option<String>,
+    title: String,
+    error: String,
+    attempts: u32,
+    created_at: String,
+    last_failed_at: String,
+}
+
+impl From<&DeadLetter> for DeadLetterResponse {
+    fn from(value: &DeadLetter) -> Self {
+        let external_id = value.request.external_id.as_ref();
+
+        Self {
+            id: value.id.into(),
+            job_id: value.ingestion_run_id.into(),
+            provider: external_id.map(|e| e.provider.clone()),
+            base_plan_id: external_id.map(|e| e.base_plan_id.clone()),
+            plan_id: external_id.map(|e| e.plan_id.clone()),
+            title: value.request.title.clone(),
+            error: value.error.clone(),
+            attempts: value.attempts,
+            created_at: value.created_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            last_failed_at: value
+                .last_failed_at
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DeadLettersReplayResponse {
+    replays: Vec<DeadLetterReplayResponse>,
+}
+
+#[derive(Serialize)]
+pub struct DeadLetterReplayResponse {
+    /// Whether the event got written, in which case the dead letter is gone
+    replayed: bool,
+    /// Whether the dead letter got dropped unwritten, as its event had been written since
+    superseded: bool,
+    /// `inserted`, `updated` or `unchanged` once written
+    outcome: Option<String>,
+    event_id: Option<String>,
+    /// Ingestion job the replay is attributed to
+    replay_job_id: String,
+    dead_letter: DeadLetterResponse,
+}
+
+impl From<&DeadLetterReplay> for DeadLetterReplayResponse {
+    fn from(value: &DeadLetterReplay) -> Self {
+        Self {
+            replayed: value.result.is_some(),
+            superseded: value.superseded,
+            outcome: value.result.as_ref().map(|result| {
+                match result.outcome {
+                    UpsertOutcome::Inserted => "inserted",
+                    UpsertOutcome::Updated => "updated",
+                    UpsertOutcome::Unchanged => "unchanged",
+                }
+                .to_string()
+            }),
+            event_id: value.result.as_ref().map(|result| result.event_id.into()),
+            replay_job_id: value.ingestion_run_id.into(),
+            dead_letter: DeadLetterResponse::from(&value.dead_letter),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct IngestionRejectionsResponse {
+    rejections: Vec<IngestionRejectionResponse>,
+}
+
+#[derive(Serialize)]
+pub struct IngestionRejectionResponse {
+    provider: String,
+    base_plan_id: Option<String>,
+    plan_id: Option<String>,
+    zone_id: Option<String>,
+    reason: String,
+    rejected_at: String,
+}
+
+impl From<&IngestionRejection> for IngestionRejectionResponse {
+    fn from(value: &IngestionRejection) -> Self {
+        Self {
+            provider: value.provider.clone(),
+            base_plan_id: value.base_plan_id.clone(),
+            plan_id: value.plan_id.clone(),
+            zone_id: value.zone_id.clone(),
+            reason: value.reason.clone(),
+            rejected_at: value.rejected_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct IngestionRunsMetadata {
+    limit: u64,
+    offset: u64,
 }
 
 #[derive(Serialize)]
 pub struct SearchMetadata {
     limit: u64,
     offset: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prev_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total: Option<u64>,
+    /// Whether `total` is an estimate, as exact counts stop at a configured limit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_estimated: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    has_more: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    links: Option<SearchLinks>,
 }
 
-impl From<SearchEventServiceResponse> for Json<ApiResponse<SearchResponse, SearchMetadata>> {
-    fn from(value: SearchEventServiceResponse) -> Self {
-        let response = SearchResponse {
-            events: value
-                .events
-                .iter()
-                .map(|e| SearchEventResponse {
+#[derive(Serialize)]
+pub struct SearchLinks {
+    #[serde(rename = "self")]
+    self_link: String,
+    next: Option<String>,
+    prev: Option<String>,
+}
+
+/// Renders searched events with their datetimes in the caller's time zone
+fn search_response(
+    value: SearchEventServiceResponse,
+    sort: &EventSort,
+    tz: Tz,
+    uri: &Uri,
+) -> Json<ApiResponse<SearchResponse, SearchMetadata>> {
+    let response = SearchResponse {
+        events: value
+            .events
+            .iter()
+            .map(|e| {
+                let start_time = e.start_time.with_timezone(&tz);
+                let end_time = e.end_time.with_timezone(&tz);
+                SearchEventResponse {
                     id: e.id.into(),
                     title: e.title.clone(),
-                    start_date: e.start_time.format("%Y-%m-%d").to_string(),
-                    start_time: e.start_time.format("%H:%M:%S").to_string(),
-                    end_date: e.end_time.format("%Y-%m-%d").to_string(),
-                    end_time: e.end_time.format("%H:%M:%S").to_string(),
-                    min_price: e.min_price,
-                    max_price: e.max_price,
-                })
-                .collect(),
-        };
+                    start_date: start_time.format("%Y-%m-%d").to_string(),
+                    start_time: start_time.format("%H:%M:%S%:z").to_string(),
+                    end_date: end_time.format("%Y-%m-%d").to_string(),
+                    end_time: end_time.format("%H:%M:%S%:z").to_string(),
+                    time_zone: e.time_zone.name().to_string(),
+                    min_price: e.min_price.into(),
+                    max_price: e.max_price.into(),
+                    sell_mode: e.sell_mode.to_string(),
+                    organizer_company_id: e.organizer_company_id.clone(),
+                    sell_from: e
+                        .sell_from
+                        .with_timezone(&tz)
+                        .to_rfc3339_opts(SecondsFormat::Secs, true),
+                    sell_to: e
+                        .sell_to
+                        .with_timezone(&tz)
+                        .to_rfc3339_opts(SecondsFormat::Secs, true),
+                    sold_out: e.sold_out,
+                    source: e
+                        .external_id
+                        .as_ref()
+                        .map(|external_id| external_id.provider.clone()),
+                    cancelled_at: e.cancelled_at.map(|cancelled_at| {
+                        cancelled_at
+                            .with_timezone(&tz)
+                            .to_rfc3339_opts(SecondsFormat::Secs, true)
+                    }),
+                    zones: value.zones.as_ref().map(|zones| {
+                        zones
+                            .get(&e.id)
+                            .map(|zones| zones.iter().map(ZoneResponse::from).collect())
+                            .unwrap_or_default()
+                    }),
+                }
+            })
+            .collect(),
+    };
 
-        Json(ApiResponse::Ok(
-            response,
-            SearchMetadata {
-                limit: value.limit,
-                offset: value.offset,
-            },
-        ))
+    let next_cursor = value.next_page.and_then(|page| page_cursor(page, sort));
+    let prev_cursor = value.prev_page.and_then(|page| page_cursor(page, sort));
+
+    Json(ApiResponse::Ok(
+        response,
+        SearchMetadata {
+            limit: value.limit,
+            offset: value.offset,
+            total: value.total.map(|total| match total {
+                EventCount::Exact(count) | EventCount::Estimated(count) => count,
+            }),
+            total_estimated: value
+                .total
+                .map(|total| matches!(total, EventCount::Estimated(_))),
+            has_more: value.total.map(|_| next_cursor.is_some()),
+            links: value.total.map(|_| SearchLinks {
+                self_link: uri
+                    .path_and_query()
+                    .map_or_else(|| uri.path().to_string(), |path| path.to_string()),
+                next: next_cursor.as_deref().map(|cursor| page_link(uri, cursor)),
+                prev: prev_cursor.as_deref().map(|cursor| page_link(uri, cursor)),
+            }),
+            next_cursor,
+            prev_cursor,
+        },
+    ))
+}
+
+/// Link to the search page at the cursor, keeping every other param of the request
+fn page_link(uri: &Uri, cursor: &str) -> String {
+    let cursor_param = format!("cursor={cursor}");
+    let params: Vec<&str> = uri
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter(|param| {
+            let name = param.split('=').next().unwrap_or_default();
+            !name.is_empty() && name != "cursor" && name != "offset"
+        })
+        .chain([cursor_param.as_str()])
+        .collect();
+
+    format!("{}?{}", uri.path(), params.join("&"))
+}
+
+/// Opaque cursor of a page next to a given event, which offset pages have none of
+fn page_cursor(page: EventPage, sort: &EventSort) -> Option<String> {
+    let (direction, key) = match page {
+        EventPage::Offset(_) => return None,
+        EventPage::After(key) => ("after", key),
+        EventPage::Before(key) => ("before", key),
+    };
+    // The title goes last as it may contain the separator
+    let cursor = format!(
+        "{direction}|{}|{}|{}|{}|{}|{}",
+        sort_spec(sort),
+        key.start_time.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        key.min_price,
+        key.max_price,
+        key.id,
+        key.title
+    );
+
+    Some(URL_SAFE_NO_PAD.encode(cursor))
+}
+
+/// Parses a cursor, which is only valid for the sort order of the search that returned it
+fn parse_cursor(cursor: &str, sort: &EventSort) -> anyhow::Result<EventPage> {
+    let decoded = String::from_utf8(URL_SAFE_NO_PAD.decode(cursor)?)?;
+    let parts: Vec<&str> = decoded.splitn(7, '|').collect();
+    let [
+        direction,
+        cursor_sort,
+        start_time,
+        min_price,
+        max_price,
+        id,
+        title,
+    ] = parts[..]
+    else {
+        anyhow::bail!("Malformed cursor: {decoded}");
+    };
+    if cursor_sort != sort_spec(sort) {
+        anyhow::bail!("Cursor of another sort order: {cursor_sort}");
+    }
+    let key = EventKey {
+        start_time: DateTime::parse_from_rfc3339(start_time)?.with_timezone(&Utc),
+        min_price: min_price.parse()?,
+        max_price: max_price.parse()?,
+        title: title.to_string(),
+        id: Uuid::from_str(id)?,
+    };
+
+    match direction {
+        "after" => Ok(EventPage::After(key)),
+        "before" => Ok(EventPage::Before(key)),
+        _ => anyhow::bail!("Unknown cursor direction: {direction}"),
     }
 }
 
+/// Sort order in the format of the `sort` param
+fn sort_spec(sort: &EventSort) -> String {
+    sort.0
+        .iter()
+        .map(|key| {
+            let name = SORT_FIELDS
+                .iter()
+                .find(|(_, field)| *field == key.field)
+                .map_or("", |(name, _)| *name);
+            if key.descending {
+                format!("-{name}")
+            } else {
+                name.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 impl From<SearchEventServiceError> for (StatusCode, Json<ApiResponse<(), ()>>) {
     fn from(_value: SearchEventServiceError) -> Self {
         (
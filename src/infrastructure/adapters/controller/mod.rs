@@ -13,18 +13,19 @@ use state::ApplicationState;
 use tower_http::{timeout::TimeoutLayer, trace::TraceLayer};
 
 use crate::application::ports::provider::EventProviderClient;
-use crate::application::ports::repository::EventRepository;
+use crate::application::ports::repository::{EventRepository, IngestionRunRepository};
 use crate::application::service::{IngestEventService, SearchEventService};
 use crate::infrastructure::config::ApplicationConfig;
 
-pub async fn init_controller<T, S>(
+pub async fn init_controller<T, S, R>(
     search_event_service: SearchEventService<T>,
-    ingest_event_service: IngestEventService<S, T>,
+    ingest_event_service: Arc<IngestEventService<S, T, R>>,
     config: &ApplicationConfig,
 ) -> anyhow::Result<Router>
 where
     T: EventRepository + Send + Sync + 'static,
     S: EventProviderClient + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
 {
     let state = init_application_state(config, search_event_service, ingest_event_service).await;
 
@@ -38,14 +39,15 @@ where
     Ok(app)
 }
 
-async fn init_application_state<T, S>(
+async fn init_application_state<T, S, R>(
     config: &ApplicationConfig,
     search_event_service: SearchEventService<T>,
-    ingest_event_service: IngestEventService<S, T>,
-) -> ApplicationState<T, S>
+    ingest_event_service: Arc<IngestEventService<S, T, R>>,
+) -> ApplicationState<T, S, R>
 where
     T: EventRepository + Send + Sync + 'static,
     S: EventProviderClient + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
 {
     ApplicationState {
         config: ArcSwap::new(Arc::new(config.clone())),
@@ -56,33 +58,92 @@ where
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashMap, str::FromStr};
+    use std::{collections::HashMap, str::FromStr, sync::Mutex, time::Duration};
 
     use axum::{
         body::Body,
-        http::{Request, StatusCode},
+        http::{Request, StatusCode, header},
     };
     use chrono::DateTime;
+    use chrono_tz::Tz;
     use http_body_util::BodyExt;
+    use httpmock::MockServer;
     use serde_json::{Value, json};
     use tower::ServiceExt;
     use uuid::Uuid;
 
     use super::*;
     use crate::{
-        domain::event::Event,
+        application::{
+            ports::{
+                provider::ProviderEvent,
+                repository::{DeadLetter, SaveEventRequest},
+            },
+            service::IngestionSettings,
+        },
+        domain::{
+            event::{Event, ExternalId, SellMode},
+            event_change::EventChange,
+            ingestion_run::{IngestionRejection, IngestionRun, IngestionRunState},
+            money::{Currency, Money},
+            price_point::PricePoint,
+            zone::Zone,
+        },
         infrastructure::adapters::{
-            provider::DummyEventProviderClient,
-            repository::{DummyEventRepository, FailingEventRepository},
+            provider::{
+                CircuitBreaker, DummyEventProviderClient, HttpEventProviderClient, RetryPolicy,
+            },
+            repository::{
+                DummyEventRepository, DummyIngestionRunRepository, FailingEventRepository,
+            },
         },
     };
 
+    fn eur(amount_in_cents: i64) -> Money {
+        Money::new(amount_in_cents, Currency::from_str("EUR").unwrap())
+    }
+
+    fn dead_letter() -> DeadLetter {
+        DeadLetter {
+            id: Uuid::from_str("a3bb189e-8bf9-3888-9912-ace4e6543002").unwrap(),
+            ingestion_run_id: Uuid::from_str("7c9e6679-7425-40de-944b-e07fc1f90ae7").unwrap(),
+            request: SaveEventRequest {
+                external_id: Some(ExternalId {
+                    provider: "test-provider".to_string(),
+                    base_plan_id: "291".to_string(),
+                    plan_id: "291".to_string(),
+                }),
+                title: "Camela en concierto".to_string(),
+                start_time: DateTime::from_str("2021-06-30T19:00:00Z").unwrap(),
+                end_time: DateTime::from_str("2021-06-30T20:00:00Z").unwrap(),
+                time_zone: Tz::Europe__Madrid,
+                min_price: eur(1500),
+                max_price: eur(3000),
+                sell_mode: SellMode::Online,
+                organizer_company_id: None,
+                sell_from: DateTime::from_str("2020-06-30T22:00:00Z").unwrap(),
+                sell_to: DateTime::from_str("2021-06-30T18:00:00Z").unwrap(),
+                sold_out: false,
+                zones: Vec::new(),
+            },
+            error: "Failed to upsert event records in event database".to_string(),
+            attempts: 1,
+            created_at: DateTime::from_str("2025-11-12T02:00:01Z").unwrap(),
+            last_failed_at: DateTime::from_str("2025-11-12T02:00:01Z").unwrap(),
+        }
+    }
+
     #[tokio::test]
     async fn root_endpoint_returns_ok_response() {
-        let event_repository = Arc::new(DummyEventRepository(HashMap::new()));
+        let event_repository = Arc::new(DummyEventRepository::default());
         let event_provider_client = Arc::new(DummyEventProviderClient);
         let search_event_service = SearchEventService::new(event_repository.clone());
-        let ingest_event_service = IngestEventService::new(event_provider_client, event_repository);
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
         let config = ApplicationConfig::default();
         let app = init_controller(search_event_service, ingest_event_service, &config)
             .await
@@ -111,20 +172,36 @@ mod tests {
 
     #[tokio::test]
     async fn event_search_endpoint_returns_events_within_datetimes() {
-        let event_repository = Arc::new(DummyEventRepository(HashMap::from([(
-            Uuid::from_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap(),
-            Event {
-                id: Uuid::from_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap(),
-                title: "Quevedo".to_string(),
-                start_time: DateTime::from_str("2025-11-12T22:00:00Z").unwrap(),
-                end_time: DateTime::from_str("2025-11-12T23:00:00Z").unwrap(),
-                min_price: 15.99,
-                max_price: 39.99,
-            },
-        )])));
+        let event_repository = Arc::new(DummyEventRepository {
+            events: HashMap::from([(
+                Uuid::from_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap(),
+                Event {
+                    id: Uuid::from_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap(),
+                    external_id: None,
+                    title: "Quevedo".to_string(),
+                    start_time: DateTime::from_str("2025-11-12T22:00:00Z").unwrap(),
+                    end_time: DateTime::from_str("2025-11-12T23:00:00Z").unwrap(),
+                    time_zone: Tz::Europe__Madrid,
+                    min_price: eur(1599),
+                    max_price: eur(3999),
+                    sell_mode: SellMode::Online,
+                    organizer_company_id: Some("1".to_string()),
+                    sell_from: DateTime::from_str("2025-06-01T00:00:00Z").unwrap(),
+                    sell_to: DateTime::from_str("2025-11-12T20:00:00Z").unwrap(),
+                    sold_out: false,
+                    cancelled_at: None,
+                },
+            )]),
+            ..Default::default()
+        });
         let event_provider_client = Arc::new(DummyEventProviderClient);
         let search_event_service = SearchEventService::new(event_repository.clone());
-        let ingest_event_service = IngestEventService::new(event_provider_client, event_repository);
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
         let config = ApplicationConfig::default();
         let app = init_controller(search_event_service, ingest_event_service, &config)
             .await
@@ -152,11 +229,98 @@ mod tests {
                         "id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
                         "title": "Quevedo",
                         "start_date": "2025-11-12",
-                        "start_time": "22:00:00",
+                        "start_time": "22:00:00+00:00",
                         "end_date": "2025-11-12",
-                        "end_time": "23:00:00",
-                        "min_price": 15.99,
-                        "max_price": 39.99,
+                        "end_time": "23:00:00+00:00",
+                        "time_zone": "Europe/Madrid",
+                        "min_price": { "amount": "15.99", "currency": "EUR" },
+                        "max_price": { "amount": "39.99", "currency": "EUR" },
+                        "sell_mode": "online",
+                        "organizer_company_id": "1",
+                        "sell_from": "2025-06-01T00:00:00Z",
+                        "sell_to": "2025-11-12T20:00:00Z",
+                        "sold_out": false,
+                    }]
+                },
+                "error": null,
+                "meta": {
+                    "limit": 100,
+                    "offset": 0,
+                }
+            })
+        )
+    }
+
+    #[tokio::test]
+    async fn event_search_endpoint_renders_datetimes_in_requested_time_zone() {
+        let event_repository = Arc::new(DummyEventRepository {
+            events: HashMap::from([(
+                Uuid::from_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap(),
+                Event {
+                    id: Uuid::from_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap(),
+                    external_id: None,
+                    title: "Quevedo".to_string(),
+                    start_time: DateTime::from_str("2025-11-12T22:00:00Z").unwrap(),
+                    end_time: DateTime::from_str("2025-11-12T23:00:00Z").unwrap(),
+                    time_zone: Tz::Europe__Madrid,
+                    min_price: eur(1599),
+                    max_price: eur(3999),
+                    sell_mode: SellMode::Online,
+                    organizer_company_id: Some("1".to_string()),
+                    sell_from: DateTime::from_str("2025-06-01T00:00:00Z").unwrap(),
+                    sell_to: DateTime::from_str("2025-11-12T20:00:00Z").unwrap(),
+                    sold_out: false,
+                    cancelled_at: None,
+                },
+            )]),
+            ..Default::default()
+        });
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app.oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/api/v1/search?start_time=2025-11-01T08:00:00Z&end_time=2025-11-30T18:00:00Z&limit=100&tz=Europe/Madrid")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "data": {
+                    "events": [{
+                        "id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+                        "title": "Quevedo",
+                        "start_date": "2025-11-12",
+                        "start_time": "23:00:00+01:00",
+                        "end_date": "2025-11-13",
+                        "end_time": "00:00:00+01:00",
+                        "time_zone": "Europe/Madrid",
+                        "min_price": { "amount": "15.99", "currency": "EUR" },
+                        "max_price": { "amount": "39.99", "currency": "EUR" },
+                        "sell_mode": "online",
+                        "organizer_company_id": "1",
+                        "sell_from": "2025-06-01T02:00:00+02:00",
+                        "sell_to": "2025-11-12T21:00:00+01:00",
+                        "sold_out": false,
                     }]
                 },
                 "error": null,
@@ -168,12 +332,844 @@ mod tests {
         )
     }
 
+    #[tokio::test]
+    async fn event_search_endpoint_returns_cancelled_events_only_when_requested() {
+        let live_event = Event {
+            id: Uuid::from_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap(),
+            external_id: None,
+            title: "Quevedo".to_string(),
+            start_time: DateTime::from_str("2025-11-12T22:00:00Z").unwrap(),
+            end_time: DateTime::from_str("2025-11-12T23:00:00Z").unwrap(),
+            time_zone: Tz::Europe__Madrid,
+            min_price: eur(1599),
+            max_price: eur(3999),
+            sell_mode: SellMode::Online,
+            organizer_company_id: Some("1".to_string()),
+            sell_from: DateTime::from_str("2025-06-01T00:00:00Z").unwrap(),
+            sell_to: DateTime::from_str("2025-11-12T20:00:00Z").unwrap(),
+            sold_out: false,
+            cancelled_at: None,
+        };
+        let cancelled_event = Event {
+            id: Uuid::from_str("9f4d6f1e-2b1c-4c47-8a0e-6a7c1f0f6b21").unwrap(),
+            external_id: Some(ExternalId {
+                provider: "acme".to_string(),
+                base_plan_id: "1".to_string(),
+                plan_id: "2".to_string(),
+            }),
+            title: "Nirvana".to_string(),
+            cancelled_at: Some(DateTime::from_str("2025-11-01T02:00:00Z").unwrap()),
+            ..live_event.clone()
+        };
+        let event_repository = Arc::new(DummyEventRepository {
+            events: HashMap::from([
+                (live_event.id, live_event),
+                (cancelled_event.id, cancelled_event),
+            ]),
+            ..Default::default()
+        });
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+        let search = |include_cancelled: bool| {
+            Request::builder()
+                .method("GET")
+                .uri(format!("/api/v1/search?start_time=2025-11-01T08:00:00Z&end_time=2025-11-30T18:00:00Z&limit=100&include_cancelled={include_cancelled}"))
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let default_response = app.clone().oneshot(search(false)).await.unwrap();
+        let cancelled_response = app.oneshot(search(true)).await.unwrap();
+
+        assert_eq!(default_response.status(), StatusCode::OK);
+        let body: Value = serde_json::from_slice(
+            &(default_response
+                .into_body()
+                .collect()
+                .await
+                .unwrap()
+                .to_bytes()),
+        )
+        .unwrap();
+        assert_eq!(body["data"]["events"].as_array().unwrap().len(), 1);
+        assert_eq!(body["data"]["events"][0]["title"], "Quevedo");
+        assert!(body["data"]["events"][0].get("cancelled_at").is_none());
+
+        assert_eq!(cancelled_response.status(), StatusCode::OK);
+        let body: Value = serde_json::from_slice(
+            &(cancelled_response
+                .into_body()
+                .collect()
+                .await
+                .unwrap()
+                .to_bytes()),
+        )
+        .unwrap();
+        let cancelled: Vec<&Value> = body["data"]["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|e| e["title"] == "Nirvana")
+            .collect();
+        assert_eq!(body["data"]["events"].as_array().unwrap().len(), 2);
+        assert_eq!(cancelled[0]["cancelled_at"], "2025-11-01T02:00:00Z");
+        assert_eq!(cancelled[0]["source"], "acme");
+    }
+
+    #[tokio::test]
+    async fn event_search_endpoint_returns_events_whose_price_dropped_since_given_datetime() {
+        let discounted_event = Event {
+            id: Uuid::from_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap(),
+            external_id: None,
+            title: "Quevedo".to_string(),
+            start_time: DateTime::from_str("2025-11-12T22:00:00Z").unwrap(),
+            end_time: DateTime::from_str("2025-11-12T23:00:00Z").unwrap(),
+            time_zone: Tz::Europe__Madrid,
+            min_price: eur(1299),
+            max_price: eur(3999),
+            sell_mode: SellMode::Online,
+            organizer_company_id: Some("1".to_string()),
+            sell_from: DateTime::from_str("2025-06-01T00:00:00Z").unwrap(),
+            sell_to: DateTime::from_str("2025-11-12T20:00:00Z").unwrap(),
+            sold_out: false,
+            cancelled_at: None,
+        };
+        let full_price_event = Event {
+            id: Uuid::from_str("9f4d6f1e-2b1c-4c47-8a0e-6a7c1f0f6b21").unwrap(),
+            title: "Nirvana".to_string(),
+            min_price: eur(1599),
+            ..discounted_event.clone()
+        };
+        let price_point = |min_price: i64, observed_at: &str| PricePoint {
+            min_price: eur(min_price),
+            max_price: eur(3999),
+            observed_at: DateTime::from_str(observed_at).unwrap(),
+        };
+        let event_repository = Arc::new(DummyEventRepository {
+            events: HashMap::from([
+                (discounted_event.id, discounted_event.clone()),
+                (full_price_event.id, full_price_event.clone()),
+            ]),
+            prices: HashMap::from([
+                (
+                    discounted_event.id,
+                    vec![
+                        price_point(1599, "2025-10-01T02:00:00Z"),
+                        price_point(1299, "2025-10-20T02:00:00Z"),
+                    ],
+                ),
+                (
+                    full_price_event.id,
+                    vec![
+                        price_point(1299, "2025-10-01T02:00:00Z"),
+                        price_point(1599, "2025-10-20T02:00:00Z"),
+                    ],
+                ),
+            ]),
+            ..Default::default()
+        });
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/api/v1/search?start_time=2025-11-01T08:00:00Z&end_time=2025-11-30T18:00:00Z&limit=100&price_dropped_since=2025-10-15T00:00:00Z")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(body["data"]["events"].as_array().unwrap().len(), 1);
+        assert_eq!(body["data"]["events"][0]["title"], "Quevedo");
+    }
+
+    #[tokio::test]
+    async fn event_search_endpoint_filters_events_by_title_price_sold_out_and_sell_mode() {
+        let quevedo = Event {
+            id: Uuid::from_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap(),
+            external_id: None,
+            title: "Quevedo en concierto".to_string(),
+            start_time: DateTime::from_str("2025-11-12T22:00:00Z").unwrap(),
+            end_time: DateTime::from_str("2025-11-12T23:00:00Z").unwrap(),
+            time_zone: Tz::Europe__Madrid,
+            min_price: eur(1599),
+            max_price: eur(3999),
+            sell_mode: SellMode::Online,
+            organizer_company_id: Some("1".to_string()),
+            sell_from: DateTime::from_str("2025-06-01T00:00:00Z").unwrap(),
+            sell_to: DateTime::from_str("2025-11-12T20:00:00Z").unwrap(),
+            sold_out: false,
+            cancelled_at: None,
+        };
+        let expensive = Event {
+            id: Uuid::from_str("9f4d6f1e-2b1c-4c47-8a0e-6a7c1f0f6b21").unwrap(),
+            title: "Quevedo VIP".to_string(),
+            min_price: eur(9999),
+            max_price: eur(19999),
+            ..quevedo.clone()
+        };
+        let sold_out = Event {
+            id: Uuid::from_str("1b4e28ba-2fa1-11d2-883f-0016d3cca427").unwrap(),
+            title: "Quevedo en Barcelona".to_string(),
+            sold_out: true,
+            ..quevedo.clone()
+        };
+        let offline = Event {
+            id: Uuid::from_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap(),
+            title: "Quevedo en taquilla".to_string(),
+            sell_mode: SellMode::Offline,
+            ..quevedo.clone()
+        };
+        let other = Event {
+            id: Uuid::from_str("6ba7b811-9dad-11d1-80b4-00c04fd430c8").unwrap(),
+            title: "Nirvana".to_string(),
+            ..quevedo.clone()
+        };
+        let event_repository = Arc::new(DummyEventRepository {
+            events: [quevedo, expensive, sold_out, offline, other]
+                .into_iter()
+                .map(|e| (e.id, e))
+                .collect(),
+            ..Default::default()
+        });
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/api/v1/search?start_time=2025-11-01T08:00:00Z&end_time=2025-11-30T18:00:00Z&limit=100&q=QUEVEDO&max_price=50&currency=EUR&sold_out=false&sell_mode=online")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(body["data"]["events"].as_array().unwrap().len(), 1);
+        assert_eq!(body["data"]["events"][0]["title"], "Quevedo en concierto");
+    }
+
+    #[tokio::test]
+    async fn event_search_endpoint_returns_client_error_when_filters_are_invalid() {
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        for filters in [
+            "min_price=10",
+            "min_price=ten&currency=EUR",
+            "min_price=50&max_price=10&currency=EUR",
+            "sell_mode=teleported",
+            "match=around",
+        ] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method("GET")
+                        .uri(format!("/api/v1/search?start_time=2025-11-01T08:00:00Z&end_time=2025-11-30T18:00:00Z&limit=100&{filters}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{filters}");
+            let body: Value =
+                serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                    .unwrap();
+            assert_eq!(
+                body,
+                json!({ "data": null, "meta": null, "error": { "code": "21", "message": "Invalid search filters" } })
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn event_search_endpoint_returns_client_error_when_range_is_inverted() {
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        for time_match in ["within", "overlaps", "starts_in"] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method("GET")
+                        .uri(format!("/api/v1/search?start_time=2025-11-30T18:00:00Z&end_time=2025-11-01T08:00:00Z&limit=100&match={time_match}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{time_match}");
+            let body: Value =
+                serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                    .unwrap();
+            assert_eq!(
+                body,
+                json!({ "data": null, "meta": null, "error": { "code": "25", "message": "Invalid search range" } })
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn event_search_endpoint_matches_event_times_as_requested() {
+        let event = |id: &str, title: &str, start_time: &str, end_time: &str| Event {
+            id: Uuid::from_str(id).unwrap(),
+            external_id: None,
+            title: title.to_string(),
+            start_time: DateTime::from_str(start_time).unwrap(),
+            end_time: DateTime::from_str(end_time).unwrap(),
+            time_zone: Tz::Europe__Madrid,
+            min_price: eur(1599),
+            max_price: eur(3999),
+            sell_mode: SellMode::Online,
+            organizer_company_id: None,
+            sell_from: DateTime::from_str("2025-06-01T00:00:00Z").unwrap(),
+            sell_to: DateTime::from_str("2025-11-10T20:00:00Z").unwrap(),
+            sold_out: false,
+            cancelled_at: None,
+        };
+        let event_repository = Arc::new(DummyEventRepository {
+            events: [
+                event(
+                    "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+                    "Festival",
+                    "2025-11-11T10:00:00Z",
+                    "2025-11-13T02:00:00Z",
+                ),
+                event(
+                    "9f4d6f1e-2b1c-4c47-8a0e-6a7c1f0f6b21",
+                    "Concierto",
+                    "2025-11-12T20:00:00Z",
+                    "2025-11-12T22:00:00Z",
+                ),
+                event(
+                    "1b4e28ba-2fa1-11d2-883f-0016d3cca427",
+                    "Sesion golfa",
+                    "2025-11-12T23:00:00Z",
+                    "2025-11-13T01:00:00Z",
+                ),
+                event(
+                    "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+                    "Matinal",
+                    "2025-11-10T10:00:00Z",
+                    "2025-11-10T12:00:00Z",
+                ),
+            ]
+            .into_iter()
+            .map(|e| (e.id, e))
+            .collect(),
+            ..Default::default()
+        });
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        for (time_match, expected) in [
+            ("within", vec!["Concierto"]),
+            ("overlaps", vec!["Festival", "Concierto", "Sesion golfa"]),
+            ("starts_in", vec!["Concierto", "Sesion golfa"]),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method("GET")
+                        .uri(format!("/api/v1/search?start_time=2025-11-12T00:00:00Z&end_time=2025-11-12T23:59:59Z&limit=100&match={time_match}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::OK);
+            let body: Value =
+                serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                    .unwrap();
+            let titles: Vec<&str> = body["data"]["events"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|e| e["title"].as_str().unwrap())
+                .collect();
+            assert_eq!(titles, expected, "{time_match}");
+        }
+    }
+
+    #[tokio::test]
+    async fn event_search_endpoint_pages_through_events_with_cursors() {
+        let event = |id: &str, title: &str, start_time: &str| Event {
+            id: Uuid::from_str(id).unwrap(),
+            external_id: None,
+            title: title.to_string(),
+            start_time: DateTime::from_str(start_time).unwrap(),
+            end_time: DateTime::from_str("2025-11-20T23:00:00Z").unwrap(),
+            time_zone: Tz::Europe__Madrid,
+            min_price: eur(1599),
+            max_price: eur(3999),
+            sell_mode: SellMode::Online,
+            organizer_company_id: None,
+            sell_from: DateTime::from_str("2025-06-01T00:00:00Z").unwrap(),
+            sell_to: DateTime::from_str("2025-11-12T20:00:00Z").unwrap(),
+            sold_out: false,
+            cancelled_at: None,
+        };
+        let event_repository = Arc::new(DummyEventRepository {
+            events: [
+                event(
+                    "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+                    "Quevedo",
+                    "2025-11-12T22:00:00Z",
+                ),
+                event(
+                    "9f4d6f1e-2b1c-4c47-8a0e-6a7c1f0f6b21",
+                    "Nirvana",
+                    "2025-11-10T22:00:00Z",
+                ),
+                event(
+                    "1b4e28ba-2fa1-11d2-883f-0016d3cca427",
+                    "Camela",
+                    "2025-11-12T22:00:00Z",
+                ),
+                event(
+                    "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+                    "Tool",
+                    "2025-11-14T22:00:00Z",
+                ),
+                event(
+                    "6ba7b811-9dad-11d1-80b4-00c04fd430c8",
+                    "Los Morancos",
+                    "2025-11-15T22:00:00Z",
+                ),
+            ]
+            .into_iter()
+            .map(|e| (e.id, e))
+            .collect(),
+            ..Default::default()
+        });
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+        let search = |page: String| {
+            let app = app.clone();
+            async move {
+                let response = app
+                    .oneshot(
+                        Request::builder()
+                            .method("GET")
+                            .uri(format!("/api/v1/search?start_time=2025-11-01T08:00:00Z&end_time=2025-11-30T18:00:00Z&limit=2&{page}"))
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let body: Value = serde_json::from_slice(
+                    &(response.into_body().collect().await.unwrap().to_bytes()),
+                )
+                .unwrap();
+                let titles: Vec<String> = body["data"]["events"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|e| e["title"].as_str().unwrap().to_string())
+                    .collect();
+                (titles, body["meta"].clone())
+            }
+        };
+
+        let (first_page, meta) = search("offset=0".to_string()).await;
+        assert_eq!(first_page, vec!["Nirvana", "Camela"]);
+        assert!(meta.get("prev_cursor").is_none());
+        let (second_page, meta) =
+            search(format!("cursor={}", meta["next_cursor"].as_str().unwrap())).await;
+        assert_eq!(second_page, vec!["Quevedo", "Tool"]);
+        let prev_cursor = meta["prev_cursor"].as_str().unwrap().to_string();
+        let (last_page, meta) =
+            search(format!("cursor={}", meta["next_cursor"].as_str().unwrap())).await;
+        assert_eq!(last_page, vec!["Los Morancos"]);
+        assert!(meta.get("next_cursor").is_none());
+        let (back_to_second_page, meta) =
+            search(format!("cursor={}", meta["prev_cursor"].as_str().unwrap())).await;
+        assert_eq!(back_to_second_page, vec!["Quevedo", "Tool"]);
+        assert_eq!(meta["prev_cursor"], prev_cursor.as_str());
+        let (back_to_first_page, meta) = search(format!("cursor={prev_cursor}")).await;
+        assert_eq!(back_to_first_page, vec!["Nirvana", "Camela"]);
+        assert!(meta.get("prev_cursor").is_none());
+        assert!(meta.get("next_cursor").is_some());
+    }
+
+    #[tokio::test]
+    async fn event_search_endpoint_sorts_events_by_requested_keys() {
+        let event = |id: &str, title: &str, max_price: i64| Event {
+            id: Uuid::from_str(id).unwrap(),
+            external_id: None,
+            title: title.to_string(),
+            start_time: DateTime::from_str("2025-11-12T22:00:00Z").unwrap(),
+            end_time: DateTime::from_str("2025-11-12T23:00:00Z").unwrap(),
+            time_zone: Tz::Europe__Madrid,
+            min_price: eur(1599),
+            max_price: eur(max_price),
+            sell_mode: SellMode::Online,
+            organizer_company_id: None,
+            sell_from: DateTime::from_str("2025-06-01T00:00:00Z").unwrap(),
+            sell_to: DateTime::from_str("2025-11-12T20:00:00Z").unwrap(),
+            sold_out: false,
+            cancelled_at: None,
+        };
+        let event_repository = Arc::new(DummyEventRepository {
+            events: [
+                event("3fa85f64-5717-4562-b3fc-2c963f66afa6", "Quevedo", 3999),
+                event("9f4d6f1e-2b1c-4c47-8a0e-6a7c1f0f6b21", "Tool", 19999),
+                event("1b4e28ba-2fa1-11d2-883f-0016d3cca427", "Camela", 3999),
+                event("6ba7b810-9dad-11d1-80b4-00c04fd430c8", "Nirvana", 9999),
+            ]
+            .into_iter()
+            .map(|e| (e.id, e))
+            .collect(),
+            ..Default::default()
+        });
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+        let search = |params: String| {
+            let app = app.clone();
+            async move {
+                let response = app
+                    .oneshot(
+                        Request::builder()
+                            .method("GET")
+                            .uri(format!("/api/v1/search?start_time=2025-11-01T08:00:00Z&end_time=2025-11-30T18:00:00Z&{params}"))
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                let status = response.status();
+                let body: Value = serde_json::from_slice(
+                    &(response.into_body().collect().await.unwrap().to_bytes()),
+                )
+                .unwrap();
+                (status, body)
+            }
+        };
+        let titles = |body: &Value| -> Vec<String> {
+            body["data"]["events"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|e| e["title"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        let (status, body) = search("limit=100&sort=-max_price,title".to_string()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(titles(&body), vec!["Tool", "Nirvana", "Camela", "Quevedo"]);
+
+        let (_, first_page) = search("limit=3&sort=-max_price,title".to_string()).await;
+        let next_cursor = first_page["meta"]["next_cursor"].as_str().unwrap();
+        let (_, last_page) = search(format!(
+            "limit=3&sort=-max_price,title&cursor={next_cursor}"
+        ))
+        .await;
+        assert_eq!(titles(&last_page), vec!["Quevedo"]);
+        let prev_cursor = last_page["meta"]["prev_cursor"].as_str().unwrap();
+        let (_, back_to_first_page) = search(format!(
+            "limit=3&sort=-max_price,title&cursor={prev_cursor}"
+        ))
+        .await;
+        assert_eq!(
+            titles(&back_to_first_page),
+            vec!["Tool", "Nirvana", "Camela"]
+        );
+
+        for sort_param in ["&sort=title", "&sort=max_price,title", ""] {
+            let (status, body) = search(format!("limit=3{sort_param}&cursor={next_cursor}")).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{sort_param}");
+            assert_eq!(
+                body,
+                json!({ "data": null, "meta": null, "error": { "code": "22", "message": "Invalid cursor" } })
+            );
+        }
+
+        for sort in ["sold_out", "title,-title", "", "id"] {
+            let (status, body) = search(format!("limit=100&sort={sort}")).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{sort}");
+            assert_eq!(
+                body,
+                json!({ "data": null, "meta": null, "error": { "code": "23", "message": "Invalid sort" } })
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn event_search_endpoint_reports_total_and_links_when_requested() {
+        let event = |id: &str, title: &str, start_time: &str| Event {
+            id: Uuid::from_str(id).unwrap(),
+            external_id: None,
+            title: title.to_string(),
+            start_time: DateTime::from_str(start_time).unwrap(),
+            end_time: DateTime::from_str("2025-11-20T23:00:00Z").unwrap(),
+            time_zone: Tz::Europe__Madrid,
+            min_price: eur(1599),
+            max_price: eur(3999),
+            sell_mode: SellMode::Online,
+            organizer_company_id: None,
+            sell_from: DateTime::from_str("2025-06-01T00:00:00Z").unwrap(),
+            sell_to: DateTime::from_str("2025-11-12T20:00:00Z").unwrap(),
+            sold_out: false,
+            cancelled_at: None,
+        };
+        let event_repository = Arc::new(DummyEventRepository {
+            events: [
+                event(
+                    "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+                    "Quevedo",
+                    "2025-11-12T22:00:00Z",
+                ),
+                event(
+                    "9f4d6f1e-2b1c-4c47-8a0e-6a7c1f0f6b21",
+                    "Nirvana",
+                    "2025-11-10T22:00:00Z",
+                ),
+                event(
+                    "1b4e28ba-2fa1-11d2-883f-0016d3cca427",
+                    "Camela",
+                    "2025-11-12T22:00:00Z",
+                ),
+                event(
+                    "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+                    "Tool",
+                    "2025-11-14T22:00:00Z",
+                ),
+                event(
+                    "6ba7b811-9dad-11d1-80b4-00c04fd430c8",
+                    "Los Morancos",
+                    "2025-11-15T22:00:00Z",
+                ),
+            ]
+            .into_iter()
+            .map(|e| (e.id, e))
+            .collect(),
+            ..Default::default()
+        });
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service =
+            SearchEventService::new(event_repository.clone()).with_exact_total_limit(3);
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+        let search = |uri: &'static str| {
+            let app = app.clone();
+            async move {
+                let response = app
+                    .oneshot(
+                        Request::builder()
+                            .method("GET")
+                            .uri(uri)
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let body: Value = serde_json::from_slice(
+                    &(response.into_body().collect().await.unwrap().to_bytes()),
+                )
+                .unwrap();
+                body["meta"].clone()
+            }
+        };
+
+        let meta = search("/api/v1/search?start_time=2025-11-01T08:00:00Z&end_time=2025-11-30T18:00:00Z&limit=2&offset=2&include_total=true").await;
+        assert_eq!(meta["total"], 5);
+        assert_eq!(meta["total_estimated"], true);
+        assert_eq!(meta["has_more"], true);
+        assert_eq!(
+            meta["links"]["self"],
+            "/api/v1/search?start_time=2025-11-01T08:00:00Z&end_time=2025-11-30T18:00:00Z&limit=2&offset=2&include_total=true"
+        );
+        assert_eq!(
+            meta["links"]["next"],
+            format!(
+                "/api/v1/search?start_time=2025-11-01T08:00:00Z&end_time=2025-11-30T18:00:00Z&limit=2&include_total=true&cursor={}",
+                meta["next_cursor"].as_str().unwrap()
+            )
+        );
+        assert_eq!(
+            meta["links"]["prev"],
+            format!(
+                "/api/v1/search?start_time=2025-11-01T08:00:00Z&end_time=2025-11-30T18:00:00Z&limit=2&include_total=true&cursor={}",
+                meta["prev_cursor"].as_str().unwrap()
+            )
+        );
+
+        let meta = search("/api/v1/search?start_time=2025-11-01T08:00:00Z&end_time=2025-11-30T18:00:00Z&limit=2&q=an&include_total=true").await;
+        assert_eq!(
+            meta,
+            json!({
+                "limit": 2,
+                "offset": 0,
+                "total": 2,
+                "total_estimated": false,
+                "has_more": false,
+                "links": {
+                    "self": "/api/v1/search?start_time=2025-11-01T08:00:00Z&end_time=2025-11-30T18:00:00Z&limit=2&q=an&include_total=true",
+                    "next": null,
+                    "prev": null,
+                },
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn event_search_endpoint_returns_client_error_when_cursor_is_invalid() {
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/api/v1/search?start_time=2025-11-01T08:00:00Z&end_time=2025-11-30T18:00:00Z&limit=100&cursor=bm90LWEtY3Vyc29y")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body,
+            json!({ "data": null, "meta": null, "error": { "code": "22", "message": "Invalid cursor" } })
+        );
+    }
+
     #[tokio::test]
     async fn event_search_endpoint_returns_client_error_when_required_param_is_missing() {
-        let event_repository = Arc::new(DummyEventRepository(HashMap::new()));
+        let event_repository = Arc::new(DummyEventRepository::default());
         let event_provider_client = Arc::new(DummyEventProviderClient);
         let search_event_service = SearchEventService::new(event_repository.clone());
-        let ingest_event_service = IngestEventService::new(event_provider_client, event_repository);
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
         let config = ApplicationConfig::default();
         let app = init_controller(search_event_service, ingest_event_service, &config)
             .await
@@ -198,7 +1194,12 @@ mod tests {
         let event_repository = Arc::new(FailingEventRepository);
         let event_provider_client = Arc::new(DummyEventProviderClient);
         let search_event_service = SearchEventService::new(event_repository.clone());
-        let ingest_event_service = IngestEventService::new(event_provider_client, event_repository);
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
         let config = ApplicationConfig::default();
         let app = init_controller(search_event_service, ingest_event_service, &config)
             .await
@@ -227,10 +1228,15 @@ mod tests {
 
     #[tokio::test]
     async fn event_ingest_endpoint_returns_accepted() {
-        let event_repository = Arc::new(DummyEventRepository(HashMap::new()));
+        let event_repository = Arc::new(DummyEventRepository::default());
         let event_provider_client = Arc::new(DummyEventProviderClient);
         let search_event_service = SearchEventService::new(event_repository.clone());
-        let ingest_event_service = IngestEventService::new(event_provider_client, event_repository);
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
         let config = ApplicationConfig::default();
         let app = init_controller(search_event_service, ingest_event_service, &config)
             .await
@@ -248,5 +1254,1403 @@ mod tests {
             .unwrap();
 
         assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let location = response.headers()[header::LOCATION]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        let job_id = body["data"]["job_id"].as_str().unwrap();
+        assert_eq!(location, format!("/api/v1/ingest/{job_id}"));
+        assert_eq!(body["data"]["state"], "running");
+        assert_eq!(body["error"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn event_ingest_endpoint_limits_run_to_requested_provider() {
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("PATCH")
+                    .uri("/api/v1/ingest?provider=dummy")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(body["data"]["provider"], "dummy");
+        assert_eq!(body["data"]["state"], "running");
+    }
+
+    #[tokio::test]
+    async fn event_ingest_endpoint_returns_not_found_when_provider_does_not_exist() {
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("PATCH")
+                    .uri("/api/v1/ingest?provider=unknown")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body,
+            json!({ "data": null, "meta": null, "error": { "code": "17", "message": "Event provider not found: unknown" } })
+        );
+    }
+
+    /// Provider whose fetch never completes, keeping the ingestion run in progress
+    struct PendingEventProviderClient;
+
+    impl EventProviderClient for PendingEventProviderClient {
+        fn name(&self) -> &str {
+            "pending"
+        }
+
+        async fn fetch_events(&self) -> anyhow::Result<Vec<ProviderEvent>> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn event_ingest_endpoint_returns_client_error_when_params_are_invalid() {
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("PATCH")
+                    .uri("/api/v1/ingest?provider=dummy&provider=other")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body,
+            json!({ "data": null, "meta": null, "error": { "code": "24", "message": "Invalid ingestion params" } })
+        );
+    }
+
+    #[tokio::test]
+    async fn event_ingest_endpoint_returns_conflict_while_run_is_in_progress() {
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(PendingEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+        let ingest_request = || {
+            Request::builder()
+                .method("PATCH")
+                .uri("/api/v1/ingest")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let first_response = app.clone().oneshot(ingest_request()).await.unwrap();
+        let second_response = app.oneshot(ingest_request()).await.unwrap();
+
+        assert_eq!(first_response.status(), StatusCode::ACCEPTED);
+        assert_eq!(second_response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            first_response.headers()[header::LOCATION],
+            second_response.headers()[header::LOCATION]
+        );
+        let first_body: Value = serde_json::from_slice(
+            &(first_response
+                .into_body()
+                .collect()
+                .await
+                .unwrap()
+                .to_bytes()),
+        )
+        .unwrap();
+        let second_body: Value = serde_json::from_slice(
+            &(second_response
+                .into_body()
+                .collect()
+                .await
+                .unwrap()
+                .to_bytes()),
+        )
+        .unwrap();
+        let job_id = first_body["data"]["job_id"].as_str().unwrap();
+        assert_eq!(
+            second_body,
+            json!({
+                "data": null,
+                "meta": null,
+                "error": {
+                    "code": "78",
+                    "message": format!("Event ingestion already in progress: job {job_id}"),
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn event_ingest_endpoint_returns_conflict_while_another_instance_is_ingesting() {
+        let run = IngestionRun {
+            id: Uuid::from_str("7c9e6679-7425-40de-944b-e07fc1f90ae7").unwrap(),
+            state: IngestionRunState::Running,
+            provider: None,
+            started_at: DateTime::from_str("2025-11-12T02:00:00Z").unwrap(),
+            finished_at: None,
+            fetched: 0,
+            inserted: 0,
+            updated: 0,
+            skipped: 0,
+            failed: 0,
+            cancelled: 0,
+            not_modified: 0,
+            rejected: 0,
+        };
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository(
+                Mutex::new(HashMap::from([(run.id, run)])),
+                true,
+                Mutex::new(Vec::new()),
+                Mutex::new(Vec::new()),
+            )),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("PATCH")
+                    .uri("/api/v1/ingest")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            response.headers()[header::LOCATION],
+            "/api/v1/ingest/7c9e6679-7425-40de-944b-e07fc1f90ae7"
+        );
+    }
+
+    #[tokio::test]
+    async fn event_ingest_endpoint_fails_runs_abandoned_by_stopped_instances() {
+        let run = IngestionRun {
+            id: Uuid::from_str("7c9e6679-7425-40de-944b-e07fc1f90ae7").unwrap(),
+            state: IngestionRunState::Running,
+            provider: None,
+            started_at: DateTime::from_str("2025-11-12T02:00:00Z").unwrap(),
+            finished_at: None,
+            fetched: 0,
+            inserted: 0,
+            updated: 0,
+            skipped: 0,
+            failed: 0,
+            cancelled: 0,
+            not_modified: 0,
+            rejected: 0,
+        };
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let ingestion_run_repository = Arc::new(DummyIngestionRunRepository(
+            Mutex::new(HashMap::from([(run.id, run)])),
+            false,
+            Mutex::new(Vec::new()),
+            Mutex::new(Vec::new()),
+        ));
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            ingestion_run_repository.clone(),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("PATCH")
+                    .uri("/api/v1/ingest")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let runs = ingestion_run_repository.0.lock().unwrap();
+        let abandoned_run = &runs[&Uuid::from_str("7c9e6679-7425-40de-944b-e07fc1f90ae7").unwrap()];
+        assert_eq!(abandoned_run.state, IngestionRunState::Failed);
+        assert!(abandoned_run.finished_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn providers_endpoint_returns_configured_providers() {
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/api/v1/providers")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "data": { "providers": [{ "name": "dummy", "circuit_state": null }] },
+                "meta": null,
+                "error": null,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn providers_endpoint_reports_open_circuit_of_failing_provider() {
+        let provider_server_mock = MockServer::start();
+        provider_server_mock.mock(|when, then| {
+            when.method("GET").path("/api/events");
+            then.status(StatusCode::INTERNAL_SERVER_ERROR.as_u16());
+        });
+        let event_provider_client = Arc::new(
+            HttpEventProviderClient::new(
+                "acme".to_string(),
+                Currency::from_str("EUR").unwrap(),
+                Tz::Europe__Madrid,
+                format!("http://localhost:{}", provider_server_mock.port()),
+                "/api/events".to_string(),
+                reqwest::Client::new(),
+            )
+            .with_resilience(
+                RetryPolicy {
+                    max_attempts: 1,
+                    ..RetryPolicy::default()
+                },
+                CircuitBreaker::new(1, Duration::from_secs(60)),
+            ),
+        );
+        assert!(event_provider_client.fetch_events().await.is_err());
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/api/v1/providers")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body["data"]["providers"],
+            json!([{ "name": "acme", "circuit_state": "open" }])
+        );
+    }
+
+    #[tokio::test]
+    async fn ingestion_job_endpoint_returns_run_of_job() {
+        let run = IngestionRun {
+            id: Uuid::from_str("7c9e6679-7425-40de-944b-e07fc1f90ae7").unwrap(),
+            state: IngestionRunState::Succeeded,
+            provider: None,
+            started_at: DateTime::from_str("2025-11-12T02:00:00Z").unwrap(),
+            finished_at: Some(DateTime::from_str("2025-11-12T02:00:05Z").unwrap()),
+            fetched: 5,
+            inserted: 2,
+            updated: 2,
+            skipped: 0,
+            failed: 1,
+            cancelled: 0,
+            not_modified: 0,
+            rejected: 0,
+        };
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository(
+                Mutex::new(HashMap::from([(run.id, run)])),
+                false,
+                Mutex::new(Vec::new()),
+                Mutex::new(Vec::new()),
+            )),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/api/v1/ingest/7c9e6679-7425-40de-944b-e07fc1f90ae7")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "data": {
+                    "job_id": "7c9e6679-7425-40de-944b-e07fc1f90ae7",
+                    "state": "succeeded",
+                    "started_at": "2025-11-12T02:00:00Z",
+                    "finished_at": "2025-11-12T02:00:05Z",
+                    "fetched": 5,
+                    "inserted": 2,
+                    "updated": 2,
+                    "skipped": 0,
+                    "failed": 1,
+                    "cancelled": 0,
+                    "not_modified": 0,
+                    "rejected": 0,
+                },
+                "meta": null,
+                "error": null,
+            })
+        )
+    }
+
+    #[tokio::test]
+    async fn ingestion_job_endpoint_returns_not_found_when_job_does_not_exist() {
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/api/v1/ingest/7c9e6679-7425-40de-944b-e07fc1f90ae7")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body,
+            json!({ "data": null, "meta": null, "error": { "code": "15", "message": "Ingestion job not found" } })
+        )
+    }
+
+    #[tokio::test]
+    async fn ingestion_rejections_endpoint_returns_items_rejected_by_job() {
+        let run = IngestionRun {
+            id: Uuid::from_str("7c9e6679-7425-40de-944b-e07fc1f90ae7").unwrap(),
+            state: IngestionRunState::Succeeded,
+            provider: None,
+            started_at: DateTime::from_str("2025-11-12T02:00:00Z").unwrap(),
+            finished_at: Some(DateTime::from_str("2025-11-12T02:00:05Z").unwrap()),
+            fetched: 2,
+            inserted: 2,
+            updated: 0,
+            skipped: 0,
+            failed: 0,
+            cancelled: 0,
+            not_modified: 0,
+            rejected: 2,
+        };
+        let rejection = IngestionRejection {
+            ingestion_run_id: run.id,
+            provider: "test-provider".to_string(),
+            base_plan_id: Some("444".to_string()),
+            plan_id: Some("1642".to_string()),
+            zone_id: None,
+            reason: "Error parsing datetime 2021-09-31T20:00:00: input is out of range".to_string(),
+            rejected_at: DateTime::from_str("2025-11-12T02:00:01Z").unwrap(),
+        };
+        let other_run_rejection = IngestionRejection {
+            ingestion_run_id: Uuid::from_str("16fd2706-8baf-433b-82eb-8c7fada847da").unwrap(),
+            ..rejection.clone()
+        };
+        let zone_rejection = IngestionRejection {
+            plan_id: Some("1643".to_string()),
+            zone_id: Some("8".to_string()),
+            reason: "Error parsing zone price free".to_string(),
+            ..rejection.clone()
+        };
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository(
+                Mutex::new(HashMap::from([(run.id, run)])),
+                false,
+                Mutex::new(vec![rejection, other_run_rejection, zone_rejection]),
+                Mutex::new(Vec::new()),
+            )),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/api/v1/ingest/7c9e6679-7425-40de-944b-e07fc1f90ae7/rejections")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "data": {
+                    "rejections": [
+                        {
+                            "provider": "test-provider",
+                            "base_plan_id": "444",
+                            "plan_id": "1642",
+                            "zone_id": null,
+                            "reason": "Error parsing datetime 2021-09-31T20:00:00: input is out of range",
+                            "rejected_at": "2025-11-12T02:00:01Z",
+                        },
+                        {
+                            "provider": "test-provider",
+                            "base_plan_id": "444",
+                            "plan_id": "1643",
+                            "zone_id": "8",
+                            "reason": "Error parsing zone price free",
+                            "rejected_at": "2025-11-12T02:00:01Z",
+                        },
+                    ],
+                },
+                "meta": { "limit": 100, "offset": 0 },
+                "error": null,
+            })
+        )
+    }
+
+    #[tokio::test]
+    async fn ingestion_rejections_endpoint_returns_not_found_when_job_does_not_exist() {
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/api/v1/ingest/7c9e6679-7425-40de-944b-e07fc1f90ae7/rejections")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body,
+            json!({ "data": null, "meta": null, "error": { "code": "15", "message": "Ingestion job not found" } })
+        )
+    }
+
+    #[tokio::test]
+    async fn ingestion_jobs_endpoint_returns_most_recent_runs_first() {
+        let older_run = IngestionRun {
+            id: Uuid::from_str("7c9e6679-7425-40de-944b-e07fc1f90ae7").unwrap(),
+            state: IngestionRunState::Failed,
+            provider: None,
+            started_at: DateTime::from_str("2025-11-11T02:00:00Z").unwrap(),
+            finished_at: Some(DateTime::from_str("2025-11-11T02:00:01Z").unwrap()),
+            fetched: 0,
+            inserted: 0,
+            updated: 0,
+            skipped: 0,
+            failed: 0,
+            cancelled: 0,
+            not_modified: 0,
+            rejected: 0,
+        };
+        let newer_run = IngestionRun {
+            id: Uuid::from_str("16fd2706-8baf-433b-82eb-8c7fada847da").unwrap(),
+            state: IngestionRunState::Running,
+            provider: None,
+            started_at: DateTime::from_str("2025-11-12T02:00:00Z").unwrap(),
+            finished_at: None,
+            ..older_run.clone()
+        };
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository(
+                Mutex::new(HashMap::from([
+                    (older_run.id, older_run),
+                    (newer_run.id, newer_run),
+                ])),
+                false,
+                Mutex::new(Vec::new()),
+                Mutex::new(Vec::new()),
+            )),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/api/v1/ingest?limit=10")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        let job_ids: Vec<&str> = body["data"]["jobs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|job| job["job_id"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            job_ids,
+            vec![
+                "16fd2706-8baf-433b-82eb-8c7fada847da",
+                "7c9e6679-7425-40de-944b-e07fc1f90ae7"
+            ]
+        );
+        assert_eq!(body["data"]["jobs"][0]["finished_at"], Value::Null);
+        assert_eq!(body["meta"], json!({ "limit": 10, "offset": 0 }));
+    }
+
+    #[tokio::test]
+    async fn event_search_endpoint_embeds_zones_when_requested() {
+        let event_repository = Arc::new(DummyEventRepository {
+            events: HashMap::from([(
+                Uuid::from_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap(),
+                Event {
+                    id: Uuid::from_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap(),
+                    external_id: None,
+                    title: "Quevedo".to_string(),
+                    start_time: DateTime::from_str("2025-11-12T22:00:00Z").unwrap(),
+                    end_time: DateTime::from_str("2025-11-12T23:00:00Z").unwrap(),
+                    time_zone: Tz::Europe__Madrid,
+                    min_price: eur(1599),
+                    max_price: eur(3999),
+                    sell_mode: SellMode::Online,
+                    organizer_company_id: Some("1".to_string()),
+                    sell_from: DateTime::from_str("2025-06-01T00:00:00Z").unwrap(),
+                    sell_to: DateTime::from_str("2025-11-12T20:00:00Z").unwrap(),
+                    sold_out: false,
+                    cancelled_at: None,
+                },
+            )]),
+            zones: HashMap::from([(
+                Uuid::from_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap(),
+                vec![
+                    Zone {
+                        zone_id: "1".to_string(),
+                        name: "Pista".to_string(),
+                        price: eur(1599),
+                        capacity: 800,
+                        numbered: false,
+                    },
+                    Zone {
+                        zone_id: "2".to_string(),
+                        name: "Grada".to_string(),
+                        price: eur(3999),
+                        capacity: 120,
+                        numbered: true,
+                    },
+                ],
+            )]),
+            ..Default::default()
+        });
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app.oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/api/v1/search?start_time=2025-11-01T08:00:00Z&end_time=2025-11-30T18:00:00Z&limit=100&include_zones=true")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body["data"]["events"][0]["zones"],
+            json!([
+                {
+                    "zone_id": "1",
+                    "name": "Pista",
+                    "price": { "amount": "15.99", "currency": "EUR" },
+                    "capacity": 800,
+                    "numbered": false,
+                },
+                {
+                    "zone_id": "2",
+                    "name": "Grada",
+                    "price": { "amount": "39.99", "currency": "EUR" },
+                    "capacity": 120,
+                    "numbered": true,
+                },
+            ])
+        )
+    }
+
+    #[tokio::test]
+    async fn event_zones_endpoint_returns_zones_of_event() {
+        let event_repository = Arc::new(DummyEventRepository {
+            events: HashMap::from([(
+                Uuid::from_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap(),
+                Event {
+                    id: Uuid::from_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap(),
+                    external_id: None,
+                    title: "Quevedo".to_string(),
+                    start_time: DateTime::from_str("2025-11-12T22:00:00Z").unwrap(),
+                    end_time: DateTime::from_str("2025-11-12T23:00:00Z").unwrap(),
+                    time_zone: Tz::Europe__Madrid,
+                    min_price: eur(1599),
+                    max_price: eur(3999),
+                    sell_mode: SellMode::Online,
+                    organizer_company_id: Some("1".to_string()),
+                    sell_from: DateTime::from_str("2025-06-01T00:00:00Z").unwrap(),
+                    sell_to: DateTime::from_str("2025-11-12T20:00:00Z").unwrap(),
+                    sold_out: false,
+                    cancelled_at: None,
+                },
+            )]),
+            zones: HashMap::from([(
+                Uuid::from_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap(),
+                vec![
+                    Zone {
+                        zone_id: "1".to_string(),
+                        name: "Pista".to_string(),
+                        price: eur(1599),
+                        capacity: 800,
+                        numbered: false,
+                    },
+                    Zone {
+                        zone_id: "2".to_string(),
+                        name: "Grada".to_string(),
+                        price: eur(3999),
+                        capacity: 120,
+                        numbered: true,
+                    },
+                ],
+            )]),
+            ..Default::default()
+        });
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/api/v1/events/3fa85f64-5717-4562-b3fc-2c963f66afa6/zones")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "data": {
+                    "zones": [
+                            {
+                                "zone_id": "1",
+                                "name": "Pista",
+                                "price": { "amount": "15.99", "currency": "EUR" },
+                                "capacity": 800,
+                                "numbered": false,
+                            },
+                            {
+                                "zone_id": "2",
+                                "name": "Grada",
+                                "price": { "amount": "39.99", "currency": "EUR" },
+                                "capacity": 120,
+                                "numbered": true,
+                            },
+                        ]
+                },
+                "error": null,
+                "meta": null
+            })
+        )
+    }
+
+    #[tokio::test]
+    async fn event_zones_endpoint_returns_not_found_when_event_does_not_exist() {
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/api/v1/events/3fa85f64-5717-4562-b3fc-2c963f66afa6/zones")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body,
+            json!({ "data": null, "meta": null, "error": { "code": "13", "message": "Event not found" } })
+        )
+    }
+
+    #[tokio::test]
+    async fn event_history_endpoint_returns_changes_of_event_oldest_first() {
+        let event_id = Uuid::from_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap();
+        let job_id = Uuid::from_str("7c9e6679-7425-40de-944b-e07fc1f90ae7").unwrap();
+        let event_repository = Arc::new(DummyEventRepository {
+            events: HashMap::from([(
+                event_id,
+                Event {
+                    id: event_id,
+                    external_id: None,
+                    title: "Quevedo".to_string(),
+                    start_time: DateTime::from_str("2025-11-13T22:00:00Z").unwrap(),
+                    end_time: DateTime::from_str("2025-11-13T23:00:00Z").unwrap(),
+                    time_zone: Tz::Europe__Madrid,
+                    min_price: eur(1299),
+                    max_price: eur(3999),
+                    sell_mode: SellMode::Online,
+                    organizer_company_id: None,
+                    sell_from: DateTime::from_str("2025-06-01T00:00:00Z").unwrap(),
+                    sell_to: DateTime::from_str("2025-11-12T20:00:00Z").unwrap(),
+                    sold_out: false,
+                    cancelled_at: None,
+                },
+            )]),
+            changes: vec![
+                EventChange {
+                    event_id,
+                    ingestion_run_id: job_id,
+                    field: "min_price".to_string(),
+                    old_value: Some("15.99 EUR".to_string()),
+                    new_value: Some("12.99 EUR".to_string()),
+                    changed_at: DateTime::from_str("2025-11-05T02:00:00Z").unwrap(),
+                },
+                EventChange {
+                    event_id,
+                    ingestion_run_id: job_id,
+                    field: "start_time".to_string(),
+                    old_value: Some("2025-11-12T22:00:00Z".to_string()),
+                    new_value: Some("2025-11-13T22:00:00Z".to_string()),
+                    changed_at: DateTime::from_str("2025-11-01T02:00:00Z").unwrap(),
+                },
+                EventChange {
+                    event_id: Uuid::new_v4(),
+                    ingestion_run_id: job_id,
+                    field: "title".to_string(),
+                    old_value: Some("Camela".to_string()),
+                    new_value: Some("Camela en concierto".to_string()),
+                    changed_at: DateTime::from_str("2025-11-01T02:00:00Z").unwrap(),
+                },
+            ],
+            ..Default::default()
+        });
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/api/v1/events/3fa85f64-5717-4562-b3fc-2c963f66afa6/history")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "data": {
+                    "changes": [
+                        {
+                            "field": "start_time",
+                            "old_value": "2025-11-12T22:00:00Z",
+                            "new_value": "2025-11-13T22:00:00Z",
+                            "job_id": "7c9e6679-7425-40de-944b-e07fc1f90ae7",
+                            "changed_at": "2025-11-01T02:00:00Z",
+                        },
+                        {
+                            "field": "min_price",
+                            "old_value": "15.99 EUR",
+                            "new_value": "12.99 EUR",
+                            "job_id": "7c9e6679-7425-40de-944b-e07fc1f90ae7",
+                            "changed_at": "2025-11-05T02:00:00Z",
+                        },
+                    ]
+                },
+                "error": null,
+                "meta": null
+            })
+        )
+    }
+
+    #[tokio::test]
+    async fn event_history_endpoint_returns_not_found_when_event_does_not_exist() {
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/api/v1/events/3fa85f64-5717-4562-b3fc-2c963f66afa6/history")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body,
+            json!({ "data": null, "meta": null, "error": { "code": "13", "message": "Event not found" } })
+        )
+    }
+
+    #[tokio::test]
+    async fn event_prices_endpoint_returns_prices_observed_within_range() {
+        let event_id = Uuid::from_str("3fa85f64-5717-4562-b3fc-2c963f66afa6").unwrap();
+        let price_point = |min_price: i64, observed_at: &str| PricePoint {
+            min_price: eur(min_price),
+            max_price: eur(3999),
+            observed_at: DateTime::from_str(observed_at).unwrap(),
+        };
+        let event_repository = Arc::new(DummyEventRepository {
+            events: HashMap::from([(
+                event_id,
+                Event {
+                    id: event_id,
+                    external_id: None,
+                    title: "Quevedo".to_string(),
+                    start_time: DateTime::from_str("2025-11-12T22:00:00Z").unwrap(),
+                    end_time: DateTime::from_str("2025-11-12T23:00:00Z").unwrap(),
+                    time_zone: Tz::Europe__Madrid,
+                    min_price: eur(1099),
+                    max_price: eur(3999),
+                    sell_mode: SellMode::Online,
+                    organizer_company_id: None,
+                    sell_from: DateTime::from_str("2025-06-01T00:00:00Z").unwrap(),
+                    sell_to: DateTime::from_str("2025-11-12T20:00:00Z").unwrap(),
+                    sold_out: false,
+                    cancelled_at: None,
+                },
+            )]),
+            prices: HashMap::from([(
+                event_id,
+                vec![
+                    price_point(1099, "2025-11-01T02:00:00Z"),
+                    price_point(1599, "2025-09-01T02:00:00Z"),
+                    price_point(1299, "2025-10-01T02:00:00Z"),
+                ],
+            )]),
+            ..Default::default()
+        });
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/api/v1/events/3fa85f64-5717-4562-b3fc-2c963f66afa6/prices?from=2025-09-15T00:00:00Z")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let invalid_range_response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/api/v1/events/3fa85f64-5717-4562-b3fc-2c963f66afa6/prices?from=2025-11-01T00:00:00Z&to=2025-10-01T00:00:00Z")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "data": {
+                    "prices": [
+                        {
+                            "min_price": { "amount": "12.99", "currency": "EUR" },
+                            "max_price": { "amount": "39.99", "currency": "EUR" },
+                            "observed_at": "2025-10-01T02:00:00Z",
+                        },
+                        {
+                            "min_price": { "amount": "10.99", "currency": "EUR" },
+                            "max_price": { "amount": "39.99", "currency": "EUR" },
+                            "observed_at": "2025-11-01T02:00:00Z",
+                        },
+                    ]
+                },
+                "error": null,
+                "meta": null
+            })
+        );
+        assert_eq!(invalid_range_response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = serde_json::from_slice(
+            &(invalid_range_response
+                .into_body()
+                .collect()
+                .await
+                .unwrap()
+                .to_bytes()),
+        )
+        .unwrap();
+        assert_eq!(
+            body,
+            json!({ "data": null, "meta": null, "error": { "code": "18", "message": "Invalid price history range" } })
+        )
+    }
+
+    #[tokio::test]
+    async fn dead_letter_replay_endpoint_writes_event_and_drops_dead_letter() {
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let ingestion_run_repository = Arc::new(DummyIngestionRunRepository(
+            Mutex::new(HashMap::new()),
+            false,
+            Mutex::new(Vec::new()),
+            Mutex::new(vec![dead_letter()]),
+        ));
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            ingestion_run_repository.clone(),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/admin/dead-letters/a3bb189e-8bf9-3888-9912-ace4e6543002/replay")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(body["data"]["replayed"], true);
+        assert_eq!(body["data"]["superseded"], false);
+        assert_eq!(body["data"]["outcome"], "inserted");
+        assert_eq!(
+            body["data"]["dead_letter"]["id"],
+            "a3bb189e-8bf9-3888-9912-ace4e6543002"
+        );
+        assert!(ingestion_run_repository.3.lock().unwrap().is_empty());
+        let replay_job_id =
+            Uuid::from_str(body["data"]["replay_job_id"].as_str().unwrap()).unwrap();
+        assert_ne!(replay_job_id, dead_letter().ingestion_run_id);
+        let replay_run = ingestion_run_repository.0.lock().unwrap()[&replay_job_id].clone();
+        assert_eq!(replay_run.provider.as_deref(), Some("test-provider"));
+        assert_eq!(replay_run.inserted, 1);
+    }
+
+    #[tokio::test]
+    async fn dead_letters_replay_endpoint_counts_failed_attempts() {
+        let event_repository = Arc::new(FailingEventRepository);
+        let ingestion_run_repository = Arc::new(DummyIngestionRunRepository(
+            Mutex::new(HashMap::new()),
+            false,
+            Mutex::new(Vec::new()),
+            Mutex::new(vec![dead_letter()]),
+        ));
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            ingestion_run_repository.clone(),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/admin/dead-letters/replay")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        let replays = body["data"]["replays"].as_array().unwrap();
+        assert_eq!(replays.len(), 1);
+        assert_eq!(replays[0]["replayed"], false);
+        assert_eq!(replays[0]["superseded"], false);
+        assert_eq!(replays[0]["outcome"], Value::Null);
+        assert_eq!(replays[0]["dead_letter"]["attempts"], 2);
+        assert_eq!(
+            replays[0]["dead_letter"]["error"],
+            "Failed to find update time of event by external id in event database"
+        );
+        assert_eq!(ingestion_run_repository.3.lock().unwrap()[0].attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn dead_letter_replay_endpoint_returns_conflict_while_another_instance_is_ingesting() {
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository(
+                Mutex::new(HashMap::new()),
+                true,
+                Mutex::new(Vec::new()),
+                Mutex::new(vec![dead_letter()]),
+            )),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/admin/dead-letters/a3bb189e-8bf9-3888-9912-ace4e6543002/replay")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body,
+            json!({ "data": null, "meta": null, "error": { "code": "78", "message": "Event ingestion already in progress" } })
+        )
+    }
+
+    #[tokio::test]
+    async fn dead_letter_replay_endpoint_returns_not_found_when_dead_letter_does_not_exist() {
+        let event_repository = Arc::new(DummyEventRepository::default());
+        let event_provider_client = Arc::new(DummyEventProviderClient);
+        let search_event_service = SearchEventService::new(event_repository.clone());
+        let ingest_event_service = Arc::new(IngestEventService::new(
+            vec![event_provider_client],
+            event_repository,
+            Arc::new(DummyIngestionRunRepository::default()),
+            IngestionSettings::default(),
+        ));
+        let config = ApplicationConfig::default();
+        let app = init_controller(search_event_service, ingest_event_service, &config)
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/admin/dead-letters/a3bb189e-8bf9-3888-9912-ace4e6543002/replay")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body: Value =
+            serde_json::from_slice(&(response.into_body().collect().await.unwrap().to_bytes()))
+                .unwrap();
+        assert_eq!(
+            body,
+            json!({ "data": null, "meta": null, "error": { "code": "20", "message": "Dead letter not found" } })
+        )
     }
 }
@@ -1,6 +1,11 @@
+use std::sync::Arc;
+
 use arc_swap::ArcSwap;
 
-use crate::application::ports::{provider::EventProviderClient, repository::EventRepository};
+use crate::application::ports::{
+    provider::EventProviderClient,
+    repository::{EventRepository, IngestionRunRepository},
+};
 use crate::application::service::{IngestEventService, SearchEventService};
 use crate::infrastructure::config::ApplicationConfig;
 
@@ -8,8 +13,9 @@ use crate::infrastructure::config::ApplicationConfig;
 pub struct ApplicationState<
     T: EventRepository + Send + Sync + 'static,
     S: EventProviderClient + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
 > {
     pub config: ArcSwap<ApplicationConfig>,
     pub search_event_service: SearchEventService<T>,
-    pub ingest_event_service: IngestEventService<S, T>,
+    pub ingest_event_service: Arc<IngestEventService<S, T, R>>,
 }
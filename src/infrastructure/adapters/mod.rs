@@ -1,3 +1,4 @@
 pub mod controller;
 pub mod provider;
 pub mod repository;
+pub mod scheduler;
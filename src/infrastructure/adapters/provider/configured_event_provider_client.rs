@@ -0,0 +1,71 @@
+use anyhow::Result;
+use futures::future::Either;
+use futures::stream::Stream;
+
+use crate::application::ports::provider::{
+    CircuitState, ConditionalFetch, EventProviderClient, FeedChunk, FeedValidators, ProviderEvent,
+};
+use crate::infrastructure::adapters::provider::{
+    FileEventProviderClient, HttpEventProviderClient, JsonEventProviderClient,
+};
+
+/// Client of a provider in whichever feed format it is configured with
+pub enum ConfiguredEventProviderClient {
+    Xml(HttpEventProviderClient),
+    Json(Box<JsonEventProviderClient>),
+    File(FileEventProviderClient),
+}
+
+impl EventProviderClient for ConfiguredEventProviderClient {
+    fn name(&self) -> &str {
+        match self {
+            Self::Xml(client) => client.name(),
+            Self::Json(client) => client.name(),
+            Self::File(client) => client.name(),
+        }
+    }
+
+    fn circuit_state(&self) -> Option<CircuitState> {
+        match self {
+            Self::Xml(client) => client.circuit_state(),
+            Self::Json(client) => client.circuit_state(),
+            Self::File(client) => client.circuit_state(),
+        }
+    }
+
+    async fn fetch_events(&self) -> Result<Vec<ProviderEvent>> {
+        match self {
+            Self::Xml(client) => client.fetch_events().await,
+            Self::Json(client) => client.fetch_events().await,
+            Self::File(client) => client.fetch_events().await,
+        }
+    }
+
+    fn stream_events(&self) -> impl Stream<Item = Result<FeedChunk>> + Send {
+        match self {
+            Self::Xml(client) => Either::Left(client.stream_events()),
+            Self::Json(client) => Either::Right(Either::Left(client.stream_events())),
+            Self::File(client) => Either::Right(Either::Right(client.stream_events())),
+        }
+    }
+
+    async fn stream_events_if_modified(
+        &self,
+        validators: &FeedValidators,
+    ) -> Result<ConditionalFetch<impl Stream<Item = Result<FeedChunk>> + Send>> {
+        Ok(match self {
+            Self::Xml(client) => client
+                .stream_events_if_modified(validators)
+                .await?
+                .map_events(Either::Left),
+            Self::Json(client) => client
+                .stream_events_if_modified(validators)
+                .await?
+                .map_events(|events| Either::Right(Either::Left(events))),
+            Self::File(client) => client
+                .stream_events_if_modified(validators)
+                .await?
+                .map_events(|events| Either::Right(Either::Right(events))),
+        })
+    }
+}
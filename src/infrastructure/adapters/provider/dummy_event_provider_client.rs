@@ -6,6 +6,10 @@ use crate::application::ports::provider::{EventProviderClient, ProviderEvent};
 pub struct DummyEventProviderClient;
 
 impl EventProviderClient for DummyEventProviderClient {
+    fn name(&self) -> &str {
+        "dummy"
+    }
+
     async fn fetch_events(&self) -> Result<Vec<ProviderEvent>> {
         Ok(Vec::new())
     }
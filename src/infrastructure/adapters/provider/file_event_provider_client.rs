@@ -0,0 +1,185 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+use chrono_tz::Tz;
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use reqwest::Url;
+use tokio::fs::File;
+use tokio::io::BufReader;
+
+use crate::application::ports::provider::{EventProviderClient, FeedChunk, ProviderEvent};
+use crate::domain::money::Currency;
+use crate::infrastructure::adapters::provider::http_event_provider_client::BasePlanReader;
+
+/// Reads `planList` XML feeds from a local file, or from every `.xml` file of a directory in file
+/// name order
+pub struct FileEventProviderClient {
+    provider_name: String,
+    currency: Currency,
+    time_zone: Tz,
+    path: PathBuf,
+}
+
+impl FileEventProviderClient {
+    pub fn new(provider_name: String, currency: Currency, time_zone: Tz, path: PathBuf) -> Self {
+        Self {
+            provider_name,
+            currency,
+            time_zone,
+            path,
+        }
+    }
+
+    /// Local path of a `file://` feed URL
+    pub fn path_from_url(url: &str) -> Result<PathBuf> {
+        Url::parse(url)
+            .ok()
+            .filter(|url| url.scheme() == "file")
+            .and_then(|url| url.to_file_path().ok())
+            .ok_or_else(|| anyhow!("Invalid event feed file URL {url}"))
+    }
+
+    /// Feed files to read, sorted so that every run processes them in the same order
+    async fn feed_files(path: &Path) -> Result<Vec<PathBuf>> {
+        let metadata = tokio::fs::metadata(path)
+            .await
+            .context(format!("Failed to access event feed {}", path.display()))?;
+        if !metadata.is_dir() {
+            return Ok(vec![path.to_path_buf()]);
+        }
+
+        let mut entries = tokio::fs::read_dir(path).await.context(format!(
+            "Failed to list event feed directory {}",
+            path.display()
+        ))?;
+        let mut files = Vec::new();
+        while let Some(entry) = entries.next_entry().await.context(format!(
+            "Failed to list event feed directory {}",
+            path.display()
+        ))? {
+            let file = entry.path();
+            if file.extension().is_some_and(|extension| extension == "xml")
+                && tokio::fs::metadata(&file)
+                    .await
+                    .is_ok_and(|metadata| metadata.is_file())
+            {
+                files.push(file);
+            }
+        }
+        files.sort();
+
+        Ok(files)
+    }
+}
+
+impl EventProviderClient for FileEventProviderClient {
+    fn name(&self) -> &str {
+        &self.provider_name
+    }
+
+    async fn fetch_events(&self) -> Result<Vec<ProviderEvent>> {
+        self.stream_events()
+            .map_ok(|chunk| chunk.events)
+            .try_concat()
+            .await
+    }
+
+    fn stream_events(&self) -> impl Stream<Item = Result<FeedChunk>> + Send {
+        let provider = self.provider_name.clone();
+        let currency = self.currency;
+        let time_zone = self.time_zone;
+
+        stream::once(Self::feed_files(&self.path))
+            .map_ok(|files| stream::iter(files).map(Ok::<_, anyhow::Error>))
+            .try_flatten()
+            .and_then(move |file| {
+                let provider = provider.clone();
+                async move {
+                    let reader = File::open(&file)
+                        .await
+                        .context(format!("Failed to open event feed {}", file.display()))?;
+                    Ok(BasePlanReader::new(BufReader::new(reader))
+                        .into_stream(provider, currency, time_zone))
+                }
+            })
+            .try_flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn client_for(path: PathBuf) -> FileEventProviderClient {
+        FileEventProviderClient::new(
+            "test-provider".to_string(),
+            Currency::from_str("EUR").unwrap(),
+            Tz::Europe__Madrid,
+            path,
+        )
+    }
+
+    fn plan_ids(provider_events: &[ProviderEvent]) -> Vec<&str> {
+        provider_events
+            .iter()
+            .map(|pe| pe.external_id.plan_id.as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn client_reads_events_from_feed_file() {
+        let client = client_for(PathBuf::from("test/fixtures/provider_response_2.xml"));
+
+        let provider_events = client.fetch_events().await.unwrap();
+
+        assert_eq!(plan_ids(&provider_events), vec!["303", "2300", "1404"]);
+        assert_eq!(
+            provider_events[0].external_id.provider,
+            "test-provider".to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn client_reads_every_feed_file_of_directory_in_file_name_order() {
+        let client = client_for(PathBuf::from("test/fixtures"));
+
+        let provider_events = client.fetch_events().await.unwrap();
+
+        assert_eq!(
+            plan_ids(&provider_events),
+            vec![
+                "291", "1642", "1643", "1642", "303", "2300", "1404", "291", "1642"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn client_reads_events_from_file_url() {
+        let url = format!(
+            "file://{}/test/fixtures/provider_response_2.xml",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let client = client_for(FileEventProviderClient::path_from_url(&url).unwrap());
+
+        let provider_events = client.fetch_events().await.unwrap();
+
+        assert_eq!(provider_events.len(), 3);
+    }
+
+    #[test]
+    fn feed_urls_must_be_file_urls() {
+        assert!(FileEventProviderClient::path_from_url("http://localhost/events.xml").is_err());
+        assert!(FileEventProviderClient::path_from_url("not a url").is_err());
+    }
+
+    #[tokio::test]
+    async fn client_fails_when_feed_does_not_exist() {
+        let client = client_for(PathBuf::from("test/fixtures/missing.xml"));
+
+        let provider_events = client.fetch_events().await;
+
+        assert!(provider_events.is_err());
+    }
+}
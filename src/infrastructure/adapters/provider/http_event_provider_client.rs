@@ -1,70 +1,305 @@
 use std::fmt::Debug;
 use std::str::FromStr;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use futures::stream::{self, Stream, TryStreamExt};
 use log::warn;
-use reqwest::Client;
-use reqwest::Url;
+use quick_xml::events::Event;
+use quick_xml::reader::Reader;
+use quick_xml::{Writer, XmlVersion};
+use reqwest::header::{
+    ETAG, HeaderMap, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use reqwest::{Client, Response, StatusCode, Url};
 use serde::Deserialize;
+use tokio::io::{AsyncBufRead, BufReader};
+use tokio_util::io::StreamReader;
 
-use crate::application::ports::provider::{EventProviderClient, ProviderEvent};
+use crate::application::ports::provider::{
+    CircuitState, ConditionalFetch, EventProviderClient, FeedChunk, FeedValidators, ProviderEvent,
+    ProviderRejection,
+};
+use crate::domain::event::{ExternalId, SellMode};
+use crate::domain::money::{Currency, Money};
+use crate::domain::zone::Zone as EventZone;
+use crate::infrastructure::adapters::provider::resilience::{
+    CircuitBreaker, RetryPolicy, get_with_resilience,
+};
 
 pub struct HttpEventProviderClient {
+    provider_name: String,
+    currency: Currency,
+    time_zone: Tz,
     provider_url: String,
     event_api_path: String,
     client: reqwest::Client,
+    retry_policy: RetryPolicy,
+    circuit_breaker: CircuitBreaker,
 }
 
 impl HttpEventProviderClient {
-    pub fn new(provider_url: String, event_api_path: String, client: Client) -> Self {
+    pub fn new(
+        provider_name: String,
+        currency: Currency,
+        time_zone: Tz,
+        provider_url: String,
+        event_api_path: String,
+        client: Client,
+    ) -> Self {
         Self {
+            provider_name,
+            currency,
+            time_zone,
             provider_url,
             event_api_path,
             client,
+            retry_policy: RetryPolicy::default(),
+            circuit_breaker: CircuitBreaker::default(),
         }
     }
+
+    /// HTTP client for provider feeds, bounding how long connecting and each read of the response
+    /// may take rather than the whole request, so that large feeds can be streamed in full
+    pub fn http_client(timeout: Duration) -> Result<Client> {
+        Client::builder()
+            .connect_timeout(timeout)
+            .read_timeout(timeout)
+            .build()
+            .context("Failed to create HTTP client for event provider client")
+    }
+
+    pub fn with_resilience(
+        mut self,
+        retry_policy: RetryPolicy,
+        circuit_breaker: CircuitBreaker,
+    ) -> Self {
+        self.retry_policy = retry_policy;
+        self.circuit_breaker = circuit_breaker;
+        self
+    }
+
+    /// Requests the event feed, retrying per the retry policy unless the circuit breaker is open.
+    /// The request is conditional on the feed having changed when validators are given.
+    async fn send_events_request(&self, validators: &FeedValidators) -> Result<Response> {
+        let url = Url::parse(&format!("{}{}", self.provider_url, self.event_api_path))?;
+        let mut headers = HeaderMap::new();
+        if let Some(etag) = &validators.etag {
+            headers.insert(
+                IF_NONE_MATCH,
+                HeaderValue::from_str(etag).context("Invalid ETag of provider feed")?,
+            );
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            headers.insert(
+                IF_MODIFIED_SINCE,
+                HeaderValue::from_str(last_modified)
+                    .context("Invalid Last-Modified date of provider feed")?,
+            );
+        }
+
+        get_with_resilience(
+            &self.client,
+            url,
+            headers,
+            &self.provider_name,
+            &self.retry_policy,
+            &self.circuit_breaker,
+        )
+        .await
+    }
+
+    fn parse_events_response(
+        &self,
+        response: Response,
+    ) -> impl Stream<Item = Result<FeedChunk>> + Send + 'static {
+        let body = StreamReader::new(response.bytes_stream().map_err(std::io::Error::other));
+
+        BasePlanReader::new(BufReader::new(body)).into_stream(
+            self.provider_name.clone(),
+            self.currency,
+            self.time_zone,
+        )
+    }
 }
 
 impl EventProviderClient for HttpEventProviderClient {
+    fn name(&self) -> &str {
+        &self.provider_name
+    }
+
+    fn circuit_state(&self) -> Option<CircuitState> {
+        Some(self.circuit_breaker.state())
+    }
+
     async fn fetch_events(&self) -> Result<Vec<ProviderEvent>> {
-        let url = Url::parse(&format!("{}{}", self.provider_url, self.event_api_path))?;
-        let response_body_text = self
-            .client
-            .get(url)
-            .send()
-            .await
-            .context("Failed to fetch events from Event Provider API")?
-            .text()
+        self.stream_events()
+            .map_ok(|chunk| chunk.events)
+            .try_concat()
             .await
-            .context("Failed to fetch events from Event Provider API")?;
+    }
+
+    fn stream_events(&self) -> impl Stream<Item = Result<FeedChunk>> + Send {
+        stream::once(async move {
+            let response = self.send_events_request(&FeedValidators::default()).await?;
 
-        serde_xml_rs::from_str(&response_body_text)
-            .map(EventPlanList::into)
-            .context("Failed to fetch events from Event Provider API")
+            Ok::<_, anyhow::Error>(self.parse_events_response(response))
+        })
+        .try_flatten()
+    }
+
+    async fn stream_events_if_modified(
+        &self,
+        validators: &FeedValidators,
+    ) -> Result<ConditionalFetch<impl Stream<Item = Result<FeedChunk>> + Send>> {
+        let response = self.send_events_request(validators).await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(ConditionalFetch::NotModified);
+        }
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from)
+        };
+        let validators = FeedValidators {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        };
+
+        Ok(ConditionalFetch::Modified {
+            events: self.parse_events_response(response),
+            validators,
+        })
     }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename = "planList")]
-struct EventPlanList {
-    output: Output,
+/// Pull parser that extracts one `base_plan` element at a time from a streamed plan list
+pub(super) struct BasePlanReader<R> {
+    reader: Reader<R>,
+    buffer: Vec<u8>,
 }
 
-#[derive(Debug, Deserialize)]
-struct Output {
-    #[serde(rename = "base_plan")]
-    base_plans: Vec<BasePlan>,
+impl<R: AsyncBufRead + Unpin + Send> BasePlanReader<R> {
+    pub(super) fn new(reader: R) -> Self {
+        Self {
+            reader: Reader::from_reader(reader),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Reads up to the next complete `base_plan`, self-closing ones included, or `None` once the
+    /// document ends. A `base_plan` that cannot be parsed is returned as its rejection.
+    async fn next_base_plan(&mut self) -> Result<Option<Result<BasePlan, ProviderRejection>>> {
+        let mut base_plan: Option<Writer<Vec<u8>>> = None;
+        let mut base_plan_id = None;
+        let mut depth = 0usize;
+        loop {
+            self.buffer.clear();
+            let event = self
+                .reader
+                .read_event_into_async(&mut self.buffer)
+                .await
+                .context("Failed to read events from Event Provider API")?;
+            if let Event::Eof = event {
+                return Ok(None);
+            }
+
+            let Some(writer) = base_plan.as_mut() else {
+                if let Event::Start(start) | Event::Empty(start) = &event
+                    && start.name().into_inner() == "base_plan"
+                {
+                    base_plan_id = start
+                        .try_get_attribute("base_plan_id")
+                        .ok()
+                        .flatten()
+                        .and_then(|attribute| {
+                            attribute.normalized_value(XmlVersion::Implicit1_0).ok()
+                        })
+                        .map(|value| value.into_owned());
+                    let self_closing = matches!(event, Event::Empty(_));
+                    let mut writer = Writer::new(Vec::new());
+                    writer.write_event(event)?;
+                    if self_closing {
+                        return Ok(Some(Self::parse_base_plan(writer, base_plan_id)));
+                    }
+                    base_plan = Some(writer);
+                    depth = 1;
+                }
+                continue;
+            };
+            match &event {
+                Event::Start(_) => depth += 1,
+                Event::End(_) => depth -= 1,
+                _ => {}
+            }
+            writer.write_event(event)?;
+            if depth > 0 {
+                continue;
+            }
+
+            if let Some(writer) = base_plan.take() {
+                return Ok(Some(Self::parse_base_plan(writer, base_plan_id.take())));
+            }
+        }
+    }
+
+    fn parse_base_plan(
+        writer: Writer<Vec<u8>>,
+        base_plan_id: Option<String>,
+    ) -> Result<BasePlan, ProviderRejection> {
+        serde_xml_rs::from_reader::<BasePlan, _>(writer.into_inner().as_slice()).map_err(|e| {
+            warn!("Failed to parse base plan from Provider API: {e:#}");
+            ProviderRejection {
+                base_plan_id,
+                plan_id: None,
+                zone_id: None,
+                reason: format!("Error parsing base plan: {e:#}"),
+            }
+        })
+    }
+
+    pub(super) fn into_stream(
+        self,
+        provider: String,
+        currency: Currency,
+        time_zone: Tz,
+    ) -> impl Stream<Item = Result<FeedChunk>> + Send + 'static
+    where
+        R: 'static,
+    {
+        stream::try_unfold(self, move |mut reader| {
+            let provider = provider.clone();
+            async move {
+                let Some(base_plan) = reader.next_base_plan().await? else {
+                    return Ok(None);
+                };
+                let chunk = match base_plan {
+                    Ok(base_plan) => base_plan.into_feed_chunk(&provider, currency, time_zone),
+                    Err(rejection) => FeedChunk {
+                        events: Vec::new(),
+                        rejections: vec![rejection],
+                    },
+                };
+                Ok(Some((chunk, reader)))
+            }
+        })
+    }
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename = "base_plan")]
-#[allow(dead_code)]
 struct BasePlan {
     #[serde(rename = "@base_plan_id")]
     base_plan_id: String,
     #[serde(rename = "@sell_mode")]
     sell_mode: String,
+    #[serde(rename = "@organizer_company_id", default)]
+    organizer_company_id: Option<String>,
     #[serde(rename = "@title")]
     title: String,
     #[serde(rename = "plan")]
@@ -72,7 +307,6 @@ struct BasePlan {
 }
 
 #[derive(Debug, Deserialize)]
-#[allow(dead_code)]
 struct Plan {
     #[serde(rename = "@plan_id")]
     plan_id: String,
@@ -80,123 +314,483 @@ struct Plan {
     plan_start_date: String,
     #[serde(rename = "@plan_end_date")]
     plan_end_date: String,
+    #[serde(rename = "@sell_from")]
+    sell_from: String,
+    #[serde(rename = "@sell_to")]
+    sell_to: String,
+    #[serde(rename = "@sold_out")]
+    sold_out: bool,
     #[serde(rename = "zone")]
     zones: Vec<Zone>,
 }
 
 #[derive(Debug, Deserialize)]
-#[allow(dead_code)]
 struct Zone {
     #[serde(rename = "@zone_id")]
     zone_id: String,
+    #[serde(rename = "@capacity")]
+    capacity: String,
     #[serde(rename = "@price")]
     price: String,
+    #[serde(rename = "@name")]
+    name: String,
+    #[serde(rename = "@numbered")]
+    numbered: String,
 }
 
-impl From<EventPlanList> for Vec<ProviderEvent> {
-    fn from(value: EventPlanList) -> Self {
-        value
-            .output
-            .base_plans
-            .iter()
-            .flat_map(|bp| {
-                bp.plans.iter().filter_map(|p| {
-                    ProviderEvent::from(p, &bp.title)
-                        .inspect_err(|e| {
-                            warn!("Failed to map event from Provider API to domain: {e:#}")
-                        })
-                        .ok()
-                })
-            })
-            .collect()
+impl BasePlan {
+    fn into_feed_chunk(self, provider: &str, currency: Currency, time_zone: Tz) -> FeedChunk {
+        let mut chunk = FeedChunk::default();
+        for p in &self.plans {
+            match ProviderEvent::from(p, &self, provider, currency, time_zone, &mut chunk) {
+                Ok(provider_event) => chunk.events.push(provider_event),
+                Err(e) => {
+                    warn!("Failed to map event from Provider API to domain: {e:#}");
+                    chunk.rejections.push(ProviderRejection {
+                        base_plan_id: Some(self.base_plan_id.clone()),
+                        plan_id: Some(p.plan_id.clone()),
+                        zone_id: None,
+                        reason: format!("{e:#}"),
+                    });
+                }
+            }
+        }
+
+        chunk
     }
 }
 
 impl ProviderEvent {
-    fn from(p: &Plan, title: &str) -> Result<Self> {
-        let min_price = p
+    /// Maps a plan to an event, adding the zones that had to be left out to the chunk's rejections
+    fn from(
+        p: &Plan,
+        bp: &BasePlan,
+        provider: &str,
+        currency: Currency,
+        time_zone: Tz,
+        chunk: &mut FeedChunk,
+    ) -> Result<Self> {
+        let zones: Vec<EventZone> = p
             .zones
             .iter()
-            .filter_map(|z| z.price.parse::<f64>().ok())
-            .fold(f64::INFINITY, |a, b| a.min(b));
-        let max_price = p
-            .zones
+            .filter_map(|z| {
+                z.to_domain(currency)
+                    .inspect_err(|e| {
+                        warn!(
+                            "Failed to map zone {} of plan {} from Provider API to domain: {e:#}",
+                            z.zone_id, p.plan_id
+                        );
+                        chunk.rejections.push(ProviderRejection {
+                            base_plan_id: Some(bp.base_plan_id.clone()),
+                            plan_id: Some(p.plan_id.clone()),
+                            zone_id: Some(z.zone_id.clone()),
+                            reason: format!("{e:#}"),
+                        });
+                    })
+                    .ok()
+            })
+            .collect();
+        let min_price = zones
+            .iter()
+            .map(|z| z.price)
+            .min_by_key(|price| price.amount_in_minor_units());
+        let max_price = zones
             .iter()
-            .filter_map(|z| z.price.parse::<f64>().ok())
-            .fold(f64::NEG_INFINITY, |a, b| a.max(b));
+            .map(|z| z.price)
+            .max_by_key(|price| price.amount_in_minor_units());
 
-        if min_price < f64::INFINITY || max_price > f64::NEG_INFINITY {
+        if let (Some(min_price), Some(max_price)) = (min_price, max_price) {
             Ok(ProviderEvent {
-                title: String::from(title),
-                start_time: NaiveDateTime::from_str(&p.plan_start_date)
-                    .context(format!("Error parsing datetime {}", p.plan_start_date))?
-                    .and_utc(),
-                end_time: NaiveDateTime::from_str(&p.plan_end_date)
-                    .context(format!("Error parsing datetime {}", p.plan_end_date))?
-                    .and_utc(),
+                external_id: ExternalId {
+                    provider: String::from(provider),
+                    base_plan_id: bp.base_plan_id.clone(),
+                    plan_id: p.plan_id.clone(),
+                },
+                title: bp.title.clone(),
+                start_time: parse_local_datetime(&p.plan_start_date, time_zone)?,
+                end_time: parse_local_datetime(&p.plan_end_date, time_zone)?,
+                time_zone,
                 min_price,
                 max_price,
+                sell_mode: SellMode::from_str(&bp.sell_mode)
+                    .context(format!("Error parsing sell mode {}", bp.sell_mode))?,
+                organizer_company_id: bp.organizer_company_id.clone(),
+                sell_from: parse_local_datetime(&p.sell_from, time_zone)?,
+                sell_to: parse_local_datetime(&p.sell_to, time_zone)?,
+                sold_out: p.sold_out,
+                zones,
             })
         } else {
             Err(anyhow::Error::msg(
-                "Error parsing zone prices: all prices are invalid",
+                "Error parsing zones: all zones are invalid",
             ))
         }
     }
 }
 
+/// Interprets a provider datetime as wall-clock time in the provider's time zone
+pub(super) fn parse_local_datetime(value: &str, time_zone: Tz) -> Result<DateTime<Utc>> {
+    let naive =
+        NaiveDateTime::from_str(value).context(format!("Error parsing datetime {value}"))?;
+
+    time_zone
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+        .ok_or_else(|| anyhow::anyhow!("Datetime {value} does not exist in time zone {time_zone}"))
+}
+
+impl Zone {
+    fn to_domain(&self, currency: Currency) -> Result<EventZone> {
+        Ok(EventZone {
+            zone_id: self.zone_id.clone(),
+            name: self.name.clone(),
+            price: Money::from_decimal_str(&self.price, currency)
+                .context(format!("Error parsing zone price {}", self.price))?,
+            capacity: self
+                .capacity
+                .parse::<u32>()
+                .context(format!("Error parsing zone capacity {}", self.capacity))?,
+            numbered: self
+                .numbered
+                .parse::<bool>()
+                .context(format!("Error parsing zone numbering {}", self.numbered))?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::time::Duration;
-
     use chrono::DateTime;
     use httpmock::MockServer;
     use reqwest::StatusCode;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
 
     use super::*;
 
-    #[test]
-    fn provider_response_is_deserialized_and_mapped_correctly() {
+    fn eur_currency() -> Currency {
+        Currency::from_str("EUR").unwrap()
+    }
+
+    fn eur(amount_in_cents: i64) -> Money {
+        Money::new(amount_in_cents, eur_currency())
+    }
+
+    fn zone(
+        zone_id: &str,
+        name: &str,
+        price_in_cents: i64,
+        capacity: u32,
+        numbered: bool,
+    ) -> EventZone {
+        EventZone {
+            zone_id: zone_id.to_string(),
+            name: name.to_string(),
+            price: eur(price_in_cents),
+            capacity,
+            numbered,
+        }
+    }
+
+    fn external_id(base_plan_id: &str, plan_id: &str) -> ExternalId {
+        ExternalId {
+            provider: "test-provider".to_string(),
+            base_plan_id: base_plan_id.to_string(),
+            plan_id: plan_id.to_string(),
+        }
+    }
+
+    fn client_for(
+        provider_server_mock: &MockServer,
+        retry_policy: RetryPolicy,
+        circuit_breaker: CircuitBreaker,
+    ) -> HttpEventProviderClient {
+        HttpEventProviderClient::new(
+            "test-provider".to_string(),
+            eur_currency(),
+            Tz::Europe__Madrid,
+            format!("http://localhost:{}", provider_server_mock.port()),
+            "/api/events".to_string(),
+            HttpEventProviderClient::http_client(Duration::from_secs(3)).unwrap(),
+        )
+        .with_resilience(retry_policy, circuit_breaker)
+    }
+
+    /// Serves a single request with the body split in `pieces`, waiting `gap` before each of them
+    async fn serve_slowly(body: String, pieces: usize, gap: Duration) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = socket.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            socket.write_all(head.as_bytes()).await.unwrap();
+            for piece in body.as_bytes().chunks(body.len().div_ceil(pieces)) {
+                tokio::time::sleep(gap).await;
+                if socket.write_all(piece).await.is_err() {
+                    return;
+                }
+            }
+        });
+        port
+    }
+
+    fn client_with_timeout(port: u16, timeout: Duration) -> HttpEventProviderClient {
+        HttpEventProviderClient::new(
+            "test-provider".to_string(),
+            eur_currency(),
+            Tz::Europe__Madrid,
+            format!("http://127.0.0.1:{port}"),
+            "/api/events".to_string(),
+            HttpEventProviderClient::http_client(timeout).unwrap(),
+        )
+        .with_resilience(fast_retry_policy(1), CircuitBreaker::default())
+    }
+
+    fn fast_retry_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(20),
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn provider_response_is_deserialized_and_mapped_correctly() {
         let response_text =
             std::fs::read_to_string("test/fixtures/provider_response_1.xml").unwrap();
 
-        let plan_list: EventPlanList = serde_xml_rs::from_str(&response_text).unwrap();
-        let provider_events: Vec<ProviderEvent> = plan_list.into();
+        let provider_events: Vec<ProviderEvent> =
+            BasePlanReader::new(std::io::Cursor::new(response_text.into_bytes()))
+                .into_stream(
+                    "test-provider".to_string(),
+                    eur_currency(),
+                    Tz::Europe__Madrid,
+                )
+                .map_ok(|chunk| chunk.events)
+                .try_concat()
+                .await
+                .unwrap();
 
         assert_eq!(provider_events.len(), 4);
         let expected = vec![
             ProviderEvent {
+                external_id: external_id("291", "291"),
                 title: "Camela en concierto".to_string(),
-                start_time: DateTime::from_str("2021-06-30T21:00:00Z").unwrap(),
-                end_time: DateTime::from_str("2021-06-30T22:00:00Z").unwrap(),
-                min_price: 15.0f64,
-                max_price: 30.0f64,
+                start_time: DateTime::from_str("2021-06-30T19:00:00Z").unwrap(),
+                end_time: DateTime::from_str("2021-06-30T20:00:00Z").unwrap(),
+                time_zone: Tz::Europe__Madrid,
+                min_price: eur(1500),
+                max_price: eur(3000),
+                sell_mode: SellMode::Online,
+                organizer_company_id: None,
+                sell_from: DateTime::from_str("2020-06-30T22:00:00Z").unwrap(),
+                sell_to: DateTime::from_str("2021-06-30T18:00:00Z").unwrap(),
+                sold_out: false,
+                zones: vec![
+                    zone("40", "Platea", 2000, 243, true),
+                    zone("38", "Grada 2", 1500, 100, false),
+                    zone("30", "A28", 3000, 90, true),
+                ],
             },
             ProviderEvent {
+                external_id: external_id("322", "1642"),
                 title: "Pantomima Full".to_string(),
-                start_time: DateTime::from_str("2021-02-10T20:00:00Z").unwrap(),
-                end_time: DateTime::from_str("2021-02-10T21:30:00Z").unwrap(),
-                min_price: 55.0f64,
-                max_price: 55.0f64,
+                start_time: DateTime::from_str("2021-02-10T19:00:00Z").unwrap(),
+                end_time: DateTime::from_str("2021-02-10T20:30:00Z").unwrap(),
+                time_zone: Tz::Europe__Madrid,
+                min_price: eur(5500),
+                max_price: eur(5500),
+                sell_mode: SellMode::Online,
+                organizer_company_id: Some("2".to_string()),
+                sell_from: DateTime::from_str("2020-12-31T23:00:00Z").unwrap(),
+                sell_to: DateTime::from_str("2021-02-09T18:50:00Z").unwrap(),
+                sold_out: false,
+                zones: vec![zone("311", "A42", 5500, 2, true)],
             },
             ProviderEvent {
+                external_id: external_id("322", "1643"),
                 title: "Pantomima Full".to_string(),
-                start_time: DateTime::from_str("2021-02-11T20:00:00Z").unwrap(),
-                end_time: DateTime::from_str("2021-02-11T21:30:00Z").unwrap(),
-                min_price: 55.0f64,
-                max_price: 55.0f64,
+                start_time: DateTime::from_str("2021-02-11T19:00:00Z").unwrap(),
+                end_time: DateTime::from_str("2021-02-11T20:30:00Z").unwrap(),
+                time_zone: Tz::Europe__Madrid,
+                min_price: eur(5500),
+                max_price: eur(5500),
+                sell_mode: SellMode::Online,
+                organizer_company_id: Some("2".to_string()),
+                sell_from: DateTime::from_str("2020-12-31T23:00:00Z").unwrap(),
+                sell_to: DateTime::from_str("2021-02-10T18:50:00Z").unwrap(),
+                sold_out: false,
+                zones: vec![zone("311", "A42", 5500, 2, true)],
             },
             ProviderEvent {
+                external_id: external_id("1591", "1642"),
                 title: "Los Morancos".to_string(),
-                start_time: DateTime::from_str("2021-07-31T20:00:00Z").unwrap(),
-                end_time: DateTime::from_str("2021-07-31T21:00:00Z").unwrap(),
-                min_price: 65.0f64,
-                max_price: 75.0f64,
+                start_time: DateTime::from_str("2021-07-31T18:00:00Z").unwrap(),
+                end_time: DateTime::from_str("2021-07-31T19:00:00Z").unwrap(),
+                time_zone: Tz::Europe__Madrid,
+                min_price: eur(6500),
+                max_price: eur(7500),
+                sell_mode: SellMode::Online,
+                organizer_company_id: Some("1".to_string()),
+                sell_from: DateTime::from_str("2021-06-25T22:00:00Z").unwrap(),
+                sell_to: DateTime::from_str("2021-07-31T17:50:00Z").unwrap(),
+                sold_out: false,
+                zones: vec![
+                    zone("186", "Amfiteatre", 7500, 2, true),
+                    zone("186", "Amfiteatre", 6500, 16, false),
+                ],
             },
         ];
         assert_eq!(provider_events, expected);
     }
 
+    #[tokio::test]
+    async fn client_downloads_slow_feeds_that_keep_arriving_past_the_timeout() {
+        let response_text =
+            std::fs::read_to_string("test/fixtures/provider_response_1.xml").unwrap();
+        let port = serve_slowly(response_text, 10, Duration::from_millis(150)).await;
+        let client = client_with_timeout(port, Duration::from_millis(500));
+
+        let provider_events = client.fetch_events().await.unwrap();
+
+        assert_eq!(provider_events.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn client_fails_when_feed_stops_arriving_for_longer_than_the_timeout() {
+        let response_text =
+            std::fs::read_to_string("test/fixtures/provider_response_1.xml").unwrap();
+        let port = serve_slowly(response_text, 2, Duration::from_millis(800)).await;
+        let client = client_with_timeout(port, Duration::from_millis(300));
+
+        assert!(client.fetch_events().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn provider_response_is_streamed_one_base_plan_at_a_time() {
+        let response_text =
+            std::fs::read_to_string("test/fixtures/provider_response_1.xml").unwrap();
+
+        let chunks: Vec<FeedChunk> =
+            BasePlanReader::new(std::io::Cursor::new(response_text.into_bytes()))
+                .into_stream(
+                    "test-provider".to_string(),
+                    eur_currency(),
+                    Tz::Europe__Madrid,
+                )
+                .try_collect()
+                .await
+                .unwrap();
+
+        let chunk_lengths: Vec<usize> = chunks.iter().map(|chunk| chunk.events.len()).collect();
+        assert_eq!(chunk_lengths, vec![1, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn items_that_cannot_be_mapped_are_reported_as_rejections() {
+        let response_text = r#"<planList><output>
+            <base_plan base_plan_id="1" sell_mode="online">
+                <plan plan_start_date="2021-06-30T21:00:00" plan_end_date="2021-06-30T22:00:00" plan_id="1" sell_from="2020-07-01T00:00:00" sell_to="2021-06-30T20:00:00" sold_out="false" />
+            </base_plan>
+            <base_plan base_plan_id="444" sell_mode="online" title="Tributo a Juanito Valderrama">
+                <plan plan_start_date="2021-09-31T20:00:00" plan_end_date="2021-09-31T21:00:00" plan_id="1642" sell_from="2021-02-10T00:00:00" sell_to="2021-09-30T19:50:00" sold_out="false">
+                    <zone zone_id="7" capacity="22" price="65.00" name="Amfiteatre" numbered="false" />
+                </plan>
+                <plan plan_start_date="2021-10-01T20:00:00" plan_end_date="2021-10-01T21:00:00" plan_id="1643" sell_from="2021-02-10T00:00:00" sell_to="2021-10-01T19:50:00" sold_out="false">
+                    <zone zone_id="7" capacity="22" price="65.00" name="Amfiteatre" numbered="false" />
+                    <zone zone_id="8" capacity="10" price="free" name="Palco" numbered="true" />
+                </plan>
+            </base_plan>
+        </output></planList>"#;
+
+        let chunks: Vec<FeedChunk> =
+            BasePlanReader::new(std::io::Cursor::new(response_text.as_bytes().to_vec()))
+                .into_stream(
+                    "test-provider".to_string(),
+                    eur_currency(),
+                    Tz::Europe__Madrid,
+                )
+                .try_collect()
+                .await
+                .unwrap();
+
+        let events: Vec<&ExternalId> = chunks
+            .iter()
+            .flat_map(|chunk| &chunk.events)
+            .map(|event| &event.external_id)
+            .collect();
+        assert_eq!(events, vec![&external_id("444", "1643")]);
+        let rejections: Vec<(Option<&str>, Option<&str>, Option<&str>)> = chunks
+            .iter()
+            .flat_map(|chunk| &chunk.rejections)
+            .map(|rejection| {
+                (
+                    rejection.base_plan_id.as_deref(),
+                    rejection.plan_id.as_deref(),
+                    rejection.zone_id.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            rejections,
+            vec![
+                (Some("1"), None, None),
+                (Some("444"), Some("1642"), None),
+                (Some("444"), Some("1643"), Some("8")),
+            ]
+        );
+        assert_eq!(
+            chunks[1].rejections[0].reason,
+            "Error parsing datetime 2021-09-31T20:00:00: input is out of range"
+        );
+    }
+
+    #[tokio::test]
+    async fn self_closing_base_plans_are_reported_as_rejections() {
+        let response_text = r#"<planList><output>
+            <base_plan base_plan_id="2" sell_mode="online" title="Sin planes" />
+            <base_plan base_plan_id="291" sell_mode="online" title="Camela en concierto">
+                <plan plan_start_date="2021-06-30T21:00:00" plan_end_date="2021-06-30T22:00:00" plan_id="291" sell_from="2020-07-01T00:00:00" sell_to="2021-06-30T20:00:00" sold_out="false">
+                    <zone zone_id="40" capacity="243" price="20.00" name="Platea" numbered="true" />
+                </plan>
+            </base_plan>
+        </output></planList>"#;
+
+        let chunks: Vec<FeedChunk> =
+            BasePlanReader::new(std::io::Cursor::new(response_text.as_bytes().to_vec()))
+                .into_stream(
+                    "test-provider".to_string(),
+                    eur_currency(),
+                    Tz::Europe__Madrid,
+                )
+                .try_collect()
+                .await
+                .unwrap();
+
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[0].events.is_empty());
+        assert_eq!(chunks[0].rejections.len(), 1);
+        assert_eq!(chunks[0].rejections[0].base_plan_id.as_deref(), Some("2"));
+        assert!(chunks[1].rejections.is_empty());
+        let events: Vec<&ExternalId> = chunks[1]
+            .events
+            .iter()
+            .map(|event| &event.external_id)
+            .collect();
+        assert_eq!(events, vec![&external_id("291", "291")]);
+    }
+
     #[tokio::test]
     async fn client_fetches_events_from_provider_correctly() {
         let provider_server_mock = MockServer::start();
@@ -206,13 +800,10 @@ mod tests {
                 .header("content-type", "application/xml")
                 .body(std::fs::read_to_string("test/fixtures/provider_response_2.xml").unwrap());
         });
-        let client = HttpEventProviderClient::new(
-            format!("http://localhost:{}", provider_server_mock.port()),
-            "/api/events".to_string(),
-            reqwest::Client::builder()
-                .timeout(Duration::from_secs(3))
-                .build()
-                .unwrap(),
+        let client = client_for(
+            &provider_server_mock,
+            RetryPolicy::default(),
+            CircuitBreaker::default(),
         );
 
         let provider_events = client.fetch_events().await;
@@ -223,25 +814,61 @@ mod tests {
         assert_eq!(provider_events.len(), 3);
         let expected = vec![
             ProviderEvent {
+                external_id: external_id("606", "303"),
                 title: "El Clasico".to_string(),
-                start_time: DateTime::from_str("2025-04-24T21:00:00Z").unwrap(),
-                end_time: DateTime::from_str("2025-04-24T23:45:00Z").unwrap(),
-                min_price: 120.0f64,
-                max_price: 250.0f64,
+                start_time: DateTime::from_str("2025-04-24T19:00:00Z").unwrap(),
+                end_time: DateTime::from_str("2025-04-24T21:45:00Z").unwrap(),
+                time_zone: Tz::Europe__Madrid,
+                min_price: eur(12000),
+                max_price: eur(25000),
+                sell_mode: SellMode::Online,
+                organizer_company_id: None,
+                sell_from: DateTime::from_str("2025-02-28T23:00:00Z").unwrap(),
+                sell_to: DateTime::from_str("2025-03-31T18:00:00Z").unwrap(),
+                sold_out: false,
+                zones: vec![
+                    zone("40", "Platea", 17000, 243, true),
+                    zone("38", "Grada 2", 12000, 100, false),
+                    zone("30", "A28", 25000, 90, true),
+                ],
             },
             ProviderEvent {
+                external_id: external_id("707", "2300"),
                 title: "Bruce Springsteen toma Madrid".to_string(),
-                start_time: DateTime::from_str("2025-08-31T18:00:00Z").unwrap(),
-                end_time: DateTime::from_str("2025-08-31T22:30:00Z").unwrap(),
-                min_price: 89.0f64,
-                max_price: 199.99f64,
+                start_time: DateTime::from_str("2025-08-31T16:00:00Z").unwrap(),
+                end_time: DateTime::from_str("2025-08-31T20:30:00Z").unwrap(),
+                time_zone: Tz::Europe__Madrid,
+                min_price: eur(8900),
+                max_price: eur(19999),
+                sell_mode: SellMode::Online,
+                organizer_company_id: None,
+                sell_from: DateTime::from_str("2025-02-28T23:00:00Z").unwrap(),
+                sell_to: DateTime::from_str("2025-03-31T18:00:00Z").unwrap(),
+                sold_out: false,
+                zones: vec![
+                    zone("50", "Platea", 8900, 243, true),
+                    zone("27", "Grada 2", 9900, 100, false),
+                    zone("61", "A28", 19999, 90, true),
+                ],
             },
             ProviderEvent {
+                external_id: external_id("707", "1404"),
                 title: "Bruce Springsteen toma Madrid".to_string(),
-                start_time: DateTime::from_str("2025-09-01T18:00:00Z").unwrap(),
-                end_time: DateTime::from_str("2025-09-01T22:30:00Z").unwrap(),
-                min_price: 75.95f64,
-                max_price: 209.99f64,
+                start_time: DateTime::from_str("2025-09-01T16:00:00Z").unwrap(),
+                end_time: DateTime::from_str("2025-09-01T20:30:00Z").unwrap(),
+                time_zone: Tz::Europe__Madrid,
+                min_price: eur(7595),
+                max_price: eur(20999),
+                sell_mode: SellMode::Online,
+                organizer_company_id: None,
+                sell_from: DateTime::from_str("2025-02-28T23:00:00Z").unwrap(),
+                sell_to: DateTime::from_str("2025-03-31T18:00:00Z").unwrap(),
+                sold_out: false,
+                zones: vec![
+                    zone("50", "Platea", 7595, 243, true),
+                    zone("27", "Grada 2", 10999, 100, false),
+                    zone("61", "A28", 20999, 90, true),
+                ],
             },
         ];
         assert_eq!(provider_events, expected);
@@ -256,13 +883,10 @@ mod tests {
                 .header("content-type", "application/xml")
                 .body(std::fs::read_to_string("test/fixtures/provider_response_3.xml").unwrap());
         });
-        let client = HttpEventProviderClient::new(
-            format!("http://localhost:{}", provider_server_mock.port()),
-            "/api/events".to_string(),
-            reqwest::Client::builder()
-                .timeout(Duration::from_secs(3))
-                .build()
-                .unwrap(),
+        let client = client_for(
+            &provider_server_mock,
+            RetryPolicy::default(),
+            CircuitBreaker::default(),
         );
 
         let provider_events = client.fetch_events().await;
@@ -273,20 +897,216 @@ mod tests {
         assert_eq!(provider_events.len(), 2);
         let expected = vec![
             ProviderEvent {
+                external_id: external_id("291", "291"),
                 title: "Camela en concierto".to_string(),
-                start_time: DateTime::from_str("2021-06-30T21:00:00Z").unwrap(),
-                end_time: DateTime::from_str("2021-06-30T22:00:00Z").unwrap(),
-                min_price: 15.0f64,
-                max_price: 30.0f64,
+                start_time: DateTime::from_str("2021-06-30T19:00:00Z").unwrap(),
+                end_time: DateTime::from_str("2021-06-30T20:00:00Z").unwrap(),
+                time_zone: Tz::Europe__Madrid,
+                min_price: eur(1500),
+                max_price: eur(3000),
+                sell_mode: SellMode::Online,
+                organizer_company_id: None,
+                sell_from: DateTime::from_str("2020-06-30T22:00:00Z").unwrap(),
+                sell_to: DateTime::from_str("2021-06-30T18:00:00Z").unwrap(),
+                sold_out: false,
+                zones: vec![
+                    zone("40", "Platea", 2000, 240, true),
+                    zone("38", "Grada 2", 1500, 50, false),
+                    zone("30", "A28", 3000, 90, true),
+                ],
             },
             ProviderEvent {
+                external_id: external_id("1591", "1642"),
                 title: "Los Morancos".to_string(),
-                start_time: DateTime::from_str("2021-07-31T20:00:00Z").unwrap(),
-                end_time: DateTime::from_str("2021-07-31T21:20:00Z").unwrap(),
-                min_price: 65.0f64,
-                max_price: 75.0f64,
+                start_time: DateTime::from_str("2021-07-31T18:00:00Z").unwrap(),
+                end_time: DateTime::from_str("2021-07-31T19:20:00Z").unwrap(),
+                time_zone: Tz::Europe__Madrid,
+                min_price: eur(6500),
+                max_price: eur(7500),
+                sell_mode: SellMode::Online,
+                organizer_company_id: Some("1".to_string()),
+                sell_from: DateTime::from_str("2021-06-25T22:00:00Z").unwrap(),
+                sell_to: DateTime::from_str("2021-07-31T17:50:00Z").unwrap(),
+                sold_out: false,
+                zones: vec![
+                    zone("186", "Amfiteatre", 7500, 0, true),
+                    zone("186", "Amfiteatre", 6500, 14, false),
+                ],
             },
         ];
         assert_eq!(provider_events, expected);
     }
+
+    #[tokio::test]
+    async fn client_retries_retryable_failures_until_provider_recovers() {
+        let provider_server_mock = MockServer::start_async().await;
+        let unavailable_mock = provider_server_mock
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/events");
+                then.status(StatusCode::SERVICE_UNAVAILABLE.as_u16());
+            })
+            .await;
+        let client = client_for(
+            &provider_server_mock,
+            RetryPolicy {
+                initial_backoff: Duration::from_millis(400),
+                ..RetryPolicy::default()
+            },
+            CircuitBreaker::default(),
+        );
+
+        let fetch = tokio::spawn(async move { client.fetch_events().await });
+        while unavailable_mock.calls_async().await == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        unavailable_mock.delete_async().await;
+        let events_mock = provider_server_mock
+            .mock_async(|when, then| {
+                when.method("GET").path("/api/events");
+                then.status(StatusCode::OK.as_u16())
+                    .header("content-type", "application/xml")
+                    .body(
+                        std::fs::read_to_string("test/fixtures/provider_response_2.xml").unwrap(),
+                    );
+            })
+            .await;
+        let provider_events = fetch.await.unwrap();
+
+        events_mock.assert_async().await;
+        assert_eq!(provider_events.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn client_gives_up_after_max_attempts() {
+        let provider_server_mock = MockServer::start();
+        let events_mock = provider_server_mock.mock(|when, then| {
+            when.method("GET").path("/api/events");
+            then.status(StatusCode::BAD_GATEWAY.as_u16());
+        });
+        let client = client_for(
+            &provider_server_mock,
+            fast_retry_policy(3),
+            CircuitBreaker::default(),
+        );
+
+        let provider_events = client.fetch_events().await;
+
+        events_mock.assert_calls(3);
+        assert!(provider_events.is_err());
+    }
+
+    #[tokio::test]
+    async fn client_does_not_retry_non_retryable_statuses() {
+        let provider_server_mock = MockServer::start();
+        let events_mock = provider_server_mock.mock(|when, then| {
+            when.method("GET").path("/api/events");
+            then.status(StatusCode::NOT_FOUND.as_u16());
+        });
+        let client = client_for(
+            &provider_server_mock,
+            fast_retry_policy(3),
+            CircuitBreaker::default(),
+        );
+
+        let provider_events = client.fetch_events().await;
+
+        events_mock.assert_calls(1);
+        assert!(provider_events.is_err());
+    }
+
+    #[tokio::test]
+    async fn client_stops_calling_provider_once_circuit_breaker_opens() {
+        let provider_server_mock = MockServer::start();
+        let events_mock = provider_server_mock.mock(|when, then| {
+            when.method("GET").path("/api/events");
+            then.status(StatusCode::INTERNAL_SERVER_ERROR.as_u16());
+        });
+        let client = client_for(
+            &provider_server_mock,
+            fast_retry_policy(1),
+            CircuitBreaker::new(2, Duration::from_secs(60)),
+        );
+
+        assert!(client.fetch_events().await.is_err());
+        assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+        assert!(client.fetch_events().await.is_err());
+        assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+        let provider_events = client.fetch_events().await;
+
+        events_mock.assert_calls(2);
+        assert!(provider_events.is_err());
+    }
+
+    #[tokio::test]
+    async fn client_returns_validators_of_fetched_feed() {
+        let provider_server_mock = MockServer::start();
+        let events_mock = provider_server_mock.mock(|when, then| {
+            when.method("GET").path("/api/events");
+            then.status(StatusCode::OK.as_u16())
+                .header("content-type", "application/xml")
+                .header("etag", "\"v1\"")
+                .header("last-modified", "Wed, 21 Oct 2025 07:28:00 GMT")
+                .body(std::fs::read_to_string("test/fixtures/provider_response_2.xml").unwrap());
+        });
+        let client = client_for(
+            &provider_server_mock,
+            RetryPolicy::default(),
+            CircuitBreaker::default(),
+        );
+
+        let previous_validators = FeedValidators::default();
+
+        let fetch = client
+            .stream_events_if_modified(&previous_validators)
+            .await
+            .unwrap();
+
+        events_mock.assert();
+        let ConditionalFetch::Modified { events, validators } = fetch else {
+            panic!("feed should have been fetched");
+        };
+        assert_eq!(
+            validators,
+            FeedValidators {
+                etag: Some("\"v1\"".to_string()),
+                last_modified: Some("Wed, 21 Oct 2025 07:28:00 GMT".to_string()),
+            }
+        );
+        let events: Vec<ProviderEvent> = events
+            .map_ok(|chunk| chunk.events)
+            .try_concat()
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn client_reports_unchanged_feed_as_not_modified() {
+        let provider_server_mock = MockServer::start();
+        let not_modified_mock = provider_server_mock.mock(|when, then| {
+            when.method("GET")
+                .path("/api/events")
+                .header("if-none-match", "\"v1\"")
+                .header("if-modified-since", "Wed, 21 Oct 2025 07:28:00 GMT");
+            then.status(StatusCode::NOT_MODIFIED.as_u16());
+        });
+        let client = client_for(
+            &provider_server_mock,
+            RetryPolicy::default(),
+            CircuitBreaker::default(),
+        );
+
+        let previous_validators = FeedValidators {
+            etag: Some("\"v1\"".to_string()),
+            last_modified: Some("Wed, 21 Oct 2025 07:28:00 GMT".to_string()),
+        };
+
+        let fetch = client
+            .stream_events_if_modified(&previous_validators)
+            .await
+            .unwrap();
+
+        not_modified_mock.assert();
+        assert!(matches!(fetch, ConditionalFetch::NotModified));
+    }
 }
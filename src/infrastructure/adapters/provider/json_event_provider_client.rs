@@ -0,0 +1,532 @@
+use std::str::FromStr;
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use futures::stream::{self, Stream};
+use log::warn;
+use reqwest::header::HeaderMap;
+use reqwest::{Client, Url};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::application::ports::provider::{
+    CircuitState, EventProviderClient, FeedChunk, ProviderEvent, ProviderRejection,
+};
+use crate::domain::event::{ExternalId, SellMode};
+use crate::domain::money::{Currency, Money};
+use crate::domain::zone::Zone;
+use crate::infrastructure::adapters::provider::http_event_provider_client::parse_local_datetime;
+use crate::infrastructure::adapters::provider::resilience::{
+    CircuitBreaker, RetryPolicy, get_with_resilience,
+};
+
+/// Location of every event attribute in a JSON feed, as JSON Pointers. `events` points into the
+/// document, zone attributes into a zone and the rest into an event.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct JsonFeedShape {
+    pub events: String,
+    pub base_plan_id: String,
+    /// Falls back to the base plan id when an event has no plan id
+    pub plan_id: String,
+    pub title: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub sell_mode: String,
+    pub organizer_company_id: String,
+    pub sell_from: String,
+    pub sell_to: String,
+    pub sold_out: String,
+    pub zones: String,
+    pub zone_id: String,
+    pub zone_name: String,
+    pub zone_price: String,
+    pub zone_capacity: String,
+    pub zone_numbered: String,
+}
+
+impl Default for JsonFeedShape {
+    fn default() -> Self {
+        Self {
+            events: "/events".to_string(),
+            base_plan_id: "/base_plan_id".to_string(),
+            plan_id: "/plan_id".to_string(),
+            title: "/title".to_string(),
+            start_time: "/start_time".to_string(),
+            end_time: "/end_time".to_string(),
+            sell_mode: "/sell_mode".to_string(),
+            organizer_company_id: "/organizer_company_id".to_string(),
+            sell_from: "/sell_from".to_string(),
+            sell_to: "/sell_to".to_string(),
+            sold_out: "/sold_out".to_string(),
+            zones: "/zones".to_string(),
+            zone_id: "/zone_id".to_string(),
+            zone_name: "/name".to_string(),
+            zone_price: "/price".to_string(),
+            zone_capacity: "/capacity".to_string(),
+            zone_numbered: "/numbered".to_string(),
+        }
+    }
+}
+
+pub struct JsonEventProviderClient {
+    provider_name: String,
+    currency: Currency,
+    time_zone: Tz,
+    provider_url: String,
+    event_api_path: String,
+    client: Client,
+    shape: JsonFeedShape,
+    retry_policy: RetryPolicy,
+    circuit_breaker: CircuitBreaker,
+}
+
+impl JsonEventProviderClient {
+    pub fn new(
+        provider_name: String,
+        currency: Currency,
+        time_zone: Tz,
+        provider_url: String,
+        event_api_path: String,
+        client: Client,
+        shape: JsonFeedShape,
+    ) -> Self {
+        Self {
+            provider_name,
+            currency,
+            time_zone,
+            provider_url,
+            event_api_path,
+            client,
+            shape,
+            retry_policy: RetryPolicy::default(),
+            circuit_breaker: CircuitBreaker::default(),
+        }
+    }
+
+    pub fn with_resilience(
+        mut self,
+        retry_policy: RetryPolicy,
+        circuit_breaker: CircuitBreaker,
+    ) -> Self {
+        self.retry_policy = retry_policy;
+        self.circuit_breaker = circuit_breaker;
+        self
+    }
+
+    async fn fetch_feed(&self) -> Result<FeedChunk> {
+        let url = Url::parse(&format!("{}{}", self.provider_url, self.event_api_path))?;
+        let response = get_with_resilience(
+            &self.client,
+            url,
+            HeaderMap::new(),
+            &self.provider_name,
+            &self.retry_policy,
+            &self.circuit_breaker,
+        )
+        .await?;
+        let body = response
+            .bytes()
+            .await
+            .context("Failed to read events from Event Provider API")?;
+        let document: Value = serde_json::from_slice(&body)
+            .context("Failed to parse events from Event Provider API")?;
+
+        self.parse_feed(&document)
+    }
+
+    /// Maps every well-formed event of a feed document, rejecting the malformed ones
+    fn parse_feed(&self, document: &Value) -> Result<FeedChunk> {
+        let events = document
+            .pointer(&self.shape.events)
+            .and_then(Value::as_array)
+            .ok_or_else(|| anyhow!("JSON feed has no event array at {}", self.shape.events))?;
+
+        let mut chunk = FeedChunk::default();
+        for (index, event) in events.iter().enumerate() {
+            match self.map_event(event, &mut chunk) {
+                Ok(provider_event) => chunk.events.push(provider_event),
+                Err(e) => {
+                    warn!("Failed to map event {index} from Provider API to domain: {e:#}");
+                    chunk.rejections.push(ProviderRejection {
+                        base_plan_id: optional_text(event, &self.shape.base_plan_id)
+                            .ok()
+                            .flatten(),
+                        plan_id: optional_text(event, &self.shape.plan_id).ok().flatten(),
+                        zone_id: None,
+                        reason: format!("{e:#}"),
+                    });
+                }
+            }
+        }
+
+        Ok(chunk)
+    }
+
+    /// Maps an event, adding the zones that had to be left out to the chunk's rejections
+    fn map_event(&self, event: &Value, chunk: &mut FeedChunk) -> Result<ProviderEvent> {
+        let shape = &self.shape;
+        let base_plan_id = text(event, &shape.base_plan_id)?;
+        let plan_id = optional_text(event, &shape.plan_id)?.unwrap_or_else(|| base_plan_id.clone());
+        let zones: Vec<Zone> = event
+            .pointer(&shape.zones)
+            .and_then(Value::as_array)
+            .ok_or_else(|| anyhow!("Missing zone array at {}", shape.zones))?
+            .iter()
+            .enumerate()
+            .filter_map(|(index, zone)| {
+                self.map_zone(zone)
+                    .inspect_err(|e| {
+                        warn!(
+                            "Failed to map zone {index} of plan {plan_id} from Provider API to domain: {e:#}"
+                        );
+                        chunk.rejections.push(ProviderRejection {
+                            base_plan_id: Some(base_plan_id.clone()),
+                            plan_id: Some(plan_id.clone()),
+                            zone_id: optional_text(zone, &shape.zone_id).ok().flatten(),
+                            reason: format!("{e:#}"),
+                        });
+                    })
+                    .ok()
+            })
+            .collect();
+        let min_price = zones
+            .iter()
+            .map(|z| z.price)
+            .min_by_key(|price| price.amount_in_minor_units());
+        let max_price = zones
+            .iter()
+            .map(|z| z.price)
+            .max_by_key(|price| price.amount_in_minor_units());
+        let (Some(min_price), Some(max_price)) = (min_price, max_price) else {
+            bail!("Error parsing zones: all zones are invalid");
+        };
+        let sell_mode = text(event, &shape.sell_mode)?;
+
+        Ok(ProviderEvent {
+            external_id: ExternalId {
+                provider: self.provider_name.clone(),
+                base_plan_id,
+                plan_id,
+            },
+            title: text(event, &shape.title)?,
+            start_time: self.datetime(event, &shape.start_time)?,
+            end_time: self.datetime(event, &shape.end_time)?,
+            time_zone: self.time_zone,
+            min_price,
+            max_price,
+            sell_mode: SellMode::from_str(&sell_mode)
+                .context(format!("Error parsing sell mode {sell_mode}"))?,
+            organizer_company_id: optional_text(event, &shape.organizer_company_id)?,
+            sell_from: self.datetime(event, &shape.sell_from)?,
+            sell_to: self.datetime(event, &shape.sell_to)?,
+            sold_out: optional_bool(event, &shape.sold_out)?.unwrap_or(false),
+            zones,
+        })
+    }
+
+    fn map_zone(&self, zone: &Value) -> Result<Zone> {
+        let shape = &self.shape;
+        let price = text(zone, &shape.zone_price)?;
+        let capacity = text(zone, &shape.zone_capacity)?;
+
+        Ok(Zone {
+            zone_id: text(zone, &shape.zone_id)?,
+            name: text(zone, &shape.zone_name)?,
+            price: Money::from_decimal_str(&price, self.currency)
+                .context(format!("Error parsing zone price {price}"))?,
+            capacity: capacity
+                .parse::<u32>()
+                .context(format!("Error parsing zone capacity {capacity}"))?,
+            numbered: optional_bool(zone, &shape.zone_numbered)?
+                .ok_or_else(|| anyhow!("Missing zone numbering at {}", shape.zone_numbered))?,
+        })
+    }
+
+    /// Reads an RFC 3339 datetime, or a wall-clock one in the provider's time zone
+    fn datetime(&self, value: &Value, pointer: &str) -> Result<DateTime<Utc>> {
+        let datetime = text(value, pointer)?;
+        match DateTime::parse_from_rfc3339(&datetime) {
+            Ok(datetime) => Ok(datetime.with_timezone(&Utc)),
+            Err(_) => parse_local_datetime(&datetime, self.time_zone),
+        }
+    }
+}
+
+impl EventProviderClient for JsonEventProviderClient {
+    fn name(&self) -> &str {
+        &self.provider_name
+    }
+
+    fn circuit_state(&self) -> Option<CircuitState> {
+        Some(self.circuit_breaker.state())
+    }
+
+    async fn fetch_events(&self) -> Result<Vec<ProviderEvent>> {
+        Ok(self.fetch_feed().await?.events)
+    }
+
+    fn stream_events(&self) -> impl Stream<Item = Result<FeedChunk>> + Send {
+        stream::once(self.fetch_feed())
+    }
+}
+
+/// Reads a string, or a number or boolean as its text, treating null as absent
+fn optional_text(value: &Value, pointer: &str) -> Result<Option<String>> {
+    match value.pointer(pointer) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(text)) => Ok(Some(text.clone())),
+        Some(Value::Number(number)) => Ok(Some(number.to_string())),
+        Some(Value::Bool(boolean)) => Ok(Some(boolean.to_string())),
+        Some(_) => bail!("Field at {pointer} is not a scalar"),
+    }
+}
+
+fn text(value: &Value, pointer: &str) -> Result<String> {
+    optional_text(value, pointer)?.ok_or_else(|| anyhow!("Missing field at {pointer}"))
+}
+
+fn optional_bool(value: &Value, pointer: &str) -> Result<Option<bool>> {
+    optional_text(value, pointer)?
+        .map(|boolean| {
+            boolean
+                .parse::<bool>()
+                .context(format!("Error parsing boolean {boolean} at {pointer}"))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use httpmock::MockServer;
+    use reqwest::StatusCode;
+
+    use super::*;
+    use crate::infrastructure::adapters::provider::HttpEventProviderClient;
+
+    fn eur(amount_in_cents: i64) -> Money {
+        Money::new(amount_in_cents, Currency::from_str("EUR").unwrap())
+    }
+
+    fn client_for(
+        provider_server_mock: &MockServer,
+        shape: JsonFeedShape,
+    ) -> JsonEventProviderClient {
+        JsonEventProviderClient::new(
+            "test-provider".to_string(),
+            Currency::from_str("EUR").unwrap(),
+            Tz::Europe__Madrid,
+            format!("http://localhost:{}", provider_server_mock.port()),
+            "/api/events".to_string(),
+            HttpEventProviderClient::http_client(Duration::from_secs(3)).unwrap(),
+            shape,
+        )
+    }
+
+    #[tokio::test]
+    async fn client_fetches_events_from_json_feed_skipping_malformed_ones() {
+        let provider_server_mock = MockServer::start();
+        let events_mock = provider_server_mock.mock(|when, then| {
+            when.method("GET").path("/api/events");
+            then.status(StatusCode::OK.as_u16())
+                .header("content-type", "application/json")
+                .body(std::fs::read_to_string("test/fixtures/provider_response_1.json").unwrap());
+        });
+        let client = client_for(&provider_server_mock, JsonFeedShape::default());
+
+        let provider_events = client.fetch_events().await;
+
+        events_mock.assert();
+        let provider_events = provider_events.unwrap();
+        let expected = vec![
+            ProviderEvent {
+                external_id: ExternalId {
+                    provider: "test-provider".to_string(),
+                    base_plan_id: "606".to_string(),
+                    plan_id: "303".to_string(),
+                },
+                title: "El Clasico".to_string(),
+                start_time: DateTime::from_str("2025-04-24T19:00:00Z").unwrap(),
+                end_time: DateTime::from_str("2025-04-24T21:45:00Z").unwrap(),
+                time_zone: Tz::Europe__Madrid,
+                min_price: eur(12000),
+                max_price: eur(17000),
+                sell_mode: SellMode::Online,
+                organizer_company_id: None,
+                sell_from: DateTime::from_str("2025-02-28T23:00:00Z").unwrap(),
+                sell_to: DateTime::from_str("2025-03-31T18:00:00Z").unwrap(),
+                sold_out: false,
+                zones: vec![
+                    Zone {
+                        zone_id: "40".to_string(),
+                        name: "Platea".to_string(),
+                        price: eur(17000),
+                        capacity: 243,
+                        numbered: true,
+                    },
+                    Zone {
+                        zone_id: "38".to_string(),
+                        name: "Grada 2".to_string(),
+                        price: eur(12000),
+                        capacity: 100,
+                        numbered: false,
+                    },
+                ],
+            },
+            ProviderEvent {
+                external_id: ExternalId {
+                    provider: "test-provider".to_string(),
+                    base_plan_id: "707".to_string(),
+                    plan_id: "2300".to_string(),
+                },
+                title: "Bruce Springsteen toma Madrid".to_string(),
+                start_time: DateTime::from_str("2025-08-31T16:00:00Z").unwrap(),
+                end_time: DateTime::from_str("2025-08-31T20:30:00Z").unwrap(),
+                time_zone: Tz::Europe__Madrid,
+                min_price: eur(8900),
+                max_price: eur(8900),
+                sell_mode: SellMode::Offline,
+                organizer_company_id: Some("2".to_string()),
+                sell_from: DateTime::from_str("2025-02-28T23:00:00Z").unwrap(),
+                sell_to: DateTime::from_str("2025-03-31T18:00:00Z").unwrap(),
+                sold_out: true,
+                zones: vec![Zone {
+                    zone_id: "50".to_string(),
+                    name: "Platea".to_string(),
+                    price: eur(8900),
+                    capacity: 243,
+                    numbered: true,
+                }],
+            },
+        ];
+        assert_eq!(provider_events, expected);
+    }
+
+    #[test]
+    fn malformed_events_and_zones_of_json_feed_are_reported_as_rejections() {
+        let client = JsonEventProviderClient::new(
+            "test-provider".to_string(),
+            Currency::from_str("EUR").unwrap(),
+            Tz::Europe__Madrid,
+            "http://localhost".to_string(),
+            "/api/events".to_string(),
+            Client::new(),
+            JsonFeedShape::default(),
+        );
+        let document: Value = serde_json::from_str(
+            &std::fs::read_to_string("test/fixtures/provider_response_1.json").unwrap(),
+        )
+        .unwrap();
+
+        let chunk = client.parse_feed(&document).unwrap();
+
+        assert_eq!(chunk.events.len(), 2);
+        let rejection = |base_plan_id: Option<&str>,
+                         plan_id: Option<&str>,
+                         zone_id: Option<&str>,
+                         reason: &str| ProviderRejection {
+            base_plan_id: base_plan_id.map(String::from),
+            plan_id: plan_id.map(String::from),
+            zone_id: zone_id.map(String::from),
+            reason: reason.to_string(),
+        };
+        assert_eq!(
+            chunk.rejections,
+            vec![
+                rejection(
+                    Some("606"),
+                    Some("303"),
+                    Some("30"),
+                    "Error parsing zone price two hundred: Invalid decimal amount: two hundred"
+                ),
+                rejection(
+                    Some("707"),
+                    Some("1404"),
+                    None,
+                    "Error parsing sell mode teleported: Unknown sell mode: teleported"
+                ),
+                rejection(
+                    Some("808"),
+                    None,
+                    None,
+                    "Error parsing zones: all zones are invalid"
+                ),
+                rejection(None, None, None, "Missing field at /base_plan_id"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn client_maps_json_feed_with_configured_shape() {
+        let provider_server_mock = MockServer::start();
+        provider_server_mock.mock(|when, then| {
+            when.method("GET").path("/api/events");
+            then.status(StatusCode::OK.as_u16())
+                .header("content-type", "application/json")
+                .body(
+                    r#"{"data": {"items": [{
+                        "id": 42,
+                        "name": "Rosalia",
+                        "schedule": {"from": "2025-10-10T21:00:00+02:00", "to": "2025-10-10T23:00:00+02:00"},
+                        "sales": {"mode": "online", "opens": "2025-05-01T10:00:00", "closes": "2025-10-10T20:00:00"},
+                        "tiers": [{"code": "vip", "label": "VIP", "amount": 99.5, "seats": 20, "seated": "true"}]
+                    }]}}"#,
+                );
+        });
+        let shape = JsonFeedShape {
+            events: "/data/items".to_string(),
+            base_plan_id: "/id".to_string(),
+            title: "/name".to_string(),
+            start_time: "/schedule/from".to_string(),
+            end_time: "/schedule/to".to_string(),
+            sell_mode: "/sales/mode".to_string(),
+            sell_from: "/sales/opens".to_string(),
+            sell_to: "/sales/closes".to_string(),
+            zones: "/tiers".to_string(),
+            zone_id: "/code".to_string(),
+            zone_name: "/label".to_string(),
+            zone_price: "/amount".to_string(),
+            zone_capacity: "/seats".to_string(),
+            zone_numbered: "/seated".to_string(),
+            ..JsonFeedShape::default()
+        };
+        let client = client_for(&provider_server_mock, shape);
+
+        let provider_events = client.fetch_events().await.unwrap();
+
+        assert_eq!(provider_events.len(), 1);
+        let provider_event = &provider_events[0];
+        assert_eq!(provider_event.external_id.base_plan_id, "42");
+        assert_eq!(provider_event.external_id.plan_id, "42");
+        assert_eq!(provider_event.title, "Rosalia");
+        assert_eq!(
+            provider_event.start_time,
+            DateTime::<Utc>::from_str("2025-10-10T19:00:00Z").unwrap()
+        );
+        assert_eq!(
+            provider_event.sell_from,
+            DateTime::<Utc>::from_str("2025-05-01T08:00:00Z").unwrap()
+        );
+        assert_eq!(provider_event.min_price, eur(9950));
+        assert!(provider_event.zones[0].numbered);
+    }
+
+    #[tokio::test]
+    async fn client_fails_when_json_feed_has_no_event_array() {
+        let provider_server_mock = MockServer::start();
+        provider_server_mock.mock(|when, then| {
+            when.method("GET").path("/api/events");
+            then.status(StatusCode::OK.as_u16())
+                .header("content-type", "application/json")
+                .body(r#"{"items": []}"#);
+        });
+        let client = client_for(&provider_server_mock, JsonFeedShape::default());
+
+        let provider_events = client.fetch_events().await;
+
+        assert!(provider_events.is_err());
+    }
+}
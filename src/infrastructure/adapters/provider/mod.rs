@@ -1,5 +1,13 @@
+mod configured_event_provider_client;
 mod dummy_event_provider_client;
+mod file_event_provider_client;
 mod http_event_provider_client;
+mod json_event_provider_client;
+mod resilience;
+pub use configured_event_provider_client::ConfiguredEventProviderClient;
 #[cfg(test)]
 pub use dummy_event_provider_client::DummyEventProviderClient;
+pub use file_event_provider_client::FileEventProviderClient;
 pub use http_event_provider_client::HttpEventProviderClient;
+pub use json_event_provider_client::{JsonEventProviderClient, JsonFeedShape};
+pub use resilience::{CircuitBreaker, RetryPolicy};
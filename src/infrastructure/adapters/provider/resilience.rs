@@ -0,0 +1,278 @@
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use log::{info, warn};
+use reqwest::header::HeaderMap;
+use reqwest::{Client, Response, StatusCode, Url};
+
+use crate::application::ports::provider::CircuitState;
+
+/// When and how often a failed provider request is tried again
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Attempts per fetch, including the first one
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub retryable_statuses: Vec<StatusCode>,
+    pub retry_on_timeout: bool,
+    pub retry_on_connect_error: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            retryable_statuses: vec![
+                StatusCode::REQUEST_TIMEOUT,
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retry_on_timeout: true,
+            retry_on_connect_error: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn is_retryable(&self, error: &reqwest::Error) -> bool {
+        match error.status() {
+            Some(status) => self.retryable_statuses.contains(&status),
+            None => {
+                (self.retry_on_timeout && error.is_timeout())
+                    || (self.retry_on_connect_error && error.is_connect())
+            }
+        }
+    }
+
+    /// Exponential backoff before the given retry (1-based), half of it randomized to spread out
+    /// clients retrying at once
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponential = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_backoff);
+        let half = exponential / 2;
+
+        half + half.mul_f64(rand::random_range(0.0..=1.0))
+    }
+}
+
+/// Stops calling a provider after consecutive failed fetches, until it has had time to recover.
+/// Once it has, a single trial request decides whether the circuit closes again.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_duration: Duration,
+    state: Mutex<CircuitBreakerState>,
+}
+
+#[derive(Debug, Default)]
+struct CircuitBreakerState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// Start of the trial request of a half-open circuit, which has not finished yet
+    trial_started_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            open_duration,
+            state: Mutex::new(CircuitBreakerState::default()),
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        self.circuit_state(&self.lock_state())
+    }
+
+    /// Fails while the circuit is open, and while it is half-open for every request but the first,
+    /// which becomes its trial. A trial that never reports back is given up on after the time the
+    /// circuit stays open.
+    pub fn check(&self, provider: &str) -> Result<()> {
+        let mut state = self.lock_state();
+        match self.circuit_state(&state) {
+            CircuitState::Closed => {}
+            CircuitState::Open => {
+                bail!("Circuit breaker for provider {provider} is open: skipping request")
+            }
+            CircuitState::HalfOpen => {
+                if state
+                    .trial_started_at
+                    .is_some_and(|started_at| started_at.elapsed() < self.open_duration)
+                {
+                    bail!(
+                        "Circuit breaker for provider {provider} is half-open with a trial request in progress: skipping request"
+                    );
+                }
+                state.trial_started_at = Some(Instant::now());
+                info!("Circuit breaker for provider {provider} half-open: sending trial request");
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn record_success(&self, provider: &str) {
+        let mut state = self.lock_state();
+        if state.opened_at.take().is_some() {
+            info!("Circuit breaker for provider {provider} closed");
+        }
+        state.trial_started_at = None;
+        state.consecutive_failures = 0;
+    }
+
+    pub fn record_failure(&self, provider: &str) {
+        let mut state = self.lock_state();
+        state.trial_started_at = None;
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        // A failed trial re-opens the circuit straight away
+        if state.opened_at.is_some() || state.consecutive_failures >= self.failure_threshold {
+            state.opened_at = Some(Instant::now());
+            warn!(
+                "Circuit breaker for provider {provider} opened after {} consecutive failures",
+                state.consecutive_failures
+            );
+        }
+    }
+
+    /// The state is kept consistent by every update, so a panic elsewhere does not invalidate it
+    fn lock_state(&self) -> MutexGuard<'_, CircuitBreakerState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn circuit_state(&self, state: &CircuitBreakerState) -> CircuitState {
+        match state.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if opened_at.elapsed() < self.open_duration => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(5, Duration::from_secs(60))
+    }
+}
+
+/// Sends a GET request to a provider, retrying per the retry policy unless the circuit breaker is
+/// open, and records the outcome on the circuit breaker
+pub async fn get_with_resilience(
+    client: &Client,
+    url: Url,
+    headers: HeaderMap,
+    provider: &str,
+    retry_policy: &RetryPolicy,
+    circuit_breaker: &CircuitBreaker,
+) -> Result<Response> {
+    circuit_breaker.check(provider)?;
+
+    let mut attempt = 1;
+    let result = loop {
+        let result = client
+            .get(url.clone())
+            .headers(headers.clone())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        match result {
+            Err(error)
+                if attempt < retry_policy.max_attempts && retry_policy.is_retryable(&error) =>
+            {
+                let backoff = retry_policy.backoff(attempt);
+                warn!(
+                    "Attempt {attempt} to fetch events from provider {provider} failed, retrying in {backoff:?}: {error}"
+                );
+                tokio::time::sleep(backoff).await;
+                attempt += 1;
+            }
+            result => break result,
+        }
+    };
+
+    match result {
+        Ok(response) => {
+            circuit_breaker.record_success(provider);
+            Ok(response)
+        }
+        Err(error) => {
+            circuit_breaker.record_failure(provider);
+            Err(error).context(format!(
+                "Failed to fetch events from Event Provider API after {attempt} attempt(s)"
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_exponentially_up_to_its_maximum() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(1000),
+            ..RetryPolicy::default()
+        };
+
+        for (retry, expected) in [
+            (1, 100),
+            (2, 200),
+            (3, 400),
+            (4, 800),
+            (5, 1000),
+            (30, 1000),
+        ] {
+            let backoff = policy.backoff(retry);
+            let expected = Duration::from_millis(expected);
+            assert!(
+                backoff >= expected / 2 && backoff <= expected,
+                "retry {retry} backed off {backoff:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn circuit_opens_after_consecutive_failures_and_closes_on_success() {
+        let breaker = CircuitBreaker::new(2, Duration::ZERO);
+
+        breaker.record_failure("test-provider");
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        breaker.record_failure("test-provider");
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        breaker.record_success("test-provider");
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn half_open_circuit_lets_a_single_trial_request_through() {
+        let open_duration = Duration::from_millis(100);
+        let breaker = CircuitBreaker::new(1, open_duration);
+
+        breaker.record_failure("test-provider");
+        assert!(breaker.check("test-provider").is_err());
+        std::thread::sleep(open_duration);
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.check("test-provider").is_ok());
+        assert!(breaker.check("test-provider").is_err());
+        breaker.record_failure("test-provider");
+        assert_eq!(breaker.state(), CircuitState::Open);
+        std::thread::sleep(open_duration);
+        assert!(breaker.check("test-provider").is_ok());
+        assert!(breaker.check("test-provider").is_err());
+        breaker.record_success("test-provider");
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.check("test-provider").is_ok());
+        assert!(breaker.check("test-provider").is_ok());
+    }
+}
@@ -4,44 +4,224 @@ use anyhow::Result;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::application::ports::repository::{EventRepository, SaveEventRequest};
-use crate::domain::event::Event;
+use crate::application::ports::repository::{
+    BulkUpsertResult, EventCount, EventKey, EventPage, EventRepository, EventSearchCriteria,
+    EventSort, SaveEventRequest, TimeMatch, UpsertOutcome,
+};
+use crate::domain::event::{Event, ExternalId};
+use crate::domain::event_change::EventChange;
+use crate::domain::price_point::PricePoint;
+use crate::domain::zone::Zone;
 
 #[allow(dead_code)]
-pub struct DummyEventRepository(pub HashMap<Uuid, Event>);
+#[derive(Default)]
+pub struct DummyEventRepository {
+    pub events: HashMap<Uuid, Event>,
+    pub zones: HashMap<Uuid, Vec<Zone>>,
+    pub changes: Vec<EventChange>,
+    pub prices: HashMap<Uuid, Vec<PricePoint>>,
+}
+
+impl DummyEventRepository {
+    fn matching(&self, criteria: &EventSearchCriteria) -> Vec<&Event> {
+        let price_dropped = |e: &Event, since: DateTime<Utc>| {
+            self.prices
+                .get(&e.id)
+                .into_iter()
+                .flatten()
+                .filter(|p| p.observed_at <= since)
+                .max_by_key(|p| p.observed_at)
+                .is_some_and(|p| e.min_price < p.min_price)
+        };
+        let title_contains = criteria.title_contains.as_deref().map(str::to_lowercase);
+        self.events
+            .values()
+            .filter(|e| match criteria.time_match {
+                TimeMatch::Within => {
+                    e.start_time >= criteria.start_time && e.end_time <= criteria.end_time
+                }
+                TimeMatch::Overlaps => {
+                    e.start_time <= criteria.end_time
+                        && e.start_time.max(e.end_time) >= criteria.start_time
+                }
+                TimeMatch::StartsIn => {
+                    e.start_time >= criteria.start_time && e.start_time <= criteria.end_time
+                }
+            })
+            .filter(|e| criteria.include_cancelled || e.cancelled_at.is_none())
+            .filter(|e| {
+                criteria
+                    .price_dropped_since
+                    .is_none_or(|since| price_dropped(e, since))
+            })
+            .filter(|e| {
+                title_contains
+                    .as_deref()
+                    .is_none_or(|text| e.title.to_lowercase().contains(text))
+            })
+            .filter(|e| criteria.min_price.is_none_or(|min| e.max_price >= min))
+            .filter(|e| criteria.max_price.is_none_or(|max| e.min_price <= max))
+            .filter(|e| {
+                criteria
+                    .sold_out
+                    .is_none_or(|sold_out| e.sold_out == sold_out)
+            })
+            .filter(|e| criteria.sell_mode.is_none_or(|mode| e.sell_mode == mode))
+            .collect()
+    }
+}
 
 #[allow(unused_variables)]
 impl EventRepository for DummyEventRepository {
     async fn find_all(&self) -> Result<Vec<Event>> {
         todo!("Not yet implemented")
     }
-    async fn find_between(
+    async fn find_matching(
         &self,
-        start_time: DateTime<Utc>,
-        end_time: DateTime<Utc>,
+        criteria: &EventSearchCriteria,
+        sort: &EventSort,
+        page: &EventPage,
         limit: u64,
-        offset: u64,
     ) -> Result<Vec<Event>> {
-        let events: Vec<Event> = self
-            .0
-            .values()
-            .filter(|e| e.start_time >= start_time && e.end_time <= end_time)
-            .skip(offset.try_into()?)
-            .take(limit.try_into()?)
-            .cloned()
-            .collect();
-        Ok(events)
+        let mut events = self.matching(criteria);
+        events.sort_by(|a, b| sort.compare(&EventKey::from(*a), &EventKey::from(*b)));
+
+        let limit: usize = limit.try_into()?;
+        let page: Vec<&Event> = match page {
+            EventPage::Offset(offset) => events
+                .into_iter()
+                .skip((*offset).try_into()?)
+                .take(limit)
+                .collect(),
+            EventPage::After(key) => events
+                .into_iter()
+                .filter(|e| sort.compare(&EventKey::from(*e), key).is_gt())
+                .take(limit)
+                .collect(),
+            EventPage::Before(key) => {
+                events.retain(|e| sort.compare(&EventKey::from(*e), key).is_lt());
+                events.split_off(events.len().saturating_sub(limit))
+            }
+        };
+        Ok(page.into_iter().cloned().collect())
+    }
+    async fn count_matching(
+        &self,
+        criteria: &EventSearchCriteria,
+        exact_limit: u64,
+    ) -> Result<EventCount> {
+        let count = self.matching(criteria).len() as u64;
+        if count > exact_limit {
+            Ok(EventCount::Estimated(count))
+        } else {
+            Ok(EventCount::Exact(count))
+        }
     }
     async fn find_by_id(&self, id: &Uuid) -> Result<Option<Event>> {
-        todo!("Not yet implemented")
+        Ok(self.events.get(id).cloned())
     }
-    async fn find_by_title(&self, title: &str) -> Result<Option<Event>> {
-        todo!("Not yet implemented")
+    /// Time of the latest recorded change of the event, as inserts are not recorded
+    async fn find_updated_at(&self, external_id: &ExternalId) -> Result<Option<DateTime<Utc>>> {
+        Ok(self
+            .events
+            .values()
+            .find(|e| e.external_id.as_ref() == Some(external_id))
+            .and_then(|e| {
+                self.changes
+                    .iter()
+                    .filter(|change| change.event_id == e.id)
+                    .map(|change| change.changed_at)
+                    .max()
+            }))
     }
-    async fn save(&self, e: SaveEventRequest) -> Result<Event> {
-        todo!("Not yet implemented")
+    /// Reports the outcome each request would have, without storing anything
+    async fn bulk_upsert(
+        &self,
+        requests: Vec<SaveEventRequest>,
+        ingestion_run_id: Uuid,
+    ) -> Result<Vec<BulkUpsertResult>> {
+        Ok(requests
+            .iter()
+            .map(|request| {
+                match self
+                    .events
+                    .values()
+                    .find(|e| e.external_id.is_some() && e.external_id == request.external_id)
+                {
+                    Some(event) => BulkUpsertResult {
+                        event_id: event.id,
+                        outcome: UpsertOutcome::Updated,
+                    },
+                    None => BulkUpsertResult {
+                        event_id: Uuid::new_v4(),
+                        outcome: UpsertOutcome::Inserted,
+                    },
+                }
+            })
+            .collect())
     }
-    async fn upsert(&self, entity: Event) -> Result<Event> {
-        todo!("Not yet implemented")
+    /// Reports how many events this run would cancel, without storing anything. As missed runs
+    /// are not kept, every missing event counts as missing for the first time.
+    async fn mark_missing_events(
+        &self,
+        provider: &str,
+        present_external_ids: &[ExternalId],
+        cancel_after_missing_runs: u32,
+        ingestion_run_id: Uuid,
+    ) -> Result<u64> {
+        if cancel_after_missing_runs > 1 {
+            return Ok(0);
+        }
+        let missing = self
+            .events
+            .values()
+            .filter(|e| e.cancelled_at.is_none())
+            .filter_map(|e| e.external_id.as_ref())
+            .filter(|external_id| {
+                external_id.provider == provider && !present_external_ids.contains(external_id)
+            })
+            .count();
+        Ok(missing.try_into()?)
+    }
+    async fn find_zones_by_event_id(&self, event_id: &Uuid) -> Result<Vec<Zone>> {
+        Ok(self.zones.get(event_id).cloned().unwrap_or_default())
+    }
+    async fn find_zones_by_event_ids(
+        &self,
+        event_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<Zone>>> {
+        let zones = event_ids
+            .iter()
+            .filter_map(|id| self.zones.get(id).map(|zones| (*id, zones.clone())))
+            .collect();
+        Ok(zones)
+    }
+    async fn find_prices_by_event_id(
+        &self,
+        event_id: &Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<PricePoint>> {
+        let mut prices: Vec<PricePoint> = self
+            .prices
+            .get(event_id)
+            .into_iter()
+            .flatten()
+            .filter(|p| from.is_none_or(|from| p.observed_at >= from))
+            .filter(|p| to.is_none_or(|to| p.observed_at <= to))
+            .cloned()
+            .collect();
+        prices.sort_by_key(|p| p.observed_at);
+        Ok(prices)
+    }
+    async fn find_changes_by_event_id(&self, event_id: &Uuid) -> Result<Vec<EventChange>> {
+        let mut changes: Vec<EventChange> = self
+            .changes
+            .iter()
+            .filter(|change| change.event_id == *event_id)
+            .cloned()
+            .collect();
+        changes.sort_by_key(|change| change.changed_at);
+        Ok(changes)
     }
 }
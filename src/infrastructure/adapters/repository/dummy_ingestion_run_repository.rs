@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use anyhow::{Result, anyhow};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::application::ports::provider::FeedValidators;
+use crate::application::ports::repository::{DeadLetter, IngestionRunRepository};
+use crate::domain::event::ExternalId;
+use crate::domain::ingestion_run::{IngestionRejection, IngestionRun, IngestionRunState};
+
+#[allow(dead_code)]
+#[derive(Default)]
+pub struct DummyIngestionRunRepository(
+    pub Mutex<HashMap<Uuid, IngestionRun>>,
+    /// Whether another instance of the service holds the ingestion lock
+    pub bool,
+    pub Mutex<Vec<IngestionRejection>>,
+    pub Mutex<Vec<DeadLetter>>,
+);
+
+impl IngestionRunRepository for DummyIngestionRunRepository {
+    type IngestionLock = ();
+
+    async fn try_acquire_ingestion_lock(&self) -> Result<Option<()>> {
+        Ok((!self.1).then_some(()))
+    }
+    async fn save_run(&self, run: IngestionRun) -> Result<IngestionRun> {
+        self.0
+            .lock()
+            .map_err(|_| anyhow!("Ingestion run store is poisoned"))?
+            .insert(run.id, run.clone());
+        Ok(run)
+    }
+    async fn find_run_by_id(&self, id: &Uuid) -> Result<Option<IngestionRun>> {
+        Ok(self
+            .0
+            .lock()
+            .map_err(|_| anyhow!("Ingestion run store is poisoned"))?
+            .get(id)
+            .cloned())
+    }
+    async fn find_running_run(&self) -> Result<Option<IngestionRun>> {
+        Ok(self
+            .0
+            .lock()
+            .map_err(|_| anyhow!("Ingestion run store is poisoned"))?
+            .values()
+            .filter(|run| run.state == IngestionRunState::Running)
+            .max_by_key(|run| run.started_at)
+            .cloned())
+    }
+    async fn fail_running_runs(&self) -> Result<u64> {
+        let mut runs = self
+            .0
+            .lock()
+            .map_err(|_| anyhow!("Ingestion run store is poisoned"))?;
+        let mut count = 0;
+        for run in runs
+            .values_mut()
+            .filter(|run| run.state == IngestionRunState::Running)
+        {
+            run.finish(IngestionRunState::Failed);
+            count += 1;
+        }
+        Ok(count)
+    }
+    async fn find_runs(&self, limit: u64, offset: u64) -> Result<Vec<IngestionRun>> {
+        let mut runs: Vec<IngestionRun> = self
+            .0
+            .lock()
+            .map_err(|_| anyhow!("Ingestion run store is poisoned"))?
+            .values()
+            .cloned()
+            .collect();
+        runs.sort_by_key(|run| std::cmp::Reverse(run.started_at));
+        Ok(runs
+            .into_iter()
+            .skip(offset.try_into()?)
+            .take(limit.try_into()?)
+            .collect())
+    }
+    /// Feeds are always fetched in full, as if never ingested before
+    async fn find_feed_validators(&self, _provider: &str) -> Result<Option<FeedValidators>> {
+        Ok(None)
+    }
+    async fn save_feed_validators(
+        &self,
+        _provider: &str,
+        _validators: &FeedValidators,
+    ) -> Result<()> {
+        Ok(())
+    }
+    async fn save_rejections(&self, rejections: Vec<IngestionRejection>) -> Result<()> {
+        self.2
+            .lock()
+            .map_err(|_| anyhow!("Ingestion rejection store is poisoned"))?
+            .extend(rejections);
+        Ok(())
+    }
+    async fn find_rejections_by_run_id(
+        &self,
+        run_id: &Uuid,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<IngestionRejection>> {
+        Ok(self
+            .2
+            .lock()
+            .map_err(|_| anyhow!("Ingestion rejection store is poisoned"))?
+            .iter()
+            .filter(|rejection| rejection.ingestion_run_id == *run_id)
+            .skip(offset.try_into()?)
+            .take(limit.try_into()?)
+            .cloned()
+            .collect())
+    }
+    async fn save_dead_letters(&self, dead_letters: Vec<DeadLetter>) -> Result<()> {
+        let mut stored = self
+            .3
+            .lock()
+            .map_err(|_| anyhow!("Dead letter store is poisoned"))?;
+        for dead_letter in dead_letters {
+            match stored.iter_mut().find(|pending| {
+                pending.request.external_id.is_some()
+                    && pending.request.external_id == dead_letter.request.external_id
+            }) {
+                Some(pending) => {
+                    pending.ingestion_run_id = dead_letter.ingestion_run_id;
+                    pending.request = dead_letter.request;
+                    pending.error = dead_letter.error;
+                    pending.attempts += dead_letter.attempts;
+                    pending.last_failed_at = dead_letter.last_failed_at;
+                }
+                None => stored.push(dead_letter),
+            }
+        }
+        Ok(())
+    }
+    async fn find_dead_letter_by_id(&self, id: &Uuid) -> Result<Option<DeadLetter>> {
+        Ok(self
+            .3
+            .lock()
+            .map_err(|_| anyhow!("Dead letter store is poisoned"))?
+            .iter()
+            .find(|dead_letter| dead_letter.id == *id)
+            .cloned())
+    }
+    async fn find_dead_letters(&self, limit: u64, offset: u64) -> Result<Vec<DeadLetter>> {
+        let mut dead_letters = self
+            .3
+            .lock()
+            .map_err(|_| anyhow!("Dead letter store is poisoned"))?
+            .clone();
+        dead_letters.sort_by_key(|dead_letter| (dead_letter.created_at, dead_letter.id));
+        Ok(dead_letters
+            .into_iter()
+            .skip(offset.try_into()?)
+            .take(limit.try_into()?)
+            .collect())
+    }
+    async fn record_dead_letter_failure(
+        &self,
+        id: &Uuid,
+        error: &str,
+    ) -> Result<Option<DeadLetter>> {
+        Ok(self
+            .3
+            .lock()
+            .map_err(|_| anyhow!("Dead letter store is poisoned"))?
+            .iter_mut()
+            .find(|dead_letter| dead_letter.id == *id)
+            .map(|dead_letter| {
+                dead_letter.attempts += 1;
+                dead_letter.error = error.to_string();
+                dead_letter.last_failed_at = Utc::now();
+                dead_letter.clone()
+            }))
+    }
+    async fn delete_dead_letter(&self, id: &Uuid) -> Result<()> {
+        self.3
+            .lock()
+            .map_err(|_| anyhow!("Dead letter store is poisoned"))?
+            .retain(|dead_letter| dead_letter.id != *id);
+        Ok(())
+    }
+    async fn delete_dead_letters_by_external_ids(
+        &self,
+        external_ids: &[ExternalId],
+    ) -> Result<u64> {
+        let mut dead_letters = self
+            .3
+            .lock()
+            .map_err(|_| anyhow!("Dead letter store is poisoned"))?;
+        let count = dead_letters.len();
+        dead_letters.retain(|dead_letter| {
+            dead_letter
+                .request
+                .external_id
+                .as_ref()
+                .is_none_or(|external_id| !external_ids.contains(external_id))
+        });
+        Ok((count - dead_letters.len()).try_into()?)
+    }
+}
@@ -1,9 +1,17 @@
+use std::collections::HashMap;
+
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::application::ports::repository::{EventRepository, SaveEventRequest};
-use crate::domain::event::Event;
+use crate::application::ports::repository::{
+    BulkUpsertResult, EventCount, EventPage, EventRepository, EventSearchCriteria, EventSort,
+    SaveEventRequest,
+};
+use crate::domain::event::{Event, ExternalId};
+use crate::domain::event_change::EventChange;
+use crate::domain::price_point::PricePoint;
+use crate::domain::zone::Zone;
 
 #[allow(dead_code)]
 pub struct FailingEventRepository;
@@ -13,25 +21,62 @@ impl EventRepository for FailingEventRepository {
     async fn find_all(&self) -> Result<Vec<Event>> {
         todo!("Not yet implemented")
     }
-    async fn find_between(
+    async fn find_matching(
         &self,
-        start_time: DateTime<Utc>,
-        end_time: DateTime<Utc>,
+        criteria: &EventSearchCriteria,
+        sort: &EventSort,
+        page: &EventPage,
         limit: u64,
-        offset: u64,
     ) -> Result<Vec<Event>> {
-        anyhow::bail!("Failed to find events between datetimes in event database")
+        anyhow::bail!("Failed to find events matching search criteria in event database")
+    }
+    async fn count_matching(
+        &self,
+        criteria: &EventSearchCriteria,
+        exact_limit: u64,
+    ) -> Result<EventCount> {
+        anyhow::bail!("Failed to count events matching search criteria in event database")
     }
     async fn find_by_id(&self, id: &Uuid) -> Result<Option<Event>> {
-        todo!("Not yet implemented")
+        anyhow::bail!("Failed to find event by id in event database")
     }
-    async fn find_by_title(&self, title: &str) -> Result<Option<Event>> {
-        todo!("Not yet implemented")
+    async fn find_updated_at(&self, external_id: &ExternalId) -> Result<Option<DateTime<Utc>>> {
+        anyhow::bail!("Failed to find update time of event by external id in event database")
     }
-    async fn save(&self, e: SaveEventRequest) -> Result<Event> {
-        todo!("Not yet implemented")
+    async fn bulk_upsert(
+        &self,
+        requests: Vec<SaveEventRequest>,
+        ingestion_run_id: Uuid,
+    ) -> Result<Vec<BulkUpsertResult>> {
+        anyhow::bail!("Failed to bulk upsert events in event database")
     }
-    async fn upsert(&self, entity: Event) -> Result<Event> {
-        todo!("Not yet implemented")
+    async fn mark_missing_events(
+        &self,
+        provider: &str,
+        present_external_ids: &[ExternalId],
+        cancel_after_missing_runs: u32,
+        ingestion_run_id: Uuid,
+    ) -> Result<u64> {
+        anyhow::bail!("Failed to mark missing events in event database")
+    }
+    async fn find_zones_by_event_id(&self, event_id: &Uuid) -> Result<Vec<Zone>> {
+        anyhow::bail!("Failed to find zones by event id in event database")
+    }
+    async fn find_zones_by_event_ids(
+        &self,
+        event_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<Zone>>> {
+        anyhow::bail!("Failed to find zones by event ids in event database")
+    }
+    async fn find_prices_by_event_id(
+        &self,
+        event_id: &Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<PricePoint>> {
+        anyhow::bail!("Failed to find prices by event id in event database")
+    }
+    async fn find_changes_by_event_id(&self, event_id: &Uuid) -> Result<Vec<EventChange>> {
+        anyhow::bail!("Failed to find changes by event id in event database")
     }
 }
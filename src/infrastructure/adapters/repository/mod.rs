@@ -1,8 +1,13 @@
 mod dummy_event_repository;
+mod dummy_ingestion_run_repository;
 mod failing_event_repository;
 mod postgres_event_repository;
+mod postgres_ingestion_run_repository;
 #[cfg(test)]
 pub use dummy_event_repository::DummyEventRepository;
 #[cfg(test)]
+pub use dummy_ingestion_run_repository::DummyIngestionRunRepository;
+#[cfg(test)]
 pub use failing_event_repository::FailingEventRepository;
 pub use postgres_event_repository::PostgresEventRepository;
+pub use postgres_ingestion_run_repository::PostgresIngestionRunRepository;
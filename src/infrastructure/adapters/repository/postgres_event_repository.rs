@@ -1,10 +1,21 @@
-use anyhow::{Context, Result, anyhow};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use chrono_tz::Tz;
+use sqlx::{PgPool, Postgres, QueryBuilder, Transaction};
 use uuid::Uuid;
 
-use crate::application::ports::repository::{EventRepository, SaveEventRequest};
-use crate::domain::event::Event;
+use crate::application::ports::repository::{
+    BulkUpsertResult, EventCount, EventKey, EventPage, EventRepository, EventSearchCriteria,
+    EventSort, EventSortField, SaveEventRequest, TimeMatch, UpsertOutcome,
+};
+use crate::domain::event::{Event, ExternalId, SellMode};
+use crate::domain::event_change::{EventChange, FieldChange, diff_events};
+use crate::domain::money::{Currency, Money};
+use crate::domain::price_point::PricePoint;
+use crate::domain::zone::Zone;
 
 pub struct PostgresEventRepository(PgPool);
 
@@ -19,7 +30,7 @@ impl EventRepository for PostgresEventRepository {
         let query = sqlx::query_as!(
             PostgresEvent,
             r#"
-            SELECT id, title, start_time, end_time, min_price as min_price_in_lowest_denomination, max_price as max_price_in_lowest_denomination
+            SELECT id, provider, base_plan_id, plan_id, title, start_time, end_time, time_zone, min_price as min_price_in_lowest_denomination, max_price as max_price_in_lowest_denomination, currency, sell_mode, organizer_company_id, sell_from, sell_to, sold_out, cancelled_at
             FROM events
         "#
         );
@@ -28,50 +39,111 @@ impl EventRepository for PostgresEventRepository {
             .fetch_all(&self.0)
             .await
             .context("Failed to find all events in event database")
-            .map(postgres_events_into_domain_events)
+            .and_then(postgres_events_into_domain_events)
     }
 
-    async fn find_between(
+    async fn find_matching(
         &self,
-        start_time: DateTime<Utc>,
-        end_time: DateTime<Utc>,
+        criteria: &EventSearchCriteria,
+        sort: &EventSort,
+        page: &EventPage,
         limit: u64,
-        offset: u64,
     ) -> Result<Vec<Event>> {
         let signed_limit: i64 = limit
             .try_into()
             .context("Failed to cast query limit from u64 to i64")?;
-        let signed_offset: i64 = offset
-            .try_into()
-            .context("Failed to cast query offset from u64 to i64")?;
 
-        let query = sqlx::query_as!(
-            PostgresEvent,
-            r#"
-            SELECT id, title, start_time, end_time, min_price as min_price_in_lowest_denomination, max_price as max_price_in_lowest_denomination
-            FROM events
-            WHERE start_time >= $1 AND end_time <= $2
-            LIMIT $3
-            OFFSET $4
-        "#,
-            start_time,
-            end_time,
-            signed_limit,
-            signed_offset,
+        let mut query = QueryBuilder::new(
+            "SELECT id, provider, base_plan_id, plan_id, title, start_time, end_time, time_zone, min_price as min_price_in_lowest_denomination, max_price as max_price_in_lowest_denomination, currency, sell_mode, organizer_company_id, sell_from, sell_to, sold_out, cancelled_at FROM events",
         );
+        push_search_conditions(&mut query, criteria);
+        match page {
+            EventPage::Offset(offset) => {
+                let signed_offset: i64 = (*offset)
+                    .try_into()
+                    .context("Failed to cast query offset from u64 to i64")?;
+                push_sort_order(&mut query, sort, false);
+                query
+                    .push(" LIMIT ")
+                    .push_bind(signed_limit)
+                    .push(" OFFSET ")
+                    .push_bind(signed_offset);
+            }
+            EventPage::After(key) => {
+                push_keyset_condition(&mut query, sort, key, false);
+                push_sort_order(&mut query, sort, false);
+                query.push(" LIMIT ").push_bind(signed_limit);
+            }
+            // Walks the sort order backwards from the key, then restores it below
+            EventPage::Before(key) => {
+                push_keyset_condition(&mut query, sort, key, true);
+                push_sort_order(&mut query, sort, true);
+                query.push(" LIMIT ").push_bind(signed_limit);
+            }
+        }
+
+        let mut events = query
+            .build_query_as::<PostgresEvent>()
+            .fetch_all(&self.0)
+            .await
+            .context("Failed to find events matching search criteria in event database")
+            .and_then(postgres_events_into_domain_events)?;
+        if let EventPage::Before(_) = page {
+            events.reverse();
+        }
 
+        Ok(events)
+    }
+
+    async fn count_matching(
+        &self,
+        criteria: &EventSearchCriteria,
+        exact_limit: u64,
+    ) -> Result<EventCount> {
+        let signed_limit: i64 = exact_limit
+            .saturating_add(1)
+            .try_into()
+            .context("Failed to cast exact count limit from u64 to i64")?;
+
+        // Counting stops one past the limit, so that large counts cost no more than that
+        let mut query = QueryBuilder::new("SELECT COUNT(*) FROM (SELECT 1 FROM events");
+        push_search_conditions(&mut query, criteria);
         query
+            .push(" LIMIT ")
+            .push_bind(signed_limit)
+            .push(") AS matching");
+        let count: i64 = query
+            .build_query_scalar()
+            .fetch_one(&self.0)
+            .await
+            .context("Failed to count events matching search criteria in event database")?;
+        let count: u64 = count
+            .try_into()
+            .context("Failed to cast event count from i64 to u64")?;
+        if count <= exact_limit {
+            return Ok(EventCount::Exact(count));
+        }
+
+        let mut explain = QueryBuilder::new("EXPLAIN SELECT 1 FROM events");
+        push_search_conditions(&mut explain, criteria);
+        let plan: Vec<String> = explain
+            .build_query_scalar()
             .fetch_all(&self.0)
             .await
-            .context("Failed to find events between datetimes in event database")
-            .map(postgres_events_into_domain_events)
+            .context("Failed to estimate events matching search criteria in event database")?;
+        let estimate = plan
+            .first()
+            .and_then(|line| planned_rows(line))
+            .unwrap_or_default();
+
+        Ok(EventCount::Estimated(estimate.max(count)))
     }
 
     async fn find_by_id(&self, id: &Uuid) -> Result<Option<Event>> {
         let query = sqlx::query_as!(
             PostgresEvent,
             r#"
-                SELECT id, title, start_time, end_time, min_price as min_price_in_lowest_denomination, max_price as max_price_in_lowest_denomination
+                SELECT id, provider, base_plan_id, plan_id, title, start_time, end_time, time_zone, min_price as min_price_in_lowest_denomination, max_price as max_price_in_lowest_denomination, currency, sell_mode, organizer_company_id, sell_from, sell_to, sold_out, cancelled_at
                 FROM events
                 WHERE id = $1
             "#,
@@ -82,133 +154,849 @@ impl EventRepository for PostgresEventRepository {
             .fetch_optional(&self.0)
             .await
             .context("Failed to find event by id in event database")
-            .map(|optional| optional.map(PostgresEvent::into))
+            .and_then(|optional| optional.map(Event::try_from).transpose())
     }
 
-    async fn find_by_title(&self, title: &str) -> Result<Option<Event>> {
-        let query = sqlx::query_as!(
-            PostgresEvent,
+    async fn find_updated_at(&self, external_id: &ExternalId) -> Result<Option<DateTime<Utc>>> {
+        let query = sqlx::query_scalar!(
             r#"
-            SELECT id, title, start_time, end_time, min_price as min_price_in_lowest_denomination, max_price as max_price_in_lowest_denomination
-            FROM events
-            WHERE title = $1
-        "#,
-            title
+                SELECT updated_at
+                FROM events
+                WHERE provider = $1 AND base_plan_id = $2 AND plan_id = $3
+            "#,
+            external_id.provider,
+            external_id.base_plan_id,
+            external_id.plan_id,
         );
 
         query
             .fetch_optional(&self.0)
             .await
-            .context("Failed to find event by title in event database")
-            .map(|optional| optional.map(PostgresEvent::into))
+            .context("Failed to find update time of event by external id in event database")
     }
 
-    async fn save(&self, e: SaveEventRequest) -> Result<Event> {
-        let event = PostgresEvent::from(e);
-        let query = sqlx::query!(
+    async fn bulk_upsert(
+        &self,
+        requests: Vec<SaveEventRequest>,
+        ingestion_run_id: Uuid,
+    ) -> Result<Vec<BulkUpsertResult>> {
+        if requests.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut tx = self
+            .0
+            .begin()
+            .await
+            .context("Failed to start transaction in event database")?;
+
+        // 1. Lock the stored version of every requested event, along with its zones
+        let mut providers = Vec::with_capacity(requests.len());
+        let mut base_plan_ids = Vec::with_capacity(requests.len());
+        let mut plan_ids = Vec::with_capacity(requests.len());
+        for external_id in requests.iter().filter_map(|r| r.external_id.as_ref()) {
+            providers.push(external_id.provider.clone());
+            base_plan_ids.push(external_id.base_plan_id.clone());
+            plan_ids.push(external_id.plan_id.clone());
+        }
+        let stored_events = sqlx::query_as!(
+            PostgresEvent,
             r#"
-                INSERT INTO events (id, title, start_time, end_time, min_price, max_price)
-                VALUES ($1, $2, $3, $4, $5, $6)
+                SELECT id, provider, base_plan_id, plan_id, title, start_time, end_time, time_zone, min_price as min_price_in_lowest_denomination, max_price as max_price_in_lowest_denomination, currency, sell_mode, organizer_company_id, sell_from, sell_to, sold_out, cancelled_at
+                FROM events
+                WHERE (provider, base_plan_id, plan_id) IN (SELECT * FROM UNNEST($1::TEXT[], $2::TEXT[], $3::TEXT[]))
+                FOR UPDATE
+            "#,
+            &providers,
+            &base_plan_ids,
+            &plan_ids,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to lock event records by external id in event database")?;
+        let stored_event_ids: Vec<Uuid> = stored_events.iter().map(|e| e.id).collect();
+        let mut stored_zones: HashMap<Uuid, Vec<Zone>> = HashMap::new();
+        for zone in sqlx::query_as!(
+            PostgresZone,
+            r#"
+                SELECT event_id, zone_id, name, price as price_in_lowest_denomination, currency, capacity, numbered
+                FROM event_zones
+                WHERE event_id = ANY($1)
+                ORDER BY event_id, position
             "#,
-            event.id,
-            event.title,
-            event.start_time,
-            event.end_time,
-            event.min_price_in_lowest_denomination,
-            event.max_price_in_lowest_denomination,
+            &stored_event_ids,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to find zones by event ids in event database")?
+        {
+            stored_zones
+                .entry(zone.event_id)
+                .or_default()
+                .push(zone.try_into()?);
+        }
+        let stored_events: HashMap<(String, String, String), PostgresEvent> = stored_events
+            .into_iter()
+            .filter_map(|e| match (&e.provider, &e.base_plan_id, &e.plan_id) {
+                (Some(provider), Some(base_plan_id), Some(plan_id)) => {
+                    Some(((provider.clone(), base_plan_id.clone(), plan_id.clone()), e))
+                }
+                _ => None,
+            })
+            .collect();
+
+        // 2. Compare each request with its stored version to decide what has to be written
+        let mut results = Vec::with_capacity(requests.len());
+        let mut events_to_write = Vec::new();
+        let mut zones_to_write = Vec::new();
+        let mut changes = Vec::new();
+        for request in requests {
+            let zones = request.zones.clone();
+            let event = PostgresEvent::from(request);
+            let stored_event = match (&event.provider, &event.base_plan_id, &event.plan_id) {
+                (Some(provider), Some(base_plan_id), Some(plan_id)) => {
+                    stored_events.get(&(provider.clone(), base_plan_id.clone(), plan_id.clone()))
+                }
+                _ => None,
+            };
+
+            let (event_id, outcome) = match stored_event {
+                None => (event.id, UpsertOutcome::Inserted),
+                Some(stored_event) => {
+                    let updated_event = PostgresEvent {
+                        id: stored_event.id,
+                        ..event.clone()
+                    };
+                    let stored_event_zones = stored_zones
+                        .get(&stored_event.id)
+                        .map(Vec::as_slice)
+                        .unwrap_or_default();
+                    if updated_event == *stored_event && stored_event_zones == zones.as_slice() {
+                        (stored_event.id, UpsertOutcome::Unchanged)
+                    } else {
+                        let field_changes = diff_events(
+                            &Event::try_from(stored_event.clone())?,
+                            stored_event_zones,
+                            &Event::try_from(updated_event)?,
+                            &zones,
+                        );
+                        changes.extend(
+                            field_changes
+                                .into_iter()
+                                .map(|change| (stored_event.id, change)),
+                        );
+                        (stored_event.id, UpsertOutcome::Updated)
+                    }
+                }
+            };
+            if outcome != UpsertOutcome::Unchanged {
+                events_to_write.push(PostgresEvent {
+                    id: event_id,
+                    ..event
+                });
+                zones_to_write.push((event_id, zones));
+            }
+            results.push(BulkUpsertResult { event_id, outcome });
+        }
+
+        // 3. Write new and changed events, replacing their zones
+        if !events_to_write.is_empty() {
+            upsert_events_by_external_id(&mut tx, &events_to_write).await?;
+            let written_event_ids: Vec<Uuid> = events_to_write.iter().map(|e| e.id).collect();
+            sqlx::query!(
+                "DELETE FROM event_zones WHERE event_id = ANY($1)",
+                &written_event_ids
+            )
+            .execute(&mut *tx)
+            .await
+            .context("Failed to delete event zone records in event database")?;
+            insert_zones(&mut tx, zones_to_write).await?;
+            insert_changes(&mut tx, changes, ingestion_run_id).await?;
+            record_price_changes(&mut tx, &written_event_ids).await?;
+        }
+        // Events present in the feed again start over their count of missed runs
+        sqlx::query!(
+            r#"
+                UPDATE events
+                SET missing_runs = 0
+                WHERE missing_runs > 0
+                    AND (provider, base_plan_id, plan_id) IN (SELECT * FROM UNNEST($1::TEXT[], $2::TEXT[], $3::TEXT[]))
+            "#,
+            &providers,
+            &base_plan_ids,
+            &plan_ids,
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to reset missed runs of event records in event database")?;
+        tx.commit()
+            .await
+            .context("Failed to commit event records in event database")?;
+
+        Ok(results)
+    }
+
+    async fn mark_missing_events(
+        &self,
+        provider: &str,
+        present_external_ids: &[ExternalId],
+        cancel_after_missing_runs: u32,
+        ingestion_run_id: Uuid,
+    ) -> Result<u64> {
+        let (base_plan_ids, plan_ids): (Vec<String>, Vec<String>) = present_external_ids
+            .iter()
+            .map(|e| (e.base_plan_id.clone(), e.plan_id.clone()))
+            .unzip();
+        let cancel_after_missing_runs: i32 = cancel_after_missing_runs
+            .max(1)
+            .try_into()
+            .context("Failed to cast missing runs threshold from u32 to i32")?;
+
+        // Cancellations are recorded in the history of the events along with the update
+        let query = sqlx::query_scalar!(
+            r#"
+                WITH marked AS (
+                    UPDATE events e
+                    SET missing_runs = e.missing_runs + 1,
+                        cancelled_at = CASE WHEN e.missing_runs + 1 >= $4 THEN NOW() END,
+                        updated_at = CASE WHEN e.missing_runs + 1 >= $4 THEN NOW() ELSE e.updated_at END
+                    WHERE e.provider = $1
+                        AND e.cancelled_at IS NULL
+                        AND NOT EXISTS (
+                            SELECT 1 FROM UNNEST($2::TEXT[], $3::TEXT[]) AS present(base_plan_id, plan_id)
+                            WHERE present.base_plan_id = e.base_plan_id AND present.plan_id = e.plan_id
+                        )
+                    RETURNING e.id, e.cancelled_at
+                ), recorded AS (
+                    INSERT INTO event_changes (event_id, ingestion_run_id, field, old_value, new_value, changed_at)
+                    SELECT id, $5, 'cancelled_at', NULL, to_char(cancelled_at AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS"Z"'), cancelled_at
+                    FROM marked
+                    WHERE cancelled_at IS NOT NULL
+                )
+                SELECT cancelled_at IS NOT NULL AS "cancelled!"
+                FROM marked
+            "#,
+            provider,
+            &base_plan_ids,
+            &plan_ids,
+            cancel_after_missing_runs,
+            ingestion_run_id,
+        );
+
+        let cancelled = query
+            .fetch_all(&self.0)
+            .await
+            .context("Failed to mark missing event records in event database")?;
+
+        Ok(cancelled.into_iter().filter(|cancelled| *cancelled).count() as u64)
+    }
+
+    async fn find_zones_by_event_id(&self, event_id: &Uuid) -> Result<Vec<Zone>> {
+        let query = sqlx::query_as!(
+            PostgresZone,
+            r#"
+                SELECT event_id, zone_id, name, price as price_in_lowest_denomination, currency, capacity, numbered
+                FROM event_zones
+                WHERE event_id = $1
+                ORDER BY position
+            "#,
+            event_id,
         );
 
         query
-            .execute(&self.0)
+            .fetch_all(&self.0)
             .await
-            .context("Failed to insert event record in event database")?;
+            .context("Failed to find zones by event id in event database")?
+            .into_iter()
+            .map(Zone::try_from)
+            .collect()
+    }
 
-        self.find_by_id(&event.id)
+    async fn find_zones_by_event_ids(
+        &self,
+        event_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<Zone>>> {
+        let query = sqlx::query_as!(
+            PostgresZone,
+            r#"
+                SELECT event_id, zone_id, name, price as price_in_lowest_denomination, currency, capacity, numbered
+                FROM event_zones
+                WHERE event_id = ANY($1)
+                ORDER BY event_id, position
+            "#,
+            event_ids,
+        );
+
+        let mut zones_by_event_id: HashMap<Uuid, Vec<Zone>> = HashMap::new();
+        for zone in query
+            .fetch_all(&self.0)
             .await
-            .context("Failed to save event in event database")?
-            .ok_or(anyhow!("Could not find saved entity by its id"))
-            .context("Failed to save event in event database")
+            .context("Failed to find zones by event ids in event database")?
+        {
+            zones_by_event_id
+                .entry(zone.event_id)
+                .or_default()
+                .push(zone.try_into()?);
+        }
+
+        Ok(zones_by_event_id)
     }
 
-    async fn upsert(&self, entity: Event) -> Result<Event> {
-        let event = PostgresEvent::from(entity);
-        let query = sqlx::query!(
+    async fn find_prices_by_event_id(
+        &self,
+        event_id: &Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<PricePoint>> {
+        let query = sqlx::query_as!(
+            PostgresPricePoint,
             r#"
-                INSERT INTO events (id, title, start_time, end_time, min_price, max_price)
-                VALUES ($1, $2, $3, $4, $5, $6)
-                ON CONFLICT (id) DO UPDATE
-                SET title = $2, start_time = $3, end_time = $4, min_price = $5, max_price = $6
+                SELECT min_price as min_price_in_lowest_denomination, max_price as max_price_in_lowest_denomination, currency, observed_at
+                FROM event_prices
+                WHERE event_id = $1
+                    AND ($2::TIMESTAMPTZ IS NULL OR observed_at >= $2)
+                    AND ($3::TIMESTAMPTZ IS NULL OR observed_at <= $3)
+                ORDER BY observed_at
             "#,
-            event.id,
-            event.title,
-            event.start_time,
-            event.end_time,
-            event.min_price_in_lowest_denomination,
-            event.max_price_in_lowest_denomination,
+            event_id,
+            from,
+            to,
         );
 
         query
-            .execute(&self.0)
+            .fetch_all(&self.0)
             .await
-            .context("Failed to update event record in event database")?;
+            .context("Failed to find prices by event id in event database")?
+            .into_iter()
+            .map(PricePoint::try_from)
+            .collect()
+    }
+
+    async fn find_changes_by_event_id(&self, event_id: &Uuid) -> Result<Vec<EventChange>> {
+        let query = sqlx::query_as!(
+            EventChange,
+            r#"
+                SELECT event_id, ingestion_run_id, field, old_value, new_value, changed_at
+                FROM event_changes
+                WHERE event_id = $1
+                ORDER BY changed_at, id
+            "#,
+            event_id,
+        );
 
-        self.find_by_id(&event.id)
+        query
+            .fetch_all(&self.0)
             .await
-            .context("Failed to upsert event in event database")?
-            .ok_or(anyhow!("Could not find upserted entity by its id"))
-            .context("Failed to upsert event in event database")
+            .context("Failed to find changes by event id in event database")
     }
 }
 
-#[derive(sqlx::FromRow, Clone)]
+#[derive(sqlx::FromRow, Clone, PartialEq)]
 struct PostgresEvent {
     id: Uuid,
+    provider: Option<String>,
+    base_plan_id: Option<String>,
+    plan_id: Option<String>,
     title: String,
     start_time: DateTime<Utc>,
     end_time: DateTime<Utc>,
-    min_price_in_lowest_denomination: i32,
-    max_price_in_lowest_denomination: i32,
+    time_zone: String,
+    min_price_in_lowest_denomination: i64,
+    max_price_in_lowest_denomination: i64,
+    currency: String,
+    sell_mode: String,
+    organizer_company_id: Option<String>,
+    sell_from: DateTime<Utc>,
+    sell_to: DateTime<Utc>,
+    sold_out: bool,
+    cancelled_at: Option<DateTime<Utc>>,
 }
 
-impl From<PostgresEvent> for Event {
-    fn from(value: PostgresEvent) -> Self {
-        Event {
-            id: value.id,
-            title: value.title,
-            start_time: value.start_time,
-            end_time: value.end_time,
-            min_price: value.min_price_in_lowest_denomination as f64 / 100.0,
-            max_price: value.max_price_in_lowest_denomination as f64 / 100.0,
-        }
-    }
-}
+impl TryFrom<PostgresEvent> for Event {
+    type Error = anyhow::Error;
 
-impl From<Event> for PostgresEvent {
-    fn from(value: Event) -> Self {
-        Self {
+    fn try_from(value: PostgresEvent) -> Result<Self> {
+        let external_id = match (value.provider, value.base_plan_id, value.plan_id) {
+            (Some(provider), Some(base_plan_id), Some(plan_id)) => Some(ExternalId {
+                provider,
+                base_plan_id,
+                plan_id,
+            }),
+            _ => None,
+        };
+
+        let currency = Currency::from_str(&value.currency)
+            .context("Failed to map event record from event database")?;
+
+        Ok(Event {
             id: value.id,
+            external_id,
             title: value.title,
             start_time: value.start_time,
             end_time: value.end_time,
-            min_price_in_lowest_denomination: (value.min_price * 100.0) as i32,
-            max_price_in_lowest_denomination: (value.max_price * 100.0) as i32,
-        }
+            time_zone: Tz::from_str(&value.time_zone)
+                .context("Failed to map event record from event database")?,
+            min_price: Money::new(value.min_price_in_lowest_denomination, currency),
+            max_price: Money::new(value.max_price_in_lowest_denomination, currency),
+            sell_mode: SellMode::from_str(&value.sell_mode)
+                .context("Failed to map event record from event database")?,
+            organizer_company_id: value.organizer_company_id,
+            sell_from: value.sell_from,
+            sell_to: value.sell_to,
+            sold_out: value.sold_out,
+            cancelled_at: value.cancelled_at,
+        })
     }
 }
 
 impl From<SaveEventRequest> for PostgresEvent {
     fn from(value: SaveEventRequest) -> Self {
+        let (provider, base_plan_id, plan_id) = external_id_into_columns(value.external_id);
+
         Self {
             id: Uuid::new_v4(),
+            provider,
+            base_plan_id,
+            plan_id,
             title: value.title,
             start_time: value.start_time,
             end_time: value.end_time,
-            min_price_in_lowest_denomination: (value.min_price * 100.0) as i32,
-            max_price_in_lowest_denomination: (value.max_price * 100.0) as i32,
+            time_zone: value.time_zone.to_string(),
+            min_price_in_lowest_denomination: value.min_price.amount_in_minor_units(),
+            max_price_in_lowest_denomination: value.max_price.amount_in_minor_units(),
+            currency: value.min_price.currency().to_string(),
+            sell_mode: value.sell_mode.to_string(),
+            organizer_company_id: value.organizer_company_id,
+            sell_from: value.sell_from,
+            sell_to: value.sell_to,
+            sold_out: value.sold_out,
+            cancelled_at: None,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PostgresZone {
+    event_id: Uuid,
+    zone_id: String,
+    name: String,
+    price_in_lowest_denomination: i64,
+    currency: String,
+    capacity: i32,
+    numbered: bool,
+}
+
+impl TryFrom<PostgresZone> for Zone {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PostgresZone) -> Result<Self> {
+        Ok(Zone {
+            zone_id: value.zone_id,
+            name: value.name,
+            price: Money::new(
+                value.price_in_lowest_denomination,
+                Currency::from_str(&value.currency)
+                    .context("Failed to map zone record from event database")?,
+            ),
+            capacity: value
+                .capacity
+                .try_into()
+                .context("Failed to map zone record from event database")?,
+            numbered: value.numbered,
+        })
+    }
+}
+
+async fn insert_zones(
+    tx: &mut Transaction<'_, Postgres>,
+    zones_by_event_id: Vec<(Uuid, Vec<Zone>)>,
+) -> Result<()> {
+    let zone_count = zones_by_event_id.iter().map(|(_, zones)| zones.len()).sum();
+    let mut event_ids = Vec::with_capacity(zone_count);
+    let mut positions = Vec::with_capacity(zone_count);
+    let mut zone_ids = Vec::with_capacity(zone_count);
+    let mut names = Vec::with_capacity(zone_count);
+    let mut prices = Vec::with_capacity(zone_count);
+    let mut currencies = Vec::with_capacity(zone_count);
+    let mut capacities = Vec::with_capacity(zone_count);
+    let mut numbered = Vec::with_capacity(zone_count);
+    for (event_id, zones) in zones_by_event_id {
+        for (position, zone) in zones.into_iter().enumerate() {
+            event_ids.push(event_id);
+            positions.push(i32::try_from(position).context("Failed to cast zone position to i32")?);
+            zone_ids.push(zone.zone_id);
+            names.push(zone.name);
+            prices.push(zone.price.amount_in_minor_units());
+            currencies.push(zone.price.currency().to_string());
+            capacities
+                .push(i32::try_from(zone.capacity).context("Failed to cast zone capacity to i32")?);
+            numbered.push(zone.numbered);
+        }
+    }
+
+    let query = sqlx::query!(
+        r#"
+            INSERT INTO event_zones (event_id, position, zone_id, name, price, currency, capacity, numbered)
+            SELECT * FROM UNNEST($1::UUID[], $2::INTEGER[], $3::TEXT[], $4::TEXT[], $5::BIGINT[], $6::TEXT[], $7::INTEGER[], $8::BOOLEAN[])
+        "#,
+        &event_ids,
+        &positions,
+        &zone_ids,
+        &names,
+        &prices,
+        &currencies,
+        &capacities,
+        &numbered,
+    );
+
+    query
+        .execute(&mut **tx)
+        .await
+        .context("Failed to insert event zone records in event database")?;
+
+    Ok(())
+}
+
+#[derive(sqlx::FromRow)]
+struct PostgresPricePoint {
+    min_price_in_lowest_denomination: i64,
+    max_price_in_lowest_denomination: i64,
+    currency: String,
+    observed_at: DateTime<Utc>,
+}
+
+impl TryFrom<PostgresPricePoint> for PricePoint {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PostgresPricePoint) -> Result<Self> {
+        let currency = Currency::from_str(&value.currency)
+            .context("Failed to map price record from event database")?;
+
+        Ok(PricePoint {
+            min_price: Money::new(value.min_price_in_lowest_denomination, currency),
+            max_price: Money::new(value.max_price_in_lowest_denomination, currency),
+            observed_at: value.observed_at,
+        })
+    }
+}
+
+/// Appends the current prices of the given events to their price history, unless they are the
+/// last ones recorded
+async fn record_price_changes(
+    tx: &mut Transaction<'_, Postgres>,
+    event_ids: &[Uuid],
+) -> Result<()> {
+    let query = sqlx::query!(
+        r#"
+            INSERT INTO event_prices (event_id, observed_at, min_price, max_price, currency)
+            SELECT e.id, NOW(), e.min_price, e.max_price, e.currency
+            FROM events e
+            LEFT JOIN LATERAL (
+                SELECT p.min_price, p.max_price, p.currency
+                FROM event_prices p
+                WHERE p.event_id = e.id
+                ORDER BY p.observed_at DESC
+                LIMIT 1
+            ) latest ON TRUE
+            WHERE e.id = ANY($1)
+                AND (latest.min_price, latest.max_price, latest.currency) IS DISTINCT FROM (e.min_price, e.max_price, e.currency)
+        "#,
+        event_ids,
+    );
+
+    query
+        .execute(&mut **tx)
+        .await
+        .context("Failed to record event prices in event database")?;
+
+    Ok(())
+}
+
+async fn insert_changes(
+    tx: &mut Transaction<'_, Postgres>,
+    changes: Vec<(Uuid, FieldChange)>,
+    ingestion_run_id: Uuid,
+) -> Result<()> {
+    let mut event_ids = Vec::with_capacity(changes.len());
+    let mut fields = Vec::with_capacity(changes.len());
+    let mut old_values = Vec::with_capacity(changes.len());
+    let mut new_values = Vec::with_capacity(changes.len());
+    for (event_id, change) in changes {
+        event_ids.push(event_id);
+        fields.push(change.field.to_string());
+        old_values.push(change.old_value);
+        new_values.push(change.new_value);
+    }
+
+    let query = sqlx::query!(
+        r#"
+            INSERT INTO event_changes (event_id, ingestion_run_id, field, old_value, new_value, changed_at)
+            SELECT event_id, $2, field, old_value, new_value, NOW()
+            FROM UNNEST($1::UUID[], $3::TEXT[], $4::TEXT[], $5::TEXT[]) AS change(event_id, field, old_value, new_value)
+        "#,
+        &event_ids,
+        ingestion_run_id,
+        &fields,
+        &old_values as &[Option<String>],
+        &new_values as &[Option<String>],
+    );
+
+    query
+        .execute(&mut **tx)
+        .await
+        .context("Failed to insert event change records in event database")?;
+
+    Ok(())
+}
+
+/// Writes every event with a single statement, updating the rows whose external id already exists
+async fn upsert_events_by_external_id(
+    tx: &mut Transaction<'_, Postgres>,
+    events: &[PostgresEvent],
+) -> Result<()> {
+    let mut ids = Vec::with_capacity(events.len());
+    let mut providers = Vec::with_capacity(events.len());
+    let mut base_plan_ids = Vec::with_capacity(events.len());
+    let mut plan_ids = Vec::with_capacity(events.len());
+    let mut titles = Vec::with_capacity(events.len());
+    let mut start_times = Vec::with_capacity(events.len());
+    let mut end_times = Vec::with_capacity(events.len());
+    let mut time_zones = Vec::with_capacity(events.len());
+    let mut min_prices = Vec::with_capacity(events.len());
+    let mut max_prices = Vec::with_capacity(events.len());
+    let mut currencies = Vec::with_capacity(events.len());
+    let mut sell_modes = Vec::with_capacity(events.len());
+    let mut organizer_company_ids = Vec::with_capacity(events.len());
+    let mut sell_froms = Vec::with_capacity(events.len());
+    let mut sell_tos = Vec::with_capacity(events.len());
+    let mut sold_outs = Vec::with_capacity(events.len());
+    let mut cancelled_ats = Vec::with_capacity(events.len());
+    for event in events.iter().cloned() {
+        ids.push(event.id);
+        providers.push(event.provider);
+        base_plan_ids.push(event.base_plan_id);
+        plan_ids.push(event.plan_id);
+        titles.push(event.title);
+        start_times.push(event.start_time);
+        end_times.push(event.end_time);
+        time_zones.push(event.time_zone);
+        min_prices.push(event.min_price_in_lowest_denomination);
+        max_prices.push(event.max_price_in_lowest_denomination);
+        currencies.push(event.currency);
+        sell_modes.push(event.sell_mode);
+        organizer_company_ids.push(event.organizer_company_id);
+        sell_froms.push(event.sell_from);
+        sell_tos.push(event.sell_to);
+        sold_outs.push(event.sold_out);
+        cancelled_ats.push(event.cancelled_at);
+    }
+
+    let query = sqlx::query!(
+        r#"
+            INSERT INTO events (id, provider, base_plan_id, plan_id, title, start_time, end_time, time_zone, min_price, max_price, currency, sell_mode, organizer_company_id, sell_from, sell_to, sold_out, cancelled_at)
+            SELECT * FROM UNNEST($1::UUID[], $2::TEXT[], $3::TEXT[], $4::TEXT[], $5::TEXT[], $6::TIMESTAMPTZ[], $7::TIMESTAMPTZ[], $8::TEXT[], $9::BIGINT[], $10::BIGINT[], $11::TEXT[], $12::TEXT[], $13::TEXT[], $14::TIMESTAMPTZ[], $15::TIMESTAMPTZ[], $16::BOOLEAN[], $17::TIMESTAMPTZ[])
+            ON CONFLICT ON CONSTRAINT events_external_id_key DO UPDATE
+            SET title = EXCLUDED.title, start_time = EXCLUDED.start_time, end_time = EXCLUDED.end_time, time_zone = EXCLUDED.time_zone,
+                min_price = EXCLUDED.min_price, max_price = EXCLUDED.max_price, currency = EXCLUDED.currency, sell_mode = EXCLUDED.sell_mode,
+                organizer_company_id = EXCLUDED.organizer_company_id, sell_from = EXCLUDED.sell_from, sell_to = EXCLUDED.sell_to, sold_out = EXCLUDED.sold_out,
+                cancelled_at = EXCLUDED.cancelled_at, updated_at = NOW()
+        "#,
+        &ids,
+        &providers as &[Option<String>],
+        &base_plan_ids as &[Option<String>],
+        &plan_ids as &[Option<String>],
+        &titles,
+        &start_times,
+        &end_times,
+        &time_zones,
+        &min_prices,
+        &max_prices,
+        &currencies,
+        &sell_modes,
+        &organizer_company_ids as &[Option<String>],
+        &sell_froms,
+        &sell_tos,
+        &sold_outs,
+        &cancelled_ats as &[Option<DateTime<Utc>>],
+    );
+
+    query
+        .execute(&mut **tx)
+        .await
+        .context("Failed to upsert event records in event database")?;
+
+    Ok(())
+}
+
+fn external_id_into_columns(
+    external_id: Option<ExternalId>,
+) -> (Option<String>, Option<String>, Option<String>) {
+    match external_id {
+        Some(ExternalId {
+            provider,
+            base_plan_id,
+            plan_id,
+        }) => (Some(provider), Some(base_plan_id), Some(plan_id)),
+        None => (None, None, None),
+    }
+}
+
+/// Appends the `WHERE` clause selecting the events that meet the criteria
+fn push_search_conditions(query: &mut QueryBuilder<'_, Postgres>, criteria: &EventSearchCriteria) {
+    match criteria.time_match {
+        TimeMatch::Within => query
+            .push(" WHERE start_time >= ")
+            .push_bind(criteria.start_time)
+            .push(" AND end_time <= ")
+            .push_bind(criteria.end_time),
+        TimeMatch::Overlaps => query
+            .push(" WHERE time_range && tstzrange(")
+            .push_bind(criteria.start_time)
+            .push(", ")
+            .push_bind(criteria.end_time)
+            .push(", '[]')"),
+        TimeMatch::StartsIn => query
+            .push(" WHERE start_time >= ")
+            .push_bind(criteria.start_time)
+            .push(" AND start_time <= ")
+            .push_bind(criteria.end_time),
+    };
+    if !criteria.include_cancelled {
+        query.push(" AND cancelled_at IS NULL");
+    }
+    if let Some(since) = criteria.price_dropped_since {
+        query
+            .push(
+                " AND min_price < (SELECT p.min_price FROM event_prices p WHERE p.event_id = events.id AND p.currency = events.currency AND p.observed_at <= ",
+            )
+            .push_bind(since)
+            .push(" ORDER BY p.observed_at DESC LIMIT 1)");
+    }
+    if let Some(text) = &criteria.title_contains {
+        query
+            .push(" AND title ILIKE ")
+            .push_bind(format!("%{}%", escape_like_pattern(text)));
+    }
+    if let Some(min_price) = criteria.min_price {
+        query
+            .push(" AND currency = ")
+            .push_bind(min_price.currency().code().to_string())
+            .push(" AND max_price >= ")
+            .push_bind(min_price.amount_in_minor_units());
+    }
+    if let Some(max_price) = criteria.max_price {
+        query
+            .push(" AND currency = ")
+            .push_bind(max_price.currency().code().to_string())
+            .push(" AND min_price <= ")
+            .push_bind(max_price.amount_in_minor_units());
+    }
+    if let Some(sold_out) = criteria.sold_out {
+        query.push(" AND sold_out = ").push_bind(sold_out);
+    }
+    if let Some(sell_mode) = criteria.sell_mode {
+        query
+            .push(" AND sell_mode = ")
+            .push_bind(sell_mode.as_str());
+    }
+}
+
+fn sort_column(field: EventSortField) -> &'static str {
+    match field {
+        EventSortField::StartTime => "start_time",
+        EventSortField::MinPrice => "min_price",
+        EventSortField::MaxPrice => "max_price",
+        EventSortField::Title => "title",
+    }
+}
+
+fn push_sort_value(query: &mut QueryBuilder<'_, Postgres>, field: EventSortField, key: &EventKey) {
+    match field {
+        EventSortField::StartTime => query.push_bind(key.start_time),
+        EventSortField::MinPrice => query.push_bind(key.min_price),
+        EventSortField::MaxPrice => query.push_bind(key.max_price),
+        EventSortField::Title => query.push_bind(key.title.clone()),
+    };
+}
+
+/// Appends the `ORDER BY` clause of the sort, with every direction flipped when reversed
+fn push_sort_order(query: &mut QueryBuilder<'_, Postgres>, sort: &EventSort, reversed: bool) {
+    query.push(" ORDER BY ");
+    for key in &sort.0 {
+        query
+            .push(sort_column(key.field))
+            .push(if key.descending != reversed {
+                " DESC, "
+            } else {
+                ", "
+            });
+    }
+    query.push(if reversed { "id DESC" } else { "id" });
+}
+
+/// Appends the condition selecting the events that come after the key in the sort order, or
+/// before it when reversed
+fn push_keyset_condition(
+    query: &mut QueryBuilder<'_, Postgres>,
+    sort: &EventSort,
+    key: &EventKey,
+    reversed: bool,
+) {
+    let comparison = |descending: bool| if descending != reversed { " < " } else { " > " };
+
+    // A row comparison can use the index on (start_time, id), but only holds for a single direction
+    if sort.0.iter().all(|sort_key| !sort_key.descending) {
+        query.push(" AND (");
+        for sort_key in &sort.0 {
+            query.push(sort_column(sort_key.field)).push(", ");
+        }
+        query.push("id)").push(comparison(false)).push("(");
+        for sort_key in &sort.0 {
+            push_sort_value(query, sort_key.field, key);
+            query.push(", ");
+        }
+        query.push_bind(key.id).push(")");
+        return;
+    }
+
+    query.push(" AND (");
+    for (position, sort_key) in sort.0.iter().enumerate() {
+        for previous in &sort.0[..position] {
+            query.push(sort_column(previous.field)).push(" = ");
+            push_sort_value(query, previous.field, key);
+            query.push(" AND ");
         }
+        query
+            .push(sort_column(sort_key.field))
+            .push(comparison(sort_key.descending));
+        push_sort_value(query, sort_key.field, key);
+        query.push(" OR ");
     }
+    for previous in &sort.0 {
+        query.push(sort_column(previous.field)).push(" = ");
+        push_sort_value(query, previous.field, key);
+        query.push(" AND ");
+    }
+    query
+        .push("id")
+        .push(comparison(false))
+        .push_bind(key.id)
+        .push(")");
+}
+
+/// Rows estimated by the planner in a line of `EXPLAIN` output, e.g.
+/// `Seq Scan on events  (cost=0.00..1.09 rows=2 width=16)`
+fn planned_rows(line: &str) -> Option<u64> {
+    line.split("rows=").nth(1)?.split(' ').next()?.parse().ok()
+}
+
+/// Escapes the wildcards of `LIKE` patterns so that the text matches literally
+fn escape_like_pattern(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
 }
 
-fn postgres_events_into_domain_events(values: Vec<PostgresEvent>) -> Vec<Event> {
-    values.iter().map(|pe| pe.clone().into()).collect()
+fn postgres_events_into_domain_events(values: Vec<PostgresEvent>) -> Result<Vec<Event>> {
+    values.into_iter().map(Event::try_from).collect()
 }
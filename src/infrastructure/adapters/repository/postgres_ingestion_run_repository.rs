@@ -0,0 +1,705 @@
+use std::str::FromStr;
+
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use sqlx::pool::PoolConnection;
+use sqlx::{PgPool, Postgres};
+use uuid::Uuid;
+
+use crate::application::ports::provider::FeedValidators;
+use crate::application::ports::repository::{DeadLetter, IngestionRunRepository, SaveEventRequest};
+use crate::domain::event::{ExternalId, SellMode};
+use crate::domain::ingestion_run::{IngestionRejection, IngestionRun, IngestionRunState};
+use crate::domain::money::{Currency, Money};
+use crate::domain::zone::Zone;
+
+/// Key of the session-level advisory lock guarding ingestion across service instances
+const INGESTION_ADVISORY_LOCK_KEY: i64 = 0x6665_7272_6963;
+
+pub struct PostgresIngestionRunRepository(PgPool);
+
+impl PostgresIngestionRunRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self(pool)
+    }
+}
+
+impl IngestionRunRepository for PostgresIngestionRunRepository {
+    type IngestionLock = PostgresIngestionLock;
+
+    async fn try_acquire_ingestion_lock(&self) -> Result<Option<PostgresIngestionLock>> {
+        let mut connection = self
+            .0
+            .acquire()
+            .await
+            .context("Failed to acquire connection for ingestion lock in event database")?;
+        let acquired = sqlx::query_scalar!(
+            "SELECT pg_try_advisory_lock($1)",
+            INGESTION_ADVISORY_LOCK_KEY
+        )
+        .fetch_one(&mut *connection)
+        .await
+        .context("Failed to try ingestion lock in event database")?;
+
+        if acquired != Some(true) {
+            return Ok(None);
+        }
+        // Closing the session is what releases the lock, so the connection must not go back to the pool
+        connection.close_on_drop();
+
+        Ok(Some(PostgresIngestionLock {
+            _connection: connection,
+        }))
+    }
+
+    async fn save_run(&self, run: IngestionRun) -> Result<IngestionRun> {
+        let run = PostgresIngestionRun::try_from(run)?;
+        let query = sqlx::query!(
+            r#"
+                INSERT INTO ingestion_runs (id, state, provider, started_at, finished_at, fetched, inserted, updated, skipped, failed, cancelled, not_modified, rejected)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                ON CONFLICT (id) DO UPDATE
+                SET state = $2, provider = $3, started_at = $4, finished_at = $5, fetched = $6, inserted = $7, updated = $8, skipped = $9, failed = $10, cancelled = $11, not_modified = $12, rejected = $13
+            "#,
+            run.id,
+            run.state,
+            run.provider,
+            run.started_at,
+            run.finished_at,
+            run.fetched,
+            run.inserted,
+            run.updated,
+            run.skipped,
+            run.failed,
+            run.cancelled,
+            run.not_modified,
+            run.rejected,
+        );
+
+        query
+            .execute(&self.0)
+            .await
+            .context("Failed to save ingestion run record in event database")?;
+
+        self.find_run_by_id(&run.id)
+            .await
+            .context("Failed to save ingestion run in event database")?
+            .ok_or(anyhow!("Could not find saved ingestion run by its id"))
+            .context("Failed to save ingestion run in event database")
+    }
+
+    async fn find_run_by_id(&self, id: &Uuid) -> Result<Option<IngestionRun>> {
+        let query = sqlx::query_as!(
+            PostgresIngestionRun,
+            r#"
+                SELECT id, state, provider, started_at, finished_at, fetched, inserted, updated, skipped, failed, cancelled, not_modified, rejected
+                FROM ingestion_runs
+                WHERE id = $1
+            "#,
+            id,
+        );
+
+        query
+            .fetch_optional(&self.0)
+            .await
+            .context("Failed to find ingestion run by id in event database")
+            .and_then(|optional| optional.map(IngestionRun::try_from).transpose())
+    }
+
+    async fn find_running_run(&self) -> Result<Option<IngestionRun>> {
+        let query = sqlx::query_as!(
+            PostgresIngestionRun,
+            r#"
+                SELECT id, state, provider, started_at, finished_at, fetched, inserted, updated, skipped, failed, cancelled, not_modified, rejected
+                FROM ingestion_runs
+                WHERE state = 'running'
+                ORDER BY started_at DESC
+                LIMIT 1
+            "#,
+        );
+
+        query
+            .fetch_optional(&self.0)
+            .await
+            .context("Failed to find running ingestion run in event database")
+            .and_then(|optional| optional.map(IngestionRun::try_from).transpose())
+    }
+
+    async fn fail_running_runs(&self) -> Result<u64> {
+        let query = sqlx::query!(
+            r#"
+                UPDATE ingestion_runs
+                SET state = 'failed', finished_at = NOW()
+                WHERE state = 'running'
+            "#,
+        );
+
+        query
+            .execute(&self.0)
+            .await
+            .context("Failed to fail running ingestion run records in event database")
+            .map(|result| result.rows_affected())
+    }
+
+    async fn find_runs(&self, limit: u64, offset: u64) -> Result<Vec<IngestionRun>> {
+        let signed_limit: i64 = limit
+            .try_into()
+            .context("Failed to cast query limit from u64 to i64")?;
+        let signed_offset: i64 = offset
+            .try_into()
+            .context("Failed to cast query offset from u64 to i64")?;
+
+        let query = sqlx::query_as!(
+            PostgresIngestionRun,
+            r#"
+                SELECT id, state, provider, started_at, finished_at, fetched, inserted, updated, skipped, failed, cancelled, not_modified, rejected
+                FROM ingestion_runs
+                ORDER BY started_at DESC
+                LIMIT $1
+                OFFSET $2
+            "#,
+            signed_limit,
+            signed_offset,
+        );
+
+        query
+            .fetch_all(&self.0)
+            .await
+            .context("Failed to find ingestion runs in event database")?
+            .into_iter()
+            .map(IngestionRun::try_from)
+            .collect()
+    }
+
+    async fn find_feed_validators(&self, provider: &str) -> Result<Option<FeedValidators>> {
+        let query = sqlx::query_as!(
+            FeedValidators,
+            r#"
+                SELECT etag, last_modified
+                FROM provider_feed_validators
+                WHERE provider = $1
+            "#,
+            provider,
+        );
+
+        query
+            .fetch_optional(&self.0)
+            .await
+            .context("Failed to find provider feed validators in event database")
+    }
+
+    async fn save_feed_validators(
+        &self,
+        provider: &str,
+        validators: &FeedValidators,
+    ) -> Result<()> {
+        let query = sqlx::query!(
+            r#"
+                INSERT INTO provider_feed_validators (provider, etag, last_modified, updated_at)
+                VALUES ($1, $2, $3, now())
+                ON CONFLICT (provider) DO UPDATE
+                SET etag = $2, last_modified = $3, updated_at = now()
+            "#,
+            provider,
+            validators.etag,
+            validators.last_modified,
+        );
+
+        query
+            .execute(&self.0)
+            .await
+            .context("Failed to save provider feed validators in event database")?;
+
+        Ok(())
+    }
+
+    async fn save_rejections(&self, rejections: Vec<IngestionRejection>) -> Result<()> {
+        let mut ingestion_run_ids = Vec::with_capacity(rejections.len());
+        let mut providers = Vec::with_capacity(rejections.len());
+        let mut base_plan_ids = Vec::with_capacity(rejections.len());
+        let mut plan_ids = Vec::with_capacity(rejections.len());
+        let mut zone_ids = Vec::with_capacity(rejections.len());
+        let mut reasons = Vec::with_capacity(rejections.len());
+        let mut rejected_ats = Vec::with_capacity(rejections.len());
+        for rejection in rejections {
+            ingestion_run_ids.push(rejection.ingestion_run_id);
+            providers.push(rejection.provider);
+            base_plan_ids.push(rejection.base_plan_id);
+            plan_ids.push(rejection.plan_id);
+            zone_ids.push(rejection.zone_id);
+            reasons.push(rejection.reason);
+            rejected_ats.push(rejection.rejected_at);
+        }
+
+        let query = sqlx::query!(
+            r#"
+                INSERT INTO ingestion_rejections (ingestion_run_id, provider, base_plan_id, plan_id, zone_id, reason, rejected_at)
+                SELECT * FROM UNNEST($1::UUID[], $2::TEXT[], $3::TEXT[], $4::TEXT[], $5::TEXT[], $6::TEXT[], $7::TIMESTAMPTZ[])
+            "#,
+            &ingestion_run_ids,
+            &providers,
+            &base_plan_ids as &[Option<String>],
+            &plan_ids as &[Option<String>],
+            &zone_ids as &[Option<String>],
+            &reasons,
+            &rejected_ats,
+        );
+
+        query
+            .execute(&self.0)
+            .await
+            .context("Failed to save ingestion rejection records in event database")?;
+
+        Ok(())
+    }
+
+    async fn find_rejections_by_run_id(
+        &self,
+        run_id: &Uuid,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<IngestionRejection>> {
+        let signed_limit: i64 = limit
+            .try_into()
+            .context("Failed to cast query limit from u64 to i64")?;
+        let signed_offset: i64 = offset
+            .try_into()
+            .context("Failed to cast query offset from u64 to i64")?;
+
+        let query = sqlx::query_as!(
+            IngestionRejection,
+            r#"
+                SELECT ingestion_run_id, provider, base_plan_id, plan_id, zone_id, reason, rejected_at
+                FROM ingestion_rejections
+                WHERE ingestion_run_id = $1
+                ORDER BY id
+                LIMIT $2
+                OFFSET $3
+            "#,
+            run_id,
+            signed_limit,
+            signed_offset,
+        );
+
+        query
+            .fetch_all(&self.0)
+            .await
+            .context("Failed to find ingestion rejections by run id in event database")
+    }
+
+    async fn save_dead_letters(&self, dead_letters: Vec<DeadLetter>) -> Result<()> {
+        let mut ids = Vec::with_capacity(dead_letters.len());
+        let mut ingestion_run_ids = Vec::with_capacity(dead_letters.len());
+        let mut providers = Vec::with_capacity(dead_letters.len());
+        let mut base_plan_ids = Vec::with_capacity(dead_letters.len());
+        let mut plan_ids = Vec::with_capacity(dead_letters.len());
+        let mut payloads = Vec::with_capacity(dead_letters.len());
+        let mut errors = Vec::with_capacity(dead_letters.len());
+        let mut attempts = Vec::with_capacity(dead_letters.len());
+        let mut created_ats = Vec::with_capacity(dead_letters.len());
+        let mut last_failed_ats = Vec::with_capacity(dead_letters.len());
+        for dead_letter in dead_letters {
+            let dead_letter = PostgresDeadLetter::try_from(dead_letter)?;
+            ids.push(dead_letter.id);
+            ingestion_run_ids.push(dead_letter.ingestion_run_id);
+            providers.push(dead_letter.provider);
+            base_plan_ids.push(dead_letter.base_plan_id);
+            plan_ids.push(dead_letter.plan_id);
+            payloads.push(dead_letter.payload);
+            errors.push(dead_letter.error);
+            attempts.push(dead_letter.attempts);
+            created_ats.push(dead_letter.created_at);
+            last_failed_ats.push(dead_letter.last_failed_at);
+        }
+
+        let query = sqlx::query!(
+            r#"
+                INSERT INTO event_dead_letters (id, ingestion_run_id, provider, base_plan_id, plan_id, payload, error, attempts, created_at, last_failed_at)
+                SELECT id, ingestion_run_id, provider, base_plan_id, plan_id, payload::JSONB, error, attempts, created_at, last_failed_at
+                FROM UNNEST($1::UUID[], $2::UUID[], $3::TEXT[], $4::TEXT[], $5::TEXT[], $6::TEXT[], $7::TEXT[], $8::INTEGER[], $9::TIMESTAMPTZ[], $10::TIMESTAMPTZ[])
+                    AS dead_letter(id, ingestion_run_id, provider, base_plan_id, plan_id, payload, error, attempts, created_at, last_failed_at)
+                ON CONFLICT (provider, base_plan_id, plan_id) DO UPDATE
+                SET ingestion_run_id = EXCLUDED.ingestion_run_id,
+                    payload = EXCLUDED.payload,
+                    error = EXCLUDED.error,
+                    attempts = event_dead_letters.attempts + EXCLUDED.attempts,
+                    last_failed_at = EXCLUDED.last_failed_at
+            "#,
+            &ids,
+            &ingestion_run_ids,
+            &providers as &[Option<String>],
+            &base_plan_ids as &[Option<String>],
+            &plan_ids as &[Option<String>],
+            &payloads,
+            &errors,
+            &attempts,
+            &created_ats,
+            &last_failed_ats,
+        );
+
+        query
+            .execute(&self.0)
+            .await
+            .context("Failed to save dead letter records in event database")?;
+
+        Ok(())
+    }
+
+    async fn find_dead_letter_by_id(&self, id: &Uuid) -> Result<Option<DeadLetter>> {
+        let query = sqlx::query_as!(
+            PostgresDeadLetter,
+            r#"
+                SELECT id, ingestion_run_id, provider, base_plan_id, plan_id, payload::TEXT AS "payload!", error, attempts, created_at, last_failed_at
+                FROM event_dead_letters
+                WHERE id = $1
+            "#,
+            id,
+        );
+
+        query
+            .fetch_optional(&self.0)
+            .await
+            .context("Failed to find dead letter by id in event database")
+            .and_then(|optional| optional.map(DeadLetter::try_from).transpose())
+    }
+
+    async fn find_dead_letters(&self, limit: u64, offset: u64) -> Result<Vec<DeadLetter>> {
+        let signed_limit: i64 = limit
+            .try_into()
+            .context("Failed to cast query limit from u64 to i64")?;
+        let signed_offset: i64 = offset
+            .try_into()
+            .context("Failed to cast query offset from u64 to i64")?;
+
+        let query = sqlx::query_as!(
+            PostgresDeadLetter,
+            r#"
+                SELECT id, ingestion_run_id, provider, base_plan_id, plan_id, payload::TEXT AS "payload!", error, attempts, created_at, last_failed_at
+                FROM event_dead_letters
+                ORDER BY created_at, id
+                LIMIT $1
+                OFFSET $2
+            "#,
+            signed_limit,
+            signed_offset,
+        );
+
+        query
+            .fetch_all(&self.0)
+            .await
+            .context("Failed to find dead letters in event database")?
+            .into_iter()
+            .map(DeadLetter::try_from)
+            .collect()
+    }
+
+    async fn record_dead_letter_failure(
+        &self,
+        id: &Uuid,
+        error: &str,
+    ) -> Result<Option<DeadLetter>> {
+        let query = sqlx::query_as!(
+            PostgresDeadLetter,
+            r#"
+                UPDATE event_dead_letters
+                SET attempts = attempts + 1, error = $2, last_failed_at = NOW()
+                WHERE id = $1
+                RETURNING id, ingestion_run_id, provider, base_plan_id, plan_id, payload::TEXT AS "payload!", error, attempts, created_at, last_failed_at
+            "#,
+            id,
+            error,
+        );
+
+        query
+            .fetch_optional(&self.0)
+            .await
+            .context("Failed to record dead letter failure in event database")
+            .and_then(|optional| optional.map(DeadLetter::try_from).transpose())
+    }
+
+    async fn delete_dead_letter(&self, id: &Uuid) -> Result<()> {
+        let query = sqlx::query!(
+            r#"
+                DELETE FROM event_dead_letters
+                WHERE id = $1
+            "#,
+            id,
+        );
+
+        query
+            .execute(&self.0)
+            .await
+            .context("Failed to delete dead letter record in event database")?;
+
+        Ok(())
+    }
+
+    async fn delete_dead_letters_by_external_ids(
+        &self,
+        external_ids: &[ExternalId],
+    ) -> Result<u64> {
+        let mut providers = Vec::with_capacity(external_ids.len());
+        let mut base_plan_ids = Vec::with_capacity(external_ids.len());
+        let mut plan_ids = Vec::with_capacity(external_ids.len());
+        for external_id in external_ids {
+            providers.push(external_id.provider.clone());
+            base_plan_ids.push(external_id.base_plan_id.clone());
+            plan_ids.push(external_id.plan_id.clone());
+        }
+
+        let query = sqlx::query!(
+            r#"
+                DELETE FROM event_dead_letters
+                WHERE (provider, base_plan_id, plan_id) IN (
+                    SELECT * FROM UNNEST($1::TEXT[], $2::TEXT[], $3::TEXT[])
+                )
+            "#,
+            &providers,
+            &base_plan_ids,
+            &plan_ids,
+        );
+
+        let result = query
+            .execute(&self.0)
+            .await
+            .context("Failed to delete dead letter records by external id in event database")?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+/// Advisory lock held by a dedicated database session for the duration of an ingestion run
+pub struct PostgresIngestionLock {
+    _connection: PoolConnection<Postgres>,
+}
+
+#[derive(sqlx::FromRow)]
+struct PostgresIngestionRun {
+    id: Uuid,
+    state: String,
+    provider: Option<String>,
+    started_at: DateTime<Utc>,
+    finished_at: Option<DateTime<Utc>>,
+    fetched: i32,
+    inserted: i32,
+    updated: i32,
+    skipped: i32,
+    failed: i32,
+    cancelled: i32,
+    not_modified: i32,
+    rejected: i32,
+}
+
+impl TryFrom<PostgresIngestionRun> for IngestionRun {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PostgresIngestionRun) -> Result<Self> {
+        let count = |value: i32| {
+            u32::try_from(value).context("Failed to map ingestion run record from event database")
+        };
+
+        Ok(IngestionRun {
+            id: value.id,
+            state: IngestionRunState::from_str(&value.state)
+                .context("Failed to map ingestion run record from event database")?,
+            provider: value.provider,
+            started_at: value.started_at,
+            finished_at: value.finished_at,
+            fetched: count(value.fetched)?,
+            inserted: count(value.inserted)?,
+            updated: count(value.updated)?,
+            skipped: count(value.skipped)?,
+            failed: count(value.failed)?,
+            cancelled: count(value.cancelled)?,
+            not_modified: count(value.not_modified)?,
+            rejected: count(value.rejected)?,
+        })
+    }
+}
+
+impl TryFrom<IngestionRun> for PostgresIngestionRun {
+    type Error = anyhow::Error;
+
+    fn try_from(value: IngestionRun) -> Result<Self> {
+        let count = |value: u32| {
+            i32::try_from(value).context("Failed to cast ingestion run count from u32 to i32")
+        };
+
+        Ok(Self {
+            id: value.id,
+            state: value.state.to_string(),
+            provider: value.provider,
+            started_at: value.started_at,
+            finished_at: value.finished_at,
+            fetched: count(value.fetched)?,
+            inserted: count(value.inserted)?,
+            updated: count(value.updated)?,
+            skipped: count(value.skipped)?,
+            failed: count(value.failed)?,
+            cancelled: count(value.cancelled)?,
+            not_modified: count(value.not_modified)?,
+            rejected: count(value.rejected)?,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PostgresDeadLetter {
+    id: Uuid,
+    ingestion_run_id: Uuid,
+    provider: Option<String>,
+    base_plan_id: Option<String>,
+    plan_id: Option<String>,
+    /// JSON serialized [`DeadLetterPayload`]
+    payload: String,
+    error: String,
+    attempts: i32,
+    created_at: DateTime<Utc>,
+    last_failed_at: DateTime<Utc>,
+}
+
+/// Attributes of a dead letter's write request besides its external id, stored as JSON
+#[derive(Serialize, Deserialize)]
+struct DeadLetterPayload {
+    title: String,
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+    time_zone: String,
+    min_price_in_lowest_denomination: i64,
+    max_price_in_lowest_denomination: i64,
+    currency: String,
+    sell_mode: String,
+    organizer_company_id: Option<String>,
+    sell_from: DateTime<Utc>,
+    sell_to: DateTime<Utc>,
+    sold_out: bool,
+    zones: Vec<DeadLetterZone>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DeadLetterZone {
+    zone_id: String,
+    name: String,
+    price_in_lowest_denomination: i64,
+    currency: String,
+    capacity: u32,
+    numbered: bool,
+}
+
+impl TryFrom<PostgresDeadLetter> for DeadLetter {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PostgresDeadLetter) -> Result<Self> {
+        let context = "Failed to map dead letter record from event database";
+        let external_id = match (value.provider, value.base_plan_id, value.plan_id) {
+            (Some(provider), Some(base_plan_id), Some(plan_id)) => Some(ExternalId {
+                provider,
+                base_plan_id,
+                plan_id,
+            }),
+            _ => None,
+        };
+        let payload: DeadLetterPayload = serde_json::from_str(&value.payload).context(context)?;
+        let currency = Currency::from_str(&payload.currency).context(context)?;
+        let zones = payload
+            .zones
+            .into_iter()
+            .map(|zone| {
+                Ok(Zone {
+                    zone_id: zone.zone_id,
+                    name: zone.name,
+                    price: Money::new(
+                        zone.price_in_lowest_denomination,
+                        Currency::from_str(&zone.currency)?,
+                    ),
+                    capacity: zone.capacity,
+                    numbered: zone.numbered,
+                })
+            })
+            .collect::<Result<Vec<Zone>>>()
+            .context(context)?;
+
+        Ok(DeadLetter {
+            id: value.id,
+            ingestion_run_id: value.ingestion_run_id,
+            request: SaveEventRequest {
+                external_id,
+                title: payload.title,
+                start_time: payload.start_time,
+                end_time: payload.end_time,
+                time_zone: Tz::from_str(&payload.time_zone).context(context)?,
+                min_price: Money::new(payload.min_price_in_lowest_denomination, currency),
+                max_price: Money::new(payload.max_price_in_lowest_denomination, currency),
+                sell_mode: SellMode::from_str(&payload.sell_mode).context(context)?,
+                organizer_company_id: payload.organizer_company_id,
+                sell_from: payload.sell_from,
+                sell_to: payload.sell_to,
+                sold_out: payload.sold_out,
+                zones,
+            },
+            error: value.error,
+            attempts: u32::try_from(value.attempts).context(context)?,
+            created_at: value.created_at,
+            last_failed_at: value.last_failed_at,
+        })
+    }
+}
+
+impl TryFrom<DeadLetter> for PostgresDeadLetter {
+    type Error = anyhow::Error;
+
+    fn try_from(value: DeadLetter) -> Result<Self> {
+        let request = value.request;
+        let (provider, base_plan_id, plan_id) = match request.external_id {
+            Some(external_id) => (
+                Some(external_id.provider),
+                Some(external_id.base_plan_id),
+                Some(external_id.plan_id),
+            ),
+            None => (None, None, None),
+        };
+        let payload = DeadLetterPayload {
+            title: request.title,
+            start_time: request.start_time,
+            end_time: request.end_time,
+            time_zone: request.time_zone.to_string(),
+            min_price_in_lowest_denomination: request.min_price.amount_in_minor_units(),
+            max_price_in_lowest_denomination: request.max_price.amount_in_minor_units(),
+            currency: request.min_price.currency().to_string(),
+            sell_mode: request.sell_mode.to_string(),
+            organizer_company_id: request.organizer_company_id,
+            sell_from: request.sell_from,
+            sell_to: request.sell_to,
+            sold_out: request.sold_out,
+            zones: request
+                .zones
+                .into_iter()
+                .map(|zone| DeadLetterZone {
+                    zone_id: zone.zone_id,
+                    name: zone.name,
+                    price_in_lowest_denomination: zone.price.amount_in_minor_units(),
+                    currency: zone.price.currency().to_string(),
+                    capacity: zone.capacity,
+                    numbered: zone.numbered,
+                })
+                .collect(),
+        };
+
+        Ok(Self {
+            id: value.id,
+            ingestion_run_id: value.ingestion_run_id,
+            provider,
+            base_plan_id,
+            plan_id,
+            payload: serde_json::to_string(&payload)
+                .context("Failed to serialize dead letter payload")?,
+            error: value.error,
+            attempts: i32::try_from(value.attempts)
+                .context("Failed to cast dead letter attempts from u32 to i32")?,
+            created_at: value.created_at,
+            last_failed_at: value.last_failed_at,
+        })
+    }
+}
@@ -0,0 +1,150 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use cron::Schedule;
+use log::{error, info};
+use tokio::sync::watch;
+
+use crate::application::ports::provider::EventProviderClient;
+use crate::application::ports::repository::{EventRepository, IngestionRunRepository};
+use crate::application::service::{IngestEventService, IngestEventServiceError};
+
+/// When scheduled ingestion runs: a fixed interval (e.g. `30m`) or a cron expression with seconds
+#[derive(Clone, Debug)]
+pub enum IngestionSchedule {
+    Interval(Duration),
+    Cron(Box<Schedule>, Tz),
+}
+
+impl IngestionSchedule {
+    pub fn parse(value: &str, time_zone: Tz) -> Result<Self> {
+        if let Ok(interval) = humantime::parse_duration(value) {
+            anyhow::ensure!(!interval.is_zero(), "Ingestion interval must not be zero");
+            return Ok(Self::Interval(interval));
+        }
+
+        let schedule = Schedule::from_str(value)
+            .with_context(|| format!("Invalid ingestion schedule: {value}"))?;
+        Ok(Self::Cron(Box::new(schedule), time_zone))
+    }
+
+    /// Time left from `now` until the next tick, or `None` if the schedule never fires again
+    fn next_delay(&self, now: DateTime<Utc>) -> Option<Duration> {
+        match self {
+            Self::Interval(interval) => Some(*interval),
+            Self::Cron(schedule, time_zone) => schedule
+                .after(&now.with_timezone(time_zone))
+                .next()
+                .map(|next| {
+                    (next.with_timezone(&Utc) - now)
+                        .to_std()
+                        .unwrap_or_default()
+                }),
+        }
+    }
+}
+
+pub struct IngestionScheduler<T, S, R>
+where
+    T: EventProviderClient,
+    S: EventRepository,
+    R: IngestionRunRepository,
+{
+    schedule: IngestionSchedule,
+    max_jitter: Duration,
+    ingest_event_service: Arc<IngestEventService<T, S, R>>,
+}
+
+impl<T, S, R> IngestionScheduler<T, S, R>
+where
+    T: EventProviderClient + Send + Sync + 'static,
+    S: EventRepository + Send + Sync + 'static,
+    R: IngestionRunRepository + Send + Sync + 'static,
+{
+    pub fn new(
+        schedule: IngestionSchedule,
+        max_jitter: Duration,
+        ingest_event_service: Arc<IngestEventService<T, S, R>>,
+    ) -> Self {
+        Self {
+            schedule,
+            max_jitter,
+            ingest_event_service,
+        }
+    }
+
+    /// Triggers ingestion on every tick until `shutdown` flips to true
+    pub async fn run(self, mut shutdown: watch::Receiver<bool>) {
+        info!(
+            "Ingestion scheduler started with schedule {:?}",
+            self.schedule
+        );
+        loop {
+            let Some(delay) = self.schedule.next_delay(Utc::now()) else {
+                info!("Ingestion schedule has no upcoming ticks");
+                break;
+            };
+            let delay = delay + self.jitter();
+
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = shutdown.wait_for(|stop| *stop) => break,
+            }
+
+            match self.ingest_event_service.ingest_events(None).await {
+                Ok(run) => info!("Scheduled ingestion started as job {}", run.id),
+                Err(IngestEventServiceError::AlreadyRunning(job_id)) => {
+                    info!("Skipping scheduled ingestion: a run is still active ({job_id:?})")
+                }
+                Err(
+                    IngestEventServiceError::UnknownProvider(_)
+                    | IngestEventServiceError::Unexpected,
+                ) => {
+                    error!("Scheduled ingestion could not be started")
+                }
+            }
+        }
+        info!("Ingestion scheduler stopped");
+    }
+
+    fn jitter(&self) -> Duration {
+        if self.max_jitter.is_zero() {
+            return Duration::ZERO;
+        }
+        Duration::from_millis(rand::random_range(
+            0..=self.max_jitter.as_millis().try_into().unwrap_or(u64::MAX),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interval_schedules_fire_after_their_interval() {
+        let schedule = IngestionSchedule::parse("15m", Tz::UTC).unwrap();
+
+        assert_eq!(
+            schedule.next_delay(DateTime::from_str("2025-11-12T02:07:00Z").unwrap()),
+            Some(Duration::from_secs(15 * 60))
+        );
+        assert!(IngestionSchedule::parse("0s", Tz::UTC).is_err());
+    }
+
+    #[test]
+    fn cron_schedules_fire_at_next_matching_time_in_their_time_zone() {
+        let schedule = IngestionSchedule::parse("0 0 3 * * *", Tz::Europe__Madrid).unwrap();
+
+        // 03:00 in Madrid is 02:00 UTC in November
+        assert_eq!(
+            schedule.next_delay(DateTime::from_str("2025-11-12T01:30:00Z").unwrap()),
+            Some(Duration::from_secs(30 * 60))
+        );
+        assert!(IngestionSchedule::parse("every night", Tz::UTC).is_err());
+    }
+}
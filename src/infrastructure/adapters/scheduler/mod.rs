@@ -0,0 +1,2 @@
+mod ingestion_scheduler;
+pub use ingestion_scheduler::{IngestionSchedule, IngestionScheduler};
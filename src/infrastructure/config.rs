@@ -1,14 +1,25 @@
+use std::collections::BTreeMap;
+
 use config::Environment;
 use serde::Deserialize;
 
+use crate::infrastructure::adapters::provider::JsonFeedShape;
+
 #[derive(Clone, Debug, Deserialize, Default)]
 #[allow(dead_code)]
 pub struct ApplicationConfig {
     pub config: ConfigInfo,
     pub port: Option<u16>,
     pub database: Database,
-    pub event_provider_client: EventProviderClient,
+    /// Single provider named by its `name`, kept alongside `event_providers` for compatibility
+    #[serde(default)]
+    pub event_provider_client: Option<EventProviderClient>,
+    /// Providers keyed by their name
+    #[serde(default)]
+    pub event_providers: BTreeMap<String, EventProviderClient>,
     pub api: Api,
+    #[serde(default)]
+    pub ingestion: Ingestion,
 }
 
 #[derive(Clone, Debug, Deserialize, Default)]
@@ -25,14 +36,188 @@ pub struct Database {
 
 #[derive(Clone, Debug, Deserialize, Default)]
 pub struct EventProviderClient {
+    #[serde(default = "default_event_provider_name")]
+    pub name: String,
+    #[serde(default = "default_event_provider_currency")]
+    pub currency: String,
+    #[serde(default = "default_event_provider_time_zone")]
+    pub time_zone: String,
+    /// HTTP base URL, or a `file://` URL of a feed file or directory of feed files
     pub url: String,
+    #[serde(default)]
     pub api_path: String,
+    /// Seconds that connecting to the provider, and each read of its response, may take
+    #[serde(default = "default_event_provider_request_timeout")]
     pub request_timeout: u64,
+    /// Feed format, `xml` (default) or `json`
+    #[serde(default = "default_event_provider_format")]
+    pub format: String,
+    /// Shape of the feed when its format is `json`
+    #[serde(default)]
+    pub json: JsonFeedShape,
+    #[serde(default)]
+    pub retry: Retry,
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreaker,
 }
 
-#[derive(Clone, Debug, Deserialize, Default)]
+fn default_event_provider_name() -> String {
+    "default".to_string()
+}
+
+fn default_event_provider_currency() -> String {
+    "EUR".to_string()
+}
+
+fn default_event_provider_time_zone() -> String {
+    "Europe/Madrid".to_string()
+}
+
+fn default_event_provider_request_timeout() -> u64 {
+    10
+}
+
+fn default_event_provider_format() -> String {
+    "xml".to_string()
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Retry {
+    /// Attempts per fetch, including the first one
+    #[serde(default = "default_retry_max_attempts")]
+    pub max_attempts: u32,
+    #[serde(default = "default_retry_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    #[serde(default = "default_retry_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    /// Comma-separated HTTP status codes worth retrying
+    #[serde(default = "default_retry_statuses")]
+    pub statuses: String,
+    #[serde(default = "default_true")]
+    pub on_timeout: bool,
+    #[serde(default = "default_true")]
+    pub on_connect_error: bool,
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_retry_max_attempts(),
+            initial_backoff_ms: default_retry_initial_backoff_ms(),
+            max_backoff_ms: default_retry_max_backoff_ms(),
+            statuses: default_retry_statuses(),
+            on_timeout: true,
+            on_connect_error: true,
+        }
+    }
+}
+
+fn default_retry_max_attempts() -> u32 {
+    3
+}
+
+fn default_retry_initial_backoff_ms() -> u64 {
+    500
+}
+
+fn default_retry_max_backoff_ms() -> u64 {
+    10_000
+}
+
+fn default_retry_statuses() -> String {
+    "408,429,500,502,503,504".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CircuitBreaker {
+    /// Consecutive failed fetches that open the circuit
+    #[serde(default = "default_circuit_breaker_failure_threshold")]
+    pub failure_threshold: u32,
+    /// How long an open circuit rejects fetches before letting a trial through
+    #[serde(default = "default_circuit_breaker_open_secs")]
+    pub open_secs: u64,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self {
+            failure_threshold: default_circuit_breaker_failure_threshold(),
+            open_secs: default_circuit_breaker_open_secs(),
+        }
+    }
+}
+
+fn default_circuit_breaker_failure_threshold() -> u32 {
+    5
+}
+
+fn default_circuit_breaker_open_secs() -> u64 {
+    60
+}
+
+#[derive(Clone, Debug, Deserialize)]
 pub struct Api {
     pub request_timeout_secs: u64,
+    /// Search totals above it are estimated rather than counted
+    #[serde(default = "default_exact_total_limit")]
+    pub exact_total_limit: u64,
+}
+
+impl Default for Api {
+    fn default() -> Self {
+        Self {
+            request_timeout_secs: 0,
+            exact_total_limit: default_exact_total_limit(),
+        }
+    }
+}
+
+fn default_exact_total_limit() -> u64 {
+    10_000
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Ingestion {
+    /// Interval (e.g. `30m`) or cron expression with seconds; scheduled ingestion is off when unset
+    pub schedule: Option<String>,
+    #[serde(default)]
+    pub schedule_jitter_secs: u64,
+    #[serde(default = "default_ingestion_schedule_time_zone")]
+    pub schedule_time_zone: String,
+    /// Consecutive runs an event may be missing from the provider's feed before it is cancelled
+    #[serde(default = "default_cancel_after_missing_runs")]
+    pub cancel_after_missing_runs: u32,
+    /// Provider events written per event store transaction
+    #[serde(default = "default_ingestion_batch_size")]
+    pub batch_size: usize,
+}
+
+impl Default for Ingestion {
+    fn default() -> Self {
+        Self {
+            schedule: None,
+            schedule_jitter_secs: 0,
+            schedule_time_zone: default_ingestion_schedule_time_zone(),
+            cancel_after_missing_runs: default_cancel_after_missing_runs(),
+            batch_size: default_ingestion_batch_size(),
+        }
+    }
+}
+
+fn default_ingestion_schedule_time_zone() -> String {
+    "UTC".to_string()
+}
+
+fn default_cancel_after_missing_runs() -> u32 {
+    3
+}
+
+fn default_ingestion_batch_size() -> usize {
+    500
 }
 
 impl ApplicationConfig {
@@ -50,4 +235,24 @@ impl ApplicationConfig {
 
         Ok(config)
     }
+
+    /// Every configured event provider, named and in a stable order
+    pub fn event_provider_configs(&self) -> anyhow::Result<Vec<EventProviderClient>> {
+        let mut providers: Vec<EventProviderClient> =
+            self.event_provider_client.iter().cloned().collect();
+        for (name, provider) in &self.event_providers {
+            if providers.iter().any(|p| &p.name == name) {
+                anyhow::bail!("Event provider {name} is configured more than once");
+            }
+            providers.push(EventProviderClient {
+                name: name.clone(),
+                ..provider.clone()
+            });
+        }
+        if providers.is_empty() {
+            anyhow::bail!("No event provider is configured");
+        }
+
+        Ok(providers)
+    }
 }
@@ -3,18 +3,27 @@ mod config;
 
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
+    str::FromStr,
     sync::Arc,
     time::Duration,
 };
 
 use anyhow::Context;
+use chrono_tz::Tz;
 use log::{debug, info};
+use reqwest::StatusCode;
 use sqlx::postgres::PgPoolOptions;
-use tokio::{net::TcpListener, signal};
+use tokio::{net::TcpListener, signal, sync::watch};
 use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt};
 
 use crate::{
-    application::service::{IngestEventService, SearchEventService},
+    application::service::{IngestEventService, IngestionSettings, SearchEventService},
+    domain::money::Currency,
+    infrastructure::adapters::provider::{
+        CircuitBreaker, ConfiguredEventProviderClient, FileEventProviderClient,
+        HttpEventProviderClient, JsonEventProviderClient, RetryPolicy,
+    },
+    infrastructure::adapters::scheduler::{IngestionSchedule, IngestionScheduler},
     infrastructure::config::ApplicationConfig,
 };
 
@@ -60,27 +69,63 @@ pub fn serve_app(config: ApplicationConfig) -> anyhow::Result<()> {
                 .await
                 .context("Failed to create database connection pool")?;
             info!("Database connection pool established");
-            let event_repository = adapters::repository::PostgresEventRepository::new(pool);
-
-            let client = reqwest::Client::builder()
-                .timeout(Duration::from_secs(
-                    config.event_provider_client.request_timeout,
-                ))
-                .build()
-                .context("Failed to create HTTP client for event provider client")?;
-            let event_provider_client = adapters::provider::HttpEventProviderClient::new(
-                config.event_provider_client.url.clone(),
-                config.event_provider_client.api_path.clone(),
-                client,
-            );
-            info!("Event Provider client initialized successfully");
+            let event_repository = adapters::repository::PostgresEventRepository::new(pool.clone());
+            let ingestion_run_repository =
+                adapters::repository::PostgresIngestionRunRepository::new(pool);
+
+            let event_provider_clients = config
+                .event_provider_configs()?
+                .into_iter()
+                .map(|provider_config| {
+                    let event_provider_client = build_event_provider_client(&provider_config)
+                        .with_context(|| {
+                            format!(
+                                "Failed to initialize client of event provider {}",
+                                provider_config.name
+                            )
+                        })?;
+                    info!(
+                        "Event Provider client {} initialized successfully",
+                        provider_config.name
+                    );
+                    Ok(Arc::new(event_provider_client))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
 
             // Dependency Injection
             let shared_event_repository = Arc::new(event_repository);
-            let shared_event_provider_client = Arc::new(event_provider_client);
-            let search_event_service = SearchEventService::new(shared_event_repository.clone());
-            let ingest_event_service =
-                IngestEventService::new(shared_event_provider_client, shared_event_repository);
+            let search_event_service = SearchEventService::new(shared_event_repository.clone())
+                .with_exact_total_limit(config.api.exact_total_limit);
+            let ingest_event_service = Arc::new(IngestEventService::new(
+                event_provider_clients,
+                shared_event_repository,
+                Arc::new(ingestion_run_repository),
+                IngestionSettings {
+                    cancel_after_missing_runs: config.ingestion.cancel_after_missing_runs,
+                    batch_size: config.ingestion.batch_size,
+                },
+            ));
+
+            // Scheduler
+            let (shutdown_sender, shutdown_receiver) = watch::channel(false);
+            let scheduler = match &config.ingestion.schedule {
+                Some(schedule) => {
+                    let time_zone = Tz::from_str(&config.ingestion.schedule_time_zone)
+                        .context("Failed to parse time zone of ingestion schedule")?;
+                    let schedule = IngestionSchedule::parse(schedule, time_zone)
+                        .context("Failed to parse ingestion schedule")?;
+                    let scheduler = IngestionScheduler::new(
+                        schedule,
+                        Duration::from_secs(config.ingestion.schedule_jitter_secs),
+                        ingest_event_service.clone(),
+                    );
+                    Some(tokio::spawn(scheduler.run(shutdown_receiver.clone())))
+                }
+                None => {
+                    info!("Scheduled ingestion is disabled");
+                    None
+                }
+            };
 
             // Controller
             let app = adapters::controller::init_controller(
@@ -97,9 +142,21 @@ pub fn serve_app(config: ApplicationConfig) -> anyhow::Result<()> {
             let listener = TcpListener::bind(addr).await?;
             info!("Server listening at {addr:?}");
 
+            let mut server_shutdown = shutdown_receiver;
+            tokio::spawn(async move {
+                shutdown_signal().await;
+                shutdown_sender.send_replace(true);
+            });
             axum::serve(listener, app)
-                .with_graceful_shutdown(shutdown_signal())
+                .with_graceful_shutdown(async move {
+                    server_shutdown.wait_for(|stop| *stop).await.ok();
+                })
                 .await?;
+            if let Some(scheduler) = scheduler {
+                scheduler
+                    .await
+                    .context("Failed to stop ingestion scheduler")?;
+            }
 
             Ok::<(), anyhow::Error>(())
         })?;
@@ -107,6 +164,77 @@ pub fn serve_app(config: ApplicationConfig) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn build_event_provider_client(
+    provider_config: &config::EventProviderClient,
+) -> anyhow::Result<ConfiguredEventProviderClient> {
+    let currency = Currency::from_str(&provider_config.currency)
+        .context("Failed to parse currency of event provider client")?;
+    let time_zone = Tz::from_str(&provider_config.time_zone)
+        .context("Failed to parse time zone of event provider client")?;
+    if provider_config.url.starts_with("file:") {
+        if provider_config.format != "xml" {
+            anyhow::bail!("Event feed files must be in xml format");
+        }
+        let url = format!("{}{}", provider_config.url, provider_config.api_path);
+        return Ok(ConfiguredEventProviderClient::File(
+            FileEventProviderClient::new(
+                provider_config.name.clone(),
+                currency,
+                time_zone,
+                FileEventProviderClient::path_from_url(&url)?,
+            ),
+        ));
+    }
+
+    let client =
+        HttpEventProviderClient::http_client(Duration::from_secs(provider_config.request_timeout))?;
+    let retry_config = &provider_config.retry;
+    let retry_policy = RetryPolicy {
+        max_attempts: retry_config.max_attempts.max(1),
+        initial_backoff: Duration::from_millis(retry_config.initial_backoff_ms),
+        max_backoff: Duration::from_millis(retry_config.max_backoff_ms),
+        retryable_statuses: retry_config
+            .statuses
+            .split(',')
+            .map(|status| StatusCode::from_str(status.trim()))
+            .collect::<Result<_, _>>()
+            .context("Failed to parse retryable statuses of event provider client")?,
+        retry_on_timeout: retry_config.on_timeout,
+        retry_on_connect_error: retry_config.on_connect_error,
+    };
+    let circuit_breaker = CircuitBreaker::new(
+        provider_config.circuit_breaker.failure_threshold,
+        Duration::from_secs(provider_config.circuit_breaker.open_secs),
+    );
+
+    match provider_config.format.as_str() {
+        "xml" => Ok(ConfiguredEventProviderClient::Xml(
+            HttpEventProviderClient::new(
+                provider_config.name.clone(),
+                currency,
+                time_zone,
+                provider_config.url.clone(),
+                provider_config.api_path.clone(),
+                client,
+            )
+            .with_resilience(retry_policy, circuit_breaker),
+        )),
+        "json" => Ok(ConfiguredEventProviderClient::Json(Box::new(
+            JsonEventProviderClient::new(
+                provider_config.name.clone(),
+                currency,
+                time_zone,
+                provider_config.url.clone(),
+                provider_config.api_path.clone(),
+                client,
+                provider_config.json.clone(),
+            )
+            .with_resilience(retry_policy, circuit_breaker),
+        ))),
+        format => anyhow::bail!("Unsupported event provider format {format}"),
+    }
+}
+
 async fn shutdown_signal() {
     let ctrl_c = async {
         signal::ctrl_c()
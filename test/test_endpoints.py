@@ -18,6 +18,7 @@ def test_search_returns_events_within_time_range():
     params = {
         "start_time": "2025-11-01T08:00:00Z",
         "end_time": "2025-11-30T18:00:00Z",
+        "tz": "Europe/Madrid",
         "limit": 2,
     }
 
@@ -31,11 +32,17 @@ def test_search_returns_events_within_time_range():
                     "id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
                     "title": "Quevedo",
                     "start_date": "2025-11-12",
-                    "start_time": "22:00:00",
-                    "end_date": "2025-11-12",
-                    "end_time": "23:00:00",
-                    "min_price": 15.99,
-                    "max_price": 39.99,
+                    "start_time": "23:00:00+01:00",
+                    "end_date": "2025-11-13",
+                    "end_time": "00:00:00+01:00",
+                    "time_zone": "Europe/Madrid",
+                    "min_price": {"amount": "15.99", "currency": "EUR"},
+                    "max_price": {"amount": "39.99", "currency": "EUR"},
+                    "sell_mode": "online",
+                    "organizer_company_id": None,
+                    "sell_from": "2025-06-01T02:00:00+02:00",
+                    "sell_to": "2025-11-12T21:00:00+01:00",
+                    "sold_out": False,
                 }
             ]
         },
@@ -71,7 +78,19 @@ def test_ingest_starts_event_data_ingestion():
 
     print(f"Response status is {resp.status_code}")
     assert resp.status_code == 202
-    assert resp.text == ""
+    job_id = resp.json()["data"]["job_id"]
+    assert resp.headers["Location"] == f"/api/v1/ingest/{job_id}"
+
+    return job_id
+
+
+def test_ingestion_job_is_tracked(job_id):
+    resp = requests.get(f"{base_url}/api/v1/ingest/{job_id}")
+
+    print(f"Response is {resp.json()}")
+    assert resp.status_code == 200
+    assert resp.json()["data"]["job_id"] == job_id
+    assert resp.json()["data"]["state"] in ("running", "succeeded", "failed")
 
 
 if __name__ == "__main__":
@@ -80,7 +99,8 @@ if __name__ == "__main__":
     test_search_returns_events_within_time_range()
     test_search_returns_client_error_when_required_param_is_missing()
 
-    test_ingest_starts_event_data_ingestion()
+    job_id = test_ingest_starts_event_data_ingestion()
+    test_ingestion_job_is_tracked(job_id)
     test_search_returns_events_within_time_range()
     test_search_returns_events_within_time_range()
     test_search_returns_client_error_when_required_param_is_missing()